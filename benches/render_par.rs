@@ -0,0 +1,46 @@
+//! `render`与`render_par`的耗时比较
+//!
+//! ```text
+//! cargo bench --features rayon --bench render_par
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use htmlbuilder::Element;
+
+/// 多个大型并列区块组成的页面
+fn page(sections: usize, rows: usize) -> Element {
+    let body = Element::new("body", "");
+    for s in 0..sections {
+        let section = Element::new("section", "").attrs(&[("class", "block")]);
+        for r in 0..rows {
+            section.add(
+                Element::new("div", format!("row {} & {}", s, r))
+                    .attrs(&[("class", "row"), ("data-id", "x")])
+                    .add_with(Element::new("a", "link").attrs(&[("href", "/a?b=1&c=2")])),
+            );
+        }
+        body.add(section);
+    }
+    body
+}
+
+fn time(f: impl Fn() -> String) -> Duration {
+    black_box(f());
+    let start = Instant::now();
+    for _ in 0..10 {
+        black_box(f());
+    }
+    start.elapsed() / 10
+}
+
+fn main() {
+    for (sections, rows) in [(4, 100), (16, 5_000), (64, 5_000)] {
+        let body = page(sections, rows);
+        assert_eq!(body.render_par("\n"), body.render("\n"));
+        let serial = time(|| body.render("\n"));
+        let parallel = time(|| body.render_par("\n"));
+        println!("{:>3} x {:>5}: render {:>10.2?}  render_par {:>10.2?}", sections, rows, serial, parallel);
+    }
+}
@@ -0,0 +1,85 @@
+//! `#[derive(HtmlForm)]`
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{DeriveInput, Ident, LitStr};
+
+use crate::{default_label, named_fields};
+
+#[derive(Default)]
+struct FieldOpts {
+    label: Option<LitStr>,
+    ty: Option<LitStr>,
+    placeholder: Option<LitStr>,
+    required: bool,
+    skip: bool,
+}
+
+impl FieldOpts {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut opts = Self::default();
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("form")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    opts.label = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("type") {
+                    opts.ty = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("placeholder") {
+                    opts.placeholder = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("required") {
+                    opts.required = true;
+                } else if meta.path.is_ident("skip") {
+                    opts.skip = true;
+                } else {
+                    return Err(meta.error("expected `label`, `type`, `placeholder`, `required` or `skip`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(opts)
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let form = Ident::new("__form", Span::mixed_site());
+    let control = Ident::new("__control", Span::mixed_site());
+    let mut fields = Vec::new();
+    for field in named_fields(input, "HtmlForm")? {
+        let opts = FieldOpts::parse(field)?;
+        if opts.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let name = ident.to_string().trim_start_matches("r#").to_string();
+        let label = match &opts.label {
+            Some(label) => label.value(),
+            None => default_label(&name),
+        };
+        let input_type = match &opts.ty {
+            Some(t) => quote! { #t },
+            None => quote! { <#ty as ::htmlbuilder::forms::FormValue>::INPUT_TYPE },
+        };
+        let required = opts.required.then(|| quote! { #control.set_attr("required", ""); });
+        let placeholder = opts.placeholder.map(|p| quote! { #control.set_attr("placeholder", #p); });
+        fields.push(quote! {
+            let #control = ::htmlbuilder::forms::control(#input_type);
+            ::htmlbuilder::forms::FormValue::fill(&self.#ident, &#control);
+            #required
+            #placeholder
+            let #form = #form.field(#name, #label, #control);
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::htmlbuilder::forms::HtmlForm for #ident #ty_generics #where_clause {
+            fn to_form(&self, action: &str, method: &str) -> ::htmlbuilder::forms::Form {
+                let #form = ::htmlbuilder::forms::Form::new(action, method);
+                #(#fields)*
+                #form
+            }
+        }
+    })
+}
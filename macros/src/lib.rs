@@ -0,0 +1,131 @@
+//! htmlbuilder的过程宏，通过`htmlbuilder`的`macros`特性使用
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, parse_macro_input};
+
+mod form;
+mod table;
+mod template;
+
+use template::{Node, Part};
+
+/// 编译期解析html模板，生成构建元素树的代码
+///
+/// 标签名、属性名和标签嵌套在编译期校验，`{expr}`插值的内容会自动转义，文本中的字符实体（如`&amp;`）按html解码
+#[proc_macro]
+pub fn template(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    match template::parse(&lit.value()) {
+        Ok(node) => expand(&node).into(),
+        Err(msg) => syn::Error::new(lit.span(), msg).to_compile_error().into(),
+    }
+}
+
+/// 由结构体的字段生成表单，见`htmlbuilder::forms::HtmlForm`
+#[proc_macro_derive(HtmlForm, attributes(form))]
+pub fn derive_html_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    form::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// 由结构体的字段生成表格的表头和行，见`htmlbuilder::table::HtmlTable`
+#[proc_macro_derive(HtmlTable, attributes(table))]
+pub fn derive_html_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    table::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// 字段名转为默认标签：首字母大写，下划线替换为空格
+pub(crate) fn default_label(name: &str) -> String {
+    let name = name.trim_start_matches("r#").replace('_', " ");
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// 具名字段结构体的字段，其他类型返回错误
+pub(crate) fn named_fields<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<impl Iterator<Item = &'a syn::Field>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(&input.ident, format!("{} can only be derived for structs", derive)));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(&input.ident, format!("{} requires named fields", derive)));
+    };
+    Ok(fields.named.iter())
+}
+
+fn expand(node: &Node) -> TokenStream2 {
+    let el = Ident::new("__el", Span::mixed_site());
+    match node {
+        Node::Text(text) => quote! { ::htmlbuilder::Element::new("", #text) },
+        Node::Expr(_) => unreachable!("expressions are expanded by their parent"),
+        Node::Element { tag, attrs, children } => {
+            let onetag = template::is_void(tag);
+            let attrs = attrs.iter().map(|attr| {
+                let name = &attr.name;
+                let value = expand_parts(&attr.value);
+                quote! { #el.set_attr(#name, #value); }
+            });
+            let children = children.iter().map(|child| match child {
+                Node::Expr(expr) => quote! {
+                    for __child in ::htmlbuilder::__private::IntoChildren::into_children(#expr) {
+                        #el.add(__child);
+                    }
+                },
+                _ => {
+                    let child = expand(child);
+                    quote! { #el.add(#child); }
+                }
+            });
+            let rawtext = matches!(tag.as_str(), "script" | "style");
+            if rawtext {
+                // 原始文本元素：内容合并后作为原始文本设置
+                let content = expand_parts(&children_parts(node));
+                quote! {{
+                    let #el = ::htmlbuilder::Element::new(#tag, "").rawtext(true);
+                    #(#attrs)*
+                    #el.configcnt(#content);
+                    #el
+                }}
+            } else {
+                quote! {{
+                    let #el = ::htmlbuilder::Element::new(#tag, "").onetag(#onetag);
+                    #(#attrs)*
+                    #(#children)*
+                    #el
+                }}
+            }
+        }
+    }
+}
+
+fn children_parts(node: &Node) -> Vec<Part> {
+    let Node::Element { children, .. } = node else { return Vec::new() };
+    children.iter()
+        .map(|child| match child {
+            Node::Text(text) => Part::Lit(text.clone()),
+            Node::Expr(expr) => Part::Expr(expr.clone()),
+            Node::Element { .. } => unreachable!("raw text elements have no child elements"),
+        })
+        .collect()
+}
+
+/// 将文本片段和插值表达式拼接为`String`
+fn expand_parts(parts: &[Part]) -> TokenStream2 {
+    let mut fmt = String::new();
+    let mut args = Vec::new();
+    for part in parts {
+        match part {
+            Part::Lit(text) => fmt.push_str(&text.replace('{', "{{").replace('}', "}}")),
+            Part::Expr(expr) => {
+                fmt.push_str("{}");
+                args.push(expr);
+            }
+        }
+    }
+    quote! { ::htmlbuilder::__private::format!(#fmt #(, #args)*) }
+}
@@ -0,0 +1,51 @@
+//! `#[derive(HtmlTable)]`
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{DeriveInput, LitStr};
+
+use crate::{default_label, named_fields};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut headers = Vec::new();
+    let mut cells = Vec::new();
+    for field in named_fields(input, "HtmlTable")? {
+        let mut header = None;
+        let mut skip = false;
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("table")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("header") {
+                    header = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                } else {
+                    return Err(meta.error("expected `header` or `skip`"));
+                }
+                Ok(())
+            })?;
+        }
+        if skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        headers.push(header.unwrap_or_else(|| default_label(&ident.to_string())));
+        cells.push(quote! {
+            ::htmlbuilder::table::IntoCell::into_cell(&self.#ident, "td")
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::htmlbuilder::table::HtmlTable for #ident #ty_generics #where_clause {
+            fn headers() -> &'static [&'static str] {
+                &[#(#headers),*]
+            }
+
+            fn to_table_row(&self) -> ::htmlbuilder::Element {
+                ::htmlbuilder::tags::tr()
+                    #(.add_with(#cells))*
+            }
+        }
+    })
+}
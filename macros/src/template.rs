@@ -0,0 +1,495 @@
+//! 模板解析与校验
+
+use syn::Expr;
+
+pub enum Node {
+    Element {
+        tag: String,
+        attrs: Vec<Attr>,
+        children: Vec<Node>,
+    },
+    Text(String),
+    Expr(Expr),
+}
+
+pub struct Attr {
+    pub name: String,
+    pub value: Vec<Part>,
+}
+
+pub enum Part {
+    Lit(String),
+    Expr(Expr),
+}
+
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+const TAGS: &[&str] = &[
+    "a", "abbr", "address", "area", "article", "aside", "audio", "b", "base", "bdi", "bdo",
+    "blockquote", "body", "br", "button", "canvas", "caption", "cite", "code", "col", "colgroup",
+    "data", "datalist", "dd", "del", "details", "dfn", "dialog", "div", "dl", "dt", "em", "embed",
+    "fieldset", "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6",
+    "head", "header", "hgroup", "hr", "html", "i", "iframe", "img", "input", "ins", "kbd", "label",
+    "legend", "li", "link", "main", "map", "mark", "menu", "meta", "meter", "nav", "noscript",
+    "object", "ol", "optgroup", "option", "output", "p", "picture", "pre", "progress", "q", "rp",
+    "rt", "ruby", "s", "samp", "script", "search", "section", "select", "slot", "small", "source",
+    "span", "strong", "style", "sub", "summary", "sup", "table", "tbody", "td", "template",
+    "textarea", "tfoot", "th", "thead", "time", "title", "tr", "track", "u", "ul", "var", "video",
+    "wbr", "svg", "math",
+];
+
+const ATTRS: &[&str] = &[
+    // 全局属性
+    "accesskey", "autocapitalize", "autofocus", "class", "contenteditable", "dir", "draggable",
+    "enterkeyhint", "hidden", "id", "inert", "inputmode", "is", "itemid", "itemprop", "itemref",
+    "itemscope", "itemtype", "lang", "nonce", "popover", "role", "slot", "spellcheck", "style",
+    "tabindex", "title", "translate",
+    // 元素属性
+    "accept", "accept-charset", "action", "allow", "alt", "as", "async", "autocomplete",
+    "autoplay", "charset", "checked", "cite", "cols", "colspan", "content", "controls", "coords",
+    "crossorigin", "datetime", "decoding", "default", "defer", "dirname", "disabled", "download",
+    "enctype", "for", "form", "formaction", "formenctype", "formmethod", "formnovalidate",
+    "formtarget", "headers", "height", "high", "href", "hreflang", "http-equiv", "integrity",
+    "kind", "label", "list", "loading", "loop", "low", "max", "maxlength", "media", "method",
+    "min", "minlength", "multiple", "muted", "name", "novalidate", "open", "optimum", "pattern",
+    "ping", "placeholder", "playsinline", "poster", "preload", "readonly", "referrerpolicy",
+    "rel", "required", "reversed", "rows", "rowspan", "sandbox", "scope", "selected", "shape",
+    "size", "sizes", "span", "src", "srcdoc", "srclang", "srcset", "start", "step", "target",
+    "type", "usemap", "value", "width", "wrap", "xmlns",
+];
+
+/// 不能出现在`p`中的块级元素
+const BLOCK_TAGS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "details", "dialog", "div", "dl", "fieldset",
+    "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6", "header",
+    "hgroup", "hr", "main", "menu", "nav", "ol", "p", "pre", "search", "section", "table", "ul",
+];
+
+/// 解码字符实体：`&amp;`、`&lt;`、`&gt;`、`&quot;`、`&apos;`、`&nbsp;`和数字实体，其他保持原样
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => '\u{a0}',
+                name => {
+                    let code = match name.strip_prefix('#')? {
+                        hex if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok()?,
+                        dec => dec.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn check_tag(tag: &str) -> Result<(), String> {
+    if TAGS.contains(&tag) || is_custom_element(tag) {
+        Ok(())
+    } else {
+        Err(format!("unknown tag `{}`", tag))
+    }
+}
+
+fn is_custom_element(tag: &str) -> bool {
+    tag.contains('-') && tag.starts_with(|c: char| c.is_ascii_lowercase())
+}
+
+fn check_attr(tag: &str, name: &str) -> Result<(), String> {
+    if ATTRS.contains(&name)
+        || name.starts_with("data-")
+        || name.starts_with("aria-")
+        || (name.starts_with("on") && name.len() > 2)
+        || is_custom_element(tag)
+        || matches!(tag, "svg" | "math")
+    {
+        Ok(())
+    } else {
+        Err(format!("unknown attribute `{}` on `<{}>`", name, tag))
+    }
+}
+
+fn check_nesting(parent: Option<&str>, tag: &str) -> Result<(), String> {
+    let allowed: &[&str] = match tag {
+        "li" => &["ul", "ol", "menu"],
+        "dt" | "dd" => &["dl", "div"],
+        "tr" => &["table", "thead", "tbody", "tfoot"],
+        "td" | "th" => &["tr"],
+        "thead" | "tbody" | "tfoot" | "caption" | "colgroup" => &["table"],
+        "option" => &["select", "datalist", "optgroup"],
+        "optgroup" => &["select"],
+        "head" | "body" => &["html"],
+        _ => &[],
+    };
+    if !allowed.is_empty() && !parent.is_some_and(|p| allowed.contains(&p)) {
+        return Err(format!("`<{}>` must be a child of {}", tag, allowed.iter()
+            .map(|t| format!("`<{}>`", t))
+            .collect::<Vec<_>>()
+            .join(" or ")));
+    }
+    if parent == Some("p") && BLOCK_TAGS.contains(&tag) {
+        return Err(format!("`<p>` cannot contain `<{}>`", tag));
+    }
+    Ok(())
+}
+
+pub fn is_void(tag: &str) -> bool {
+    VOID_TAGS.contains(&tag)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars().enumerate().all(|(i, c)| self.chars.get(self.pos + i) == Some(&c))
+    }
+
+    /// 是否位于原始文本元素的结束标签`</tag`（不区分大小写）
+    fn at_raw_end(&self, tag: &str) -> bool {
+        let end = self.pos + 2 + tag.chars().count();
+        self.starts_with("</")
+            && self.chars.get(self.pos + 2..end).is_some_and(|name| {
+                name.iter().zip(tag.chars()).all(|(a, b)| a.eq_ignore_ascii_case(&b))
+            })
+            && self.chars.get(end).is_none_or(|c| c.is_whitespace() || matches!(c, '/' | '>'))
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.starts_with(s) {
+            self.pos += s.chars().count();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':' {
+                name.push(c);
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        name
+    }
+
+    /// 解析`{`之后的表达式，直到匹配的`}`
+    fn expr(&mut self) -> Result<Expr, String> {
+        let start = self.pos;
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            match c {
+                '"' => {
+                    self.pos += 1;
+                    while let Some(c) = self.peek() {
+                        self.pos += 1;
+                        match c {
+                            '\\' => self.pos += 1,
+                            '"' => break,
+                            _ => {}
+                        }
+                    }
+                    continue;
+                }
+                '{' => depth += 1,
+                '}' if depth == 0 => {
+                    let src: String = self.chars[start..self.pos].iter().collect();
+                    self.pos += 1;
+                    return syn::parse_str(&src)
+                        .map_err(|e| format!("invalid expression `{}`: {}", src.trim(), e));
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        Err("unclosed `{` in template".to_string())
+    }
+
+    /// 解析文本或属性值，`{{`、`}}`表示字面量大括号
+    fn parts(&mut self, end: impl Fn(char) -> bool) -> Result<Vec<Part>, String> {
+        self.parts_until(|parser| parser.peek().is_some_and(&end))
+    }
+
+    /// 同`parts`，直到`end`对当前位置返回true
+    fn parts_until(&mut self, end: impl Fn(&Self) -> bool) -> Result<Vec<Part>, String> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            if end(self) {
+                break;
+            }
+            if self.eat("{{") {
+                text.push('{');
+            } else if self.eat("}}") {
+                text.push('}');
+            } else if self.eat("{") {
+                if !text.is_empty() {
+                    parts.push(Part::Lit(std::mem::take(&mut text)));
+                }
+                parts.push(Part::Expr(self.expr()?));
+            } else if c == '}' {
+                return Err("unmatched `}` in template, use `}}` for a literal brace".to_string());
+            } else {
+                text.push(c);
+                self.pos += 1;
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Lit(text));
+        }
+        Ok(parts)
+    }
+
+    /// 解析普通文本或属性值，字面量中的字符实体解码为字符，生成的代码会重新转义
+    fn text_parts(&mut self, end: impl Fn(char) -> bool) -> Result<Vec<Part>, String> {
+        Ok(self.parts(end)?
+            .into_iter()
+            .map(|part| match part {
+                Part::Lit(text) => Part::Lit(unescape(&text)),
+                part => part,
+            })
+            .collect())
+    }
+
+    fn attrs(&mut self, tag: &str) -> Result<Vec<Attr>, String> {
+        let mut attrs: Vec<Attr> = Vec::new();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some('>') | Some('/') | None => return Ok(attrs),
+                _ => {}
+            }
+            let name = self.name();
+            if name.is_empty() {
+                return Err(format!("unexpected `{}` in `<{}>`", self.peek().unwrap(), tag));
+            }
+            check_attr(tag, &name)?;
+            if attrs.iter().any(|a| a.name == name) {
+                return Err(format!("duplicate attribute `{}` on `<{}>`", name, tag));
+            }
+            self.skip_ws();
+            let value = if self.eat("=") {
+                self.skip_ws();
+                if self.eat("\"") {
+                    let value = self.text_parts(|c| c == '"')?;
+                    if !self.eat("\"") {
+                        return Err(format!("unclosed value of attribute `{}`", name));
+                    }
+                    value
+                } else if self.eat("'") {
+                    let value = self.text_parts(|c| c == '\'')?;
+                    if !self.eat("'") {
+                        return Err(format!("unclosed value of attribute `{}`", name));
+                    }
+                    value
+                } else if self.eat("{") {
+                    vec![Part::Expr(self.expr()?)]
+                } else {
+                    return Err(format!("attribute `{}` must be quoted or an `{{expr}}`", name));
+                }
+            } else {
+                Vec::new()
+            };
+            attrs.push(Attr { name, value });
+        }
+    }
+
+    /// 解析子节点，直到遇到`</`或结束
+    fn nodes(&mut self, parent: Option<&str>) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+        loop {
+            if self.peek().is_none() || self.starts_with("</") {
+                return Ok(nodes);
+            }
+            if self.eat("<!--") {
+                while !self.eat("-->") {
+                    if self.peek().is_none() {
+                        return Err("unclosed comment".to_string());
+                    }
+                    self.pos += 1;
+                }
+                continue;
+            }
+            if self.eat("<") {
+                nodes.push(self.element(parent)?);
+                continue;
+            }
+            for part in self.text_parts(|c| c == '<')? {
+                match part {
+                    // 仅含换行的空白用于排版，忽略
+                    Part::Lit(text) if text.trim().is_empty() && text.contains('\n') => {}
+                    Part::Lit(text) => nodes.push(Node::Text(text)),
+                    Part::Expr(expr) => nodes.push(Node::Expr(expr)),
+                }
+            }
+        }
+    }
+
+    fn element(&mut self, parent: Option<&str>) -> Result<Node, String> {
+        let tag = self.name();
+        if tag.is_empty() {
+            return Err("expected a tag name after `<`".to_string());
+        }
+        check_tag(&tag)?;
+        check_nesting(parent, &tag)?;
+        let attrs = self.attrs(&tag)?;
+
+        if self.eat("/>") {
+            return Ok(Node::Element { tag, attrs, children: Vec::new() });
+        }
+        if !self.eat(">") {
+            return Err(format!("unclosed tag `<{}>`", tag));
+        }
+        if is_void(&tag) {
+            if self.starts_with(&format!("</{}>", tag)) {
+                return Err(format!("void element `<{}>` cannot have a closing tag", tag));
+            }
+            return Ok(Node::Element { tag, attrs, children: Vec::new() });
+        }
+
+        let children = if matches!(tag.as_str(), "script" | "style") {
+            // 原始文本元素不解析子元素，内容可以含有`<`
+            let text = self.parts_until(|parser| parser.at_raw_end(&tag))?;
+            text.into_iter()
+                .map(|part| match part {
+                    Part::Lit(text) => Node::Text(text),
+                    Part::Expr(expr) => Node::Expr(expr),
+                })
+                .collect()
+        } else {
+            self.nodes(Some(&tag))?
+        };
+
+        if !self.eat("</") {
+            return Err(format!("missing closing tag `</{}>`", tag));
+        }
+        let close = self.name();
+        self.skip_ws();
+        if close != tag || !self.eat(">") {
+            return Err(format!("expected `</{}>`, found `</{}>`", tag, close));
+        }
+        Ok(Node::Element { tag, attrs, children })
+    }
+}
+
+/// 解析模板，要求只有一个根元素
+pub fn parse(template: &str) -> Result<Node, String> {
+    let mut parser = Parser { chars: template.chars().collect(), pos: 0 };
+    let mut nodes = parser.nodes(None)?;
+    if parser.peek().is_some() {
+        let rest: String = parser.chars[parser.pos..].iter().take(20).collect();
+        return Err(format!("unexpected closing tag `{}`", rest));
+    }
+    nodes.retain(|n| !matches!(n, Node::Text(t) if t.trim().is_empty()));
+    match nodes.len() {
+        1 => Ok(nodes.pop().unwrap()),
+        0 => Err("template is empty".to_string()),
+        n => Err(format!("template must have exactly one root element, found {}", n)),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn err(template: &str) -> String {
+        match parse(template) {
+            Ok(_) => panic!("`{}` should not parse", template),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let node = parse(r#"
+            <ul class="list {extra}" data-x='1'>
+                <li>{a} and {{b}}</li>
+                <li><br/><img src={src} alt=""></li>
+            </ul>
+        "#).unwrap();
+        let Node::Element { tag, attrs, children } = node else { panic!() };
+        assert_eq!(tag, "ul");
+        assert_eq!(attrs.len(), 2);
+        assert_eq!(attrs[0].value.len(), 2);
+        assert_eq!(children.len(), 2);
+        let Node::Element { children, .. } = &children[0] else { panic!() };
+        assert!(matches!(&children[..], [Node::Expr(_), Node::Text(t)] if t == " and {b}"));
+    }
+
+    #[test]
+    fn test_entities() {
+        let node = parse(r#"<p title="a &amp; b">1 &lt; 2 &#x26; &#169; &copy; &</p>"#).unwrap();
+        let Node::Element { attrs, children, .. } = node else { panic!() };
+        assert!(matches!(&attrs[0].value[..], [Part::Lit(v)] if v == "a & b"));
+        assert!(matches!(&children[..], [Node::Text(t)] if t == "1 < 2 & \u{a9} &copy; &"));
+
+        // 原始文本元素的内容不解码
+        let node = parse("<script>a &amp;&amp; b</script>").unwrap();
+        let Node::Element { children, .. } = node else { panic!() };
+        assert!(matches!(&children[..], [Node::Text(t)] if t == "a &amp;&amp; b"));
+    }
+
+    #[test]
+    fn test_raw_text() {
+        let node = parse("<script>if (a < b && c </scripts) {{ f(\"</p>\") }}</script>").unwrap();
+        let Node::Element { children, .. } = node else { panic!() };
+        assert!(matches!(&children[..], [Node::Text(t)] if t == "if (a < b && c </scripts) { f(\"</p>\") }"));
+        assert!(parse("<style>a < b {{}}</style >").is_ok());
+        assert!(err("<style>a {{}}").contains("missing closing tag"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(err("<dvi></dvi>").contains("unknown tag"));
+        assert!(err(r#"<div clas="x"></div>"#).contains("unknown attribute"));
+        assert!(err("<div><li></li></div>").contains("must be a child of"));
+        assert!(err("<p><div></div></p>").contains("cannot contain"));
+        assert!(err("<div><span></div>").contains("expected `</span>`"));
+        assert!(err("<br></br>").contains("void element"));
+        assert!(err("<p></p><p></p>").contains("exactly one root"));
+        assert!(err("<p>{1 +}</p>").contains("invalid expression"));
+    }
+}
@@ -0,0 +1,177 @@
+//! 无障碍检查
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::validate::{IssueKind, ValidationIssue};
+use crate::{Element, un_escape_ascii};
+
+fn tag_of(elem: &Element) -> String {
+    elem.inner.borrow().tag.to_ascii_lowercase()
+}
+
+fn attr_of(elem: &Element, name: &str) -> Option<String> {
+    let inner = elem.inner.borrow();
+    inner.kws.get(name).map(|v| if inner.pre { v.clone() } else { un_escape_ascii(v) })
+}
+
+fn has_nonempty_attr(elem: &Element, name: &str) -> bool {
+    attr_of(elem, name).is_some_and(|v| !v.trim().is_empty())
+}
+
+/// 是否有`aria-label`、`aria-labelledby`或`title`
+fn has_aria_name(elem: &Element) -> bool {
+    ["aria-label", "aria-labelledby", "title"].iter().any(|name| has_nonempty_attr(elem, name))
+}
+
+/// 元素内容是否能提供可访问名称：文本或带`alt`的图片
+fn has_content_name(elem: &Element) -> bool {
+    if !elem.text_normalized().is_empty() {
+        return true;
+    }
+    elem.children().iter().any(|child| {
+        (tag_of(child) == "img" && has_nonempty_attr(child, "alt")) || has_aria_name(child) || has_content_name(child)
+    })
+}
+
+/// 收集`<label for="...">`指向的id
+fn label_targets(elem: &Element, ids: &mut Vec<String>) {
+    if tag_of(elem) == "label"
+        && let Some(id) = attr_of(elem, "for")
+    {
+        ids.push(id);
+    }
+    for child in elem.children() {
+        label_targets(&child, ids);
+    }
+}
+
+struct Checker {
+    path: Vec<usize>,
+    issues: Vec<ValidationIssue>,
+    labelled: Vec<String>,
+    last_heading: Option<usize>,
+}
+
+impl Checker {
+    fn report(&mut self, elem: &Element, kind: IssueKind, message: String) {
+        #[cfg(feature = "track-source")]
+        let message = crate::source::describe(elem, message);
+        #[cfg(not(feature = "track-source"))]
+        let _ = elem;
+        self.issues.push(ValidationIssue { path: self.path.clone(), kind, message });
+    }
+
+    fn check(&mut self, elem: &Element, in_label: bool) {
+        let tag = tag_of(elem);
+        match tag.as_str() {
+            "" => return,
+            "html" if !has_nonempty_attr(elem, "lang") => {
+                self.report(elem, IssueKind::MissingLang, "`<html>` has no `lang` attribute".to_string());
+            }
+            "img" if attr_of(elem, "alt").is_none() => {
+                self.report(elem, IssueKind::MissingAlt, "`<img>` has no `alt` attribute".to_string());
+            }
+            "input" | "select" | "textarea" => {
+                let ty = attr_of(elem, "type").unwrap_or_default().to_ascii_lowercase();
+                let exempt = tag == "input" && matches!(ty.as_str(), "hidden" | "submit" | "reset" | "button" | "image");
+                let labelled = in_label
+                    || has_aria_name(elem)
+                    || attr_of(elem, "id").is_some_and(|id| self.labelled.contains(&id));
+                if !exempt && !labelled {
+                    self.report(elem, IssueKind::MissingLabel, format!("`<{}>` has no associated label", tag));
+                }
+            }
+            "button" | "a" => {
+                let needs_name = tag == "button" || elem.inner.borrow().kws.contains_key("href");
+                if needs_name && !has_aria_name(elem) && !has_content_name(elem) {
+                    self.report(elem, IssueKind::MissingName, format!("`<{}>` has no accessible name", tag));
+                }
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = (tag.as_bytes()[1] - b'0') as usize;
+                if let Some(last) = self.last_heading
+                    && level > last + 1
+                {
+                    self.report(
+                        elem,
+                        IssueKind::HeadingLevelSkip,
+                        format!("`<{}>` follows `<h{}>`", tag, last),
+                    );
+                }
+                self.last_heading = Some(level);
+            }
+            _ => {}
+        }
+
+        let in_label = in_label || tag == "label";
+        for (i, child) in elem.children().iter().enumerate() {
+            self.path.push(i);
+            self.check(child, in_label);
+            self.path.pop();
+        }
+    }
+}
+
+impl Element {
+    /// 无障碍检查，返回发现的问题
+    ///
+    /// 检查缺少`alt`的图片、没有标签的表单控件、没有可访问名称的按钮和链接、
+    /// `html`缺少`lang`以及跳级的标题
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// # use htmlbuilder::validate::IssueKind;
+    /// let form = Element::new("form", "")
+    ///     .add_with(Element::new("label", "Name").attrs(&[("for", "name")]))
+    ///     .add_with(Element::new("input", "").attrs(&[("id", "name")]).onetag(true))
+    ///     .add_with(Element::new("input", "").attrs(&[("id", "email")]).onetag(true));
+    /// let issues = form.check_a11y();
+    /// assert_eq!(issues.len(), 1);
+    /// assert_eq!(issues[0].kind, IssueKind::MissingLabel);
+    /// assert_eq!(issues[0].path, vec![2]);
+    /// ```
+    pub fn check_a11y(&self) -> Vec<ValidationIssue> {
+        let mut labelled = Vec::new();
+        label_targets(self, &mut labelled);
+        let mut checker = Checker { path: Vec::new(), issues: Vec::new(), labelled, last_heading: None };
+        let in_label = self.parent().is_some_and(|p| tag_of(&p) == "label");
+        checker.check(self, in_label);
+        checker.issues
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_check_a11y() {
+        let body = Element::new("body", "")
+            .add_with(Element::new("h1", "Title"))
+            .add_with(Element::new("h3", "Skipped"))
+            .add_with(Element::new("img", "").attrs(&[("src", "a.png")]).onetag(true))
+            .add_with(Element::new("img", "").attrs(&[("src", "b.png"), ("alt", "")]).onetag(true))
+            .add_with(Element::new("label", "Agree ").add_with(Element::new("input", "").attrs(&[("type", "checkbox")])))
+            .add_with(Element::new("input", "").attrs(&[("type", "hidden")]))
+            .add_with(Element::new("button", ""))
+            .add_with(Element::new("button", "").attrs(&[("aria-label", "Close")]))
+            .add_with(Element::new("a", "").attrs(&[("href", "/")])
+                .add_with(Element::new("img", "").attrs(&[("src", "logo.png"), ("alt", "Home")])))
+            .add_with(Element::new("a", " ").attrs(&[("href", "/x")]));
+        let html = Element::new("html", "").add_with(body);
+
+        let issues: Vec<_> = html.check_a11y().into_iter().map(|i| (i.path, i.kind)).collect();
+        assert_eq!(issues, vec![
+            (vec![], IssueKind::MissingLang),
+            (vec![0, 1], IssueKind::HeadingLevelSkip),
+            (vec![0, 2], IssueKind::MissingAlt),
+            (vec![0, 6], IssueKind::MissingName),
+            (vec![0, 9], IssueKind::MissingName),
+        ]);
+    }
+}
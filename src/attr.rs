@@ -0,0 +1,216 @@
+//! 常用属性名与属性值
+//!
+//! `set_attr`同时接受`Attr`和任意字符串属性名，属性值可以是字符串、数字或`bool`（见`AttrValue`）
+//!
+//! ```
+//! use htmlbuilder::{Attr, Element};
+//!
+//! let link = Element::new("a", "rust");
+//! link.set_attr(Attr::Href, "https://www.rust-lang.org/");
+//! link.set_attr("data-id", 1);
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+
+macro_rules! attrs {
+    ($($variant:ident => $name:literal),* $(,)?) => {
+        /// 常用html属性
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Attr {
+            $(
+                #[doc = concat!("`", $name, "`")]
+                $variant,
+            )*
+        }
+
+        impl Attr {
+            /// 属性名
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(Attr::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+attrs!(
+    // 全局属性
+    AccessKey => "accesskey",
+    Class => "class",
+    ContentEditable => "contenteditable",
+    Dir => "dir",
+    Draggable => "draggable",
+    Hidden => "hidden",
+    Id => "id",
+    Lang => "lang",
+    Nonce => "nonce",
+    Role => "role",
+    Slot => "slot",
+    Spellcheck => "spellcheck",
+    Style => "style",
+    TabIndex => "tabindex",
+    Title => "title",
+    Translate => "translate",
+    // 链接与资源
+    Href => "href",
+    HrefLang => "hreflang",
+    Rel => "rel",
+    Target => "target",
+    Download => "download",
+    Src => "src",
+    SrcSet => "srcset",
+    Sizes => "sizes",
+    Alt => "alt",
+    Width => "width",
+    Height => "height",
+    Loading => "loading",
+    Integrity => "integrity",
+    CrossOrigin => "crossorigin",
+    Async => "async",
+    Defer => "defer",
+    Media => "media",
+    // 表单
+    Action => "action",
+    Method => "method",
+    EncType => "enctype",
+    For => "for",
+    Name => "name",
+    Type => "type",
+    Value => "value",
+    Placeholder => "placeholder",
+    Required => "required",
+    Disabled => "disabled",
+    ReadOnly => "readonly",
+    Checked => "checked",
+    Selected => "selected",
+    Multiple => "multiple",
+    Min => "min",
+    Max => "max",
+    Step => "step",
+    MinLength => "minlength",
+    MaxLength => "maxlength",
+    Pattern => "pattern",
+    AutoComplete => "autocomplete",
+    AutoFocus => "autofocus",
+    Rows => "rows",
+    Cols => "cols",
+    // 表格
+    ColSpan => "colspan",
+    RowSpan => "rowspan",
+    Scope => "scope",
+    Headers => "headers",
+    // 元数据
+    Charset => "charset",
+    Content => "content",
+    HttpEquiv => "http-equiv",
+    Property => "property",
+);
+
+impl From<Attr> for &'static str {
+    fn from(attr: Attr) -> Self {
+        attr.as_str()
+    }
+}
+
+impl From<Attr> for Cow<'static, str> {
+    fn from(attr: Attr) -> Self {
+        Cow::Borrowed(attr.as_str())
+    }
+}
+
+/// 可以作为属性值的类型，用于`set_attr`和`attr_if`
+///
+/// 字符串原样使用，数字和`char`转为字符串；`true`设置空值的布尔属性，`false`和`None`删除属性
+///
+/// ```
+/// # use htmlbuilder::Element;
+/// let input = Element::new("input", "").onetag(true);
+/// input.set_attr("required", true);
+/// input.set_attr("maxlength", 64);
+/// input.set_attr("required", false);
+/// assert_eq!(input.render(""), r#"<input maxlength="64">"#);
+/// ```
+pub trait AttrValue {
+    /// 转为属性值，None表示删除属性
+    fn into_attr_value(self) -> Option<String>;
+}
+
+/// 字符串、数字和`char`原样转为属性值
+impl<T: DisplayText> AttrValue for T {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.into_text())
+    }
+}
+
+impl AttrValue for bool {
+    fn into_attr_value(self) -> Option<String> {
+        self.then(String::new)
+    }
+}
+
+impl<T: AttrValue> AttrValue for Option<T> {
+    fn into_attr_value(self) -> Option<String> {
+        self.and_then(AttrValue::into_attr_value)
+    }
+}
+
+/// 可以直接转为文本的类型：字符串、数字、`char`及它们的引用
+///
+/// 可以作为属性值（`AttrValue`）、表格单元格（`IntoCell`）、列表项（`IntoListItem`），
+/// 也可以作为`html!`中的文本子元素
+pub trait DisplayText {
+    /// 转为未转义的文本
+    fn into_text(self) -> String;
+}
+
+impl DisplayText for String {
+    fn into_text(self) -> String {
+        self
+    }
+}
+
+impl DisplayText for &str {
+    fn into_text(self) -> String {
+        self.into()
+    }
+}
+
+impl DisplayText for &String {
+    fn into_text(self) -> String {
+        self.clone()
+    }
+}
+
+impl DisplayText for Cow<'_, str> {
+    fn into_text(self) -> String {
+        self.into_owned()
+    }
+}
+
+impl DisplayText for &Cow<'_, str> {
+    fn into_text(self) -> String {
+        self.to_string()
+    }
+}
+
+macro_rules! impl_display_text {
+    ($($t:ty),*) => {
+        $(
+            impl DisplayText for $t {
+                fn into_text(self) -> String {
+                    self.to_string()
+                }
+            }
+
+            impl DisplayText for &$t {
+                fn into_text(self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_display_text!(char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
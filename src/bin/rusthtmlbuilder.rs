@@ -0,0 +1,200 @@
+//! 命令行工具：格式化、压缩和检查html文件
+//!
+//! ```text
+//! rusthtmlbuilder format [--write] [FILE...]
+//! rusthtmlbuilder minify [--write] [FILE...]
+//! rusthtmlbuilder validate [FILE...]
+//! ```
+//!
+//! 没有文件参数时读取标准输入。`--write`将结果写回原文件，否则输出到标准输出
+
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+use htmlbuilder::testing::normalize_html;
+use htmlbuilder::{Element, RenderOptions};
+
+const USAGE: &str = "usage: rusthtmlbuilder <format|minify|validate> [--write] [FILE...]
+
+commands:
+  format    reindent, one tag or text per line, attributes sorted
+  minify    drop comments and whitespace between tags, minify inline css and js
+  validate  report structural problems, exit with status 1 if any are found
+
+options:
+  --write   rewrite the files in place instead of printing to stdout
+  -h, --help  print this message
+
+reads stdin when no FILE is given";
+
+/// 保留空白的元素
+const PRESERVE_WS: &[&str] = &["pre", "textarea", "script", "style"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Format,
+    Minify,
+    Validate,
+}
+
+/// 删除注释，保留条件注释
+fn strip_comments(elem: &Element) {
+    if PRESERVE_WS.contains(&elem.tag().as_str()) {
+        return;
+    }
+    elem.retain_children(|child| {
+        if !child.tag().is_empty() {
+            return true;
+        }
+        let text = child.content();
+        !text.starts_with("<!--") || text.starts_with("<!--[if")
+    });
+    for child in elem.children() {
+        if !child.tag().is_empty() {
+            strip_comments(&child);
+        }
+    }
+}
+
+fn format_html(html: &str) -> String {
+    normalize_html(html) + "\n"
+}
+
+fn minify(html: &str) -> String {
+    let root = Element::new("div", "").with_children(Element::parse_html(html));
+    strip_comments(&root);
+    // 行内元素之间的空白合并为一个空格，块级元素之间的空白删除
+    root.normalize_whitespace_except(PRESERVE_WS);
+    let opts = RenderOptions::new().minify_assets(true);
+    root.children().iter().map(|node| node.render_with("", &opts)).collect()
+}
+
+/// 检查每个顶层节点，返回问题说明，路径从顶层节点的下标开始
+fn validate(html: &str) -> Vec<String> {
+    let mut issues = Vec::new();
+    for (i, node) in Element::parse_html(html).iter().enumerate() {
+        if node.tag().is_empty() {
+            continue;
+        }
+        for issue in node.validate() {
+            let mut path = vec![i];
+            path.extend(&issue.path);
+            issues.push(format!("{:?}: {}", path, issue.message));
+        }
+    }
+    issues
+}
+
+fn run(command: Command, write: bool, files: &[String]) -> io::Result<bool> {
+    let inputs: Vec<(Option<&str>, String)> = if files.is_empty() {
+        let mut html = String::new();
+        io::stdin().read_to_string(&mut html)?;
+        vec![(None, html)]
+    } else {
+        files
+            .iter()
+            .map(|f| std::fs::read_to_string(f).map(|html| (Some(f.as_str()), html)))
+            .collect::<io::Result<_>>()?
+    };
+
+    let mut ok = true;
+    let mut stdout = io::stdout().lock();
+    for (name, html) in inputs {
+        let output = match command {
+            Command::Format => format_html(&html),
+            Command::Minify => minify(&html),
+            Command::Validate => {
+                let name = name.unwrap_or("<stdin>");
+                for issue in validate(&html) {
+                    ok = false;
+                    writeln!(stdout, "{}: {}", name, issue)?;
+                }
+                continue;
+            }
+        };
+        match name {
+            Some(path) if write => std::fs::write(path, output)?,
+            _ => stdout.write_all(output.as_bytes())?,
+        }
+    }
+    Ok(ok)
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let command = match args.next().as_deref() {
+        Some("format") => Command::Format,
+        Some("minify") => Command::Minify,
+        Some("validate") => Command::Validate,
+        Some("-h" | "--help") => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Some(other) => {
+            eprintln!("unknown command `{}`\n\n{}", other, USAGE);
+            return ExitCode::from(2);
+        }
+        None => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut write = false;
+    let mut files = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--write" if command != Command::Validate => write = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ if arg.starts_with('-') => {
+                eprintln!("unknown option `{}`\n\n{}", arg, USAGE);
+                return ExitCode::from(2);
+            }
+            _ => files.push(arg),
+        }
+    }
+    if write && files.is_empty() {
+        eprintln!("--write needs at least one FILE");
+        return ExitCode::from(2);
+    }
+
+    match run(command, write, &files) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("rusthtmlbuilder: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format_html("<div><p>a</p></div>"), "<div>\n  <p>\n    a\n  </p>\n</div>\n");
+    }
+
+    #[test]
+    fn test_minify() {
+        let html = "<div>\n  <!-- x --><!--[if IE]>ie<![endif]-->\n  <p><b>a</b> <i>b</i>\n  c</p>\n  <pre> x  y </pre>\n</div>\n";
+        assert_eq!(
+            minify(html),
+            "<div><!--[if IE]>ie<![endif]--><p><b>a</b> <i>b</i> c</p><pre> x  y </pre></div>",
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(validate("<ul><li>a</li></ul>"), Vec::<String>::new());
+        let issues = validate("<p>x</p><div><li>a</li></div>");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].starts_with("[1, 0]: `<li>` inside `<div>`"));
+    }
+}
@@ -0,0 +1,175 @@
+//! 批量构建
+//!
+//! 先用普通的值描述整棵树，`build`时一次性创建元素：每个节点只分配一个`Rc`，
+//! 子元素列表按最终长度分配，父指针在创建时直接设置，不需要逐个`add`和清除渲染缓存
+
+use alloc::borrow::Cow;
+use alloc::rc::{Rc, Weak};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use crate::name::TagName;
+use crate::{AttrMap, Element, ElementInner, escape_ascii, escape_cow, is_void_tag};
+
+enum Child {
+    Node(TreeBuilder),
+    Element(Element),
+}
+
+/// 元素树的描述，用于大量生成元素（如上万行的表格）
+///
+/// ```
+/// use htmlbuilder::TreeBuilder;
+///
+/// let rows = (1..=3).map(|i| {
+///     TreeBuilder::new("tr")
+///         .child(TreeBuilder::new("td").text(i.to_string()))
+///         .child(TreeBuilder::new("td").text("<ok>"))
+/// });
+/// let table = TreeBuilder::new("table").child(TreeBuilder::new("tbody").children(rows)).build();
+/// assert_eq!(table.render("").matches("<tr>").count(), 3);
+/// assert!(table.render("").contains("<td>1</td><td>&lt;ok&gt;</td>"));
+/// ```
+pub struct TreeBuilder {
+    tag: TagName,
+    content: Cow<'static, str>,
+    kws: AttrMap,
+    onetag: bool,
+    children: Vec<Child>,
+}
+
+impl TreeBuilder {
+    /// 描述标签为`tag`的元素，空字符串表示文本节点
+    pub fn new(tag: &'static str) -> Self {
+        Self { tag: TagName::Static(tag), content: Cow::Borrowed(""), kws: AttrMap::new(), onetag: false, children: Vec::new() }
+    }
+
+    /// 文本节点
+    pub fn text_node(text: impl Into<Cow<'static, str>>) -> Self {
+        Self::new("").text(text)
+    }
+
+    /// 设置内容（会被转义），静态字符串无需转义时不复制
+    pub fn text(mut self, text: impl Into<Cow<'static, str>>) -> Self {
+        self.content = escape_cow(text.into());
+        self
+    }
+
+    /// 设置一个属性
+    pub fn attr(mut self, name: &'static str, value: impl AsRef<str>) -> Self {
+        self.kws.insert(Cow::Borrowed(name), escape_ascii(value.as_ref()));
+        self
+    }
+
+    /// 设置是否单标签
+    pub fn onetag(mut self, onetag: bool) -> Self {
+        self.onetag = onetag;
+        self
+    }
+
+    /// 添加子元素
+    pub fn child(mut self, child: TreeBuilder) -> Self {
+        self.children.push(Child::Node(child));
+        self
+    }
+
+    /// 添加多个子元素
+    pub fn children(mut self, children: impl IntoIterator<Item = TreeBuilder>) -> Self {
+        self.children.extend(children.into_iter().map(Child::Node));
+        self
+    }
+
+    /// 添加已创建的元素作为子元素，例如`<style>`等需要特殊设置的元素
+    pub fn element(mut self, elem: Element) -> Self {
+        self.children.push(Child::Element(elem));
+        self
+    }
+
+    /// 创建元素树
+    ///
+    /// 单标签或html空元素有子元素时panic，见`Element::add`
+    #[track_caller]
+    pub fn build(self) -> Element {
+        #[cfg(feature = "track-source")]
+        let source = core::panic::Location::caller();
+        self.into_element(
+            None,
+            #[cfg(feature = "track-source")]
+            source,
+        )
+    }
+
+    #[track_caller]
+    fn into_element(
+        self,
+        parent: Option<Weak<RefCell<ElementInner>>>,
+        #[cfg(feature = "track-source")] source: &'static core::panic::Location<'static>,
+    ) -> Element {
+        let Self { tag, content, kws, onetag, children } = self;
+        if !children.is_empty() && (onetag || is_void_tag(&tag)) {
+            panic!("cannot add a child to void element `<{}>`", tag);
+        }
+        let inner = Rc::new_cyclic(|weak| {
+            let children = children
+                .into_iter()
+                .map(|child| match child {
+                    Child::Node(node) => node.into_element(
+                        Some(weak.clone()),
+                        #[cfg(feature = "track-source")]
+                        source,
+                    ),
+                    Child::Element(elem) => {
+                        elem.inner.borrow_mut().parent = Some(weak.clone());
+                        elem
+                    }
+                })
+                .collect();
+            RefCell::new(ElementInner {
+                parent,
+                children,
+                tag,
+                content,
+                kws,
+                onetag,
+                pre: false,
+                rawtext: false,
+                scoped_css: None,
+                cache: None,
+                #[cfg(feature = "track-source")]
+                source: crate::source::Source::Location(source),
+            })
+        });
+        Element { inner }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_tree_builder() {
+        let style = Element::style_sheet("a > b {}");
+        let built = TreeBuilder::new("div")
+            .attr("id", "main")
+            .child(TreeBuilder::new("p").text("a & b").child(TreeBuilder::text_node(" c")))
+            .child(TreeBuilder::new("br").onetag(true))
+            .children((0..2).map(|i| TreeBuilder::new("span").text(i.to_string())))
+            .element(style.clone())
+            .build();
+
+        let expected = Element::new("div", "")
+            .attrs(&[("id", "main")])
+            .add_with(Element::new("p", "a & b").add_with(Element::new("", " c")))
+            .add_with(Element::new("br", "").onetag(true))
+            .add_with(Element::new("span", "0"))
+            .add_with(Element::new("span", "1"))
+            .add_with(Element::style_sheet("a > b {}"));
+        assert_eq!(built.render("\n"), expected.render("\n"));
+        assert_eq!(built.children()[0].children()[0].parent(), Some(built.children()[0].clone()));
+        assert_eq!(style.parent(), Some(built.clone()));
+    }
+}
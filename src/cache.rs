@@ -0,0 +1,168 @@
+//! 增量渲染缓存
+//!
+//! 启用缓存的元素保存上次渲染的结果，修改元素时清除自身和全部祖先的缓存，
+//! 再次渲染时未修改的子树直接复用。缓存本身可在共享借用下修改，
+//! 祖先元素被只读借用时（如`children_ref`）仍可修改子元素
+
+use alloc::string::{String, ToString};
+use core::cell::{Ref, RefCell};
+
+use crate::{Element, ElementInner};
+
+/// 元素的渲染缓存
+pub(crate) struct RenderCache {
+    split_s: String,
+    html: Option<String>, // None表示已失效
+}
+
+impl ElementInner {
+    /// 清除自身和全部祖先的渲染缓存
+    ///
+    /// 缓存设置由父元素传给子元素，未启用缓存的元素的祖先也都没有启用，不需要向上查找；
+    /// 已失效的元素的祖先在它失效时已一并清除，遇到时停止（`<head>`从不缓存，除外）
+    pub(crate) fn invalidate(&mut self) {
+        let Some(cache) = &mut self.cache else {
+            return;
+        };
+        if cache.get_mut().html.take().is_none() && self.tag != "head" {
+            return;
+        }
+        let mut parent = self.parent.as_ref().and_then(|weak| weak.upgrade());
+        while let Some(rc) = parent {
+            let inner = rc.borrow();
+            let Some(cache) = &inner.cache else {
+                break;
+            };
+            if cache.borrow_mut().html.take().is_none() && inner.tag != "head" {
+                break;
+            }
+            parent = inner.parent.as_ref().and_then(|weak| weak.upgrade());
+        }
+    }
+
+    /// 有效的缓存结果
+    pub(crate) fn cached(&self, split_s: &str) -> Option<Ref<'_, str>> {
+        let cache = self.cache.as_ref()?.borrow();
+        Ref::filter_map(cache, |c| if c.split_s == split_s { c.html.as_deref() } else { None }).ok()
+    }
+
+    /// 保存渲染结果
+    ///
+    /// `<head>`中输出整个文档的作用域样式，结果依赖于树的其他部分，不缓存
+    pub(crate) fn store(&self, split_s: &str, html: &str) {
+        if self.tag == "head" {
+            return;
+        }
+        if let Some(cache) = &self.cache {
+            let mut cache = cache.borrow_mut();
+            cache.split_s = split_s.to_string();
+            cache.html = Some(html.to_string());
+        }
+    }
+}
+
+impl Element {
+    /// 为整棵子树启用或关闭渲染缓存
+    ///
+    /// 启用后，`render`复用未修改子树上次的结果，修改元素（`set_attr`、`configcnt`、`add`、
+    /// `remove_*`等）时自动清除该元素和全部祖先的缓存。之后添加到已启用缓存元素下的子元素也会启用缓存。
+    /// 关闭时祖先元素的缓存也一并关闭，否则祖先无法得知子树的修改。
+    /// 未启用缓存时修改元素不需要访问祖先
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(0..3);
+    /// ul.cache_render(true);
+    /// assert_eq!(ul.render(""), "<ul><li>0</li><li>1</li><li>2</li></ul>");
+    ///
+    /// ul.children()[1].configcnt("one");
+    /// assert_eq!(ul.render(""), "<ul><li>0</li><li>one</li><li>2</li></ul>");
+    /// ```
+    pub fn cache_render(&self, enabled: bool) -> &Self {
+        {
+            let mut inner = self.inner.borrow_mut();
+            if enabled != inner.cache.is_some() {
+                inner.cache = enabled.then(|| RefCell::new(RenderCache { split_s: String::new(), html: None }));
+                inner.invalidate();
+            }
+        }
+        for child in self.children() {
+            child.cache_render(enabled);
+        }
+        if !enabled {
+            let mut parent = self.parent();
+            while let Some(elem) = parent {
+                if elem.inner.borrow_mut().cache.take().is_none() {
+                    break;
+                }
+                parent = elem.parent();
+            }
+        }
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::Stylesheet;
+
+    fn is_clean(elem: &Element) -> bool {
+        elem.inner.borrow().cache.as_ref().is_some_and(|c| c.borrow().html.is_some())
+    }
+
+    #[test]
+    fn test_cache_render() {
+        let list = Element::ul_from(["a", "b"]);
+        let body = Element::new("body", "").add_with(list.clone()).add_with(Element::new("p", "x"));
+        let head = Element::new("head", "");
+        let html = Element::new("html", "").add_with(head).add_with(body.clone());
+        html.cache_render(true);
+        let plain = html.render("\n");
+        assert_eq!(html.render("\n"), plain);
+        assert!(is_clean(&html) && is_clean(&list));
+
+        // 修改只影响所在的路径
+        let item = list.children()[0].clone();
+        item.set_attr("id", "first");
+        assert!(!is_clean(&item) && !is_clean(&list) && !is_clean(&html));
+        assert!(is_clean(&body.children()[1]));
+        assert!(html.render("").contains("<li id=\"first\">a</li>"));
+
+        // 新子元素继承缓存设置
+        let added = Element::new("p", "y");
+        body.add(added.clone());
+        assert!(html.render("").ends_with("<p>y</p></body></html>"));
+        assert!(is_clean(&added));
+
+        // head中的作用域样式不会过期
+        let class = added.scoped_style(Stylesheet::new().rule("&", &[("color", "red")]));
+        assert!(html.render("").contains(&class));
+        html.cache_render(false);
+        assert!(html.inner.borrow().cache.is_none());
+    }
+
+    #[test]
+    fn test_invalidate_path() {
+        let list = Element::ul_from(["a", "b"]);
+        let root = Element::new("div", "").add_with(Element::new("section", "").add_with(list.clone()));
+        root.cache_render(true);
+        root.render("");
+        let item = list.children()[0].clone();
+
+        // 祖先已失效时停止，之后单独渲染的子树再次修改仍会清除到根元素
+        item.configcnt("x");
+        list.render("");
+        assert!(is_clean(&list) && !is_clean(&root));
+        item.configcnt("y");
+        assert!(!is_clean(&list) && !is_clean(&root));
+        assert_eq!(root.render(""), "<div><section><ul><li>y</li><li>b</li></ul></section></div>");
+
+        // 关闭子树的缓存时祖先也关闭，修改不会得到过期的结果
+        list.cache_render(false);
+        assert!(root.inner.borrow().cache.is_none());
+        item.configcnt("z");
+        assert!(root.render("").contains("<li>z</li>"));
+    }
+}
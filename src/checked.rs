@@ -0,0 +1,188 @@
+//! 不会panic的`try_*`接口
+//!
+//! `Element`是共享的句柄，在借用期间（如持有`children_ref`的guard时）修改同一元素会panic。
+//! `try_*`方法先检查所需的借用，无法完成时返回`TreeError`且不修改元素
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{AttrValue, Element};
+
+/// `try_*`方法的错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeError {
+    /// 元素或其祖先正在被借用
+    Borrowed,
+    /// 添加的子元素是当前元素自身或其祖先，会形成环
+    Cycle,
+    /// 单标签或html空元素（如`<br>`）不能有子元素
+    VoidElement,
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TreeError::Borrowed => write!(f, "element is already borrowed"),
+            TreeError::Cycle => write!(f, "element cannot be added to itself or its descendant"),
+            TreeError::VoidElement => write!(f, "void element cannot have children"),
+        }
+    }
+}
+
+impl core::error::Error for TreeError {}
+
+/// 子树中的元素是否都可以借用
+fn subtree_free(elem: &Element, exclusive: bool) -> bool {
+    let free = if exclusive { elem.inner.try_borrow_mut().is_ok() } else { elem.inner.try_borrow().is_ok() };
+    free && elem.inner.borrow().children.iter().all(|child| subtree_free(child, exclusive))
+}
+
+/// 逐级获取父元素，遇到被独占借用的元素时返回错误
+fn try_parent(elem: &Element) -> Result<Option<Element>, TreeError> {
+    let inner = elem.inner.try_borrow().map_err(|_| TreeError::Borrowed)?;
+    Ok(inner.parent.as_ref().and_then(|weak| weak.upgrade()).map(|rc| Element { inner: rc }))
+}
+
+impl Element {
+    /// 修改当前元素需要：当前元素可独占借用，祖先可共享借用（清除渲染缓存）
+    fn check_writable(&self) -> Result<(), TreeError> {
+        self.inner.try_borrow_mut().map_err(|_| TreeError::Borrowed)?;
+        let mut parent = try_parent(self)?;
+        while let Some(elem) = parent {
+            parent = try_parent(&elem)?;
+        }
+        Ok(())
+    }
+
+    /// 添加子元素，不会panic
+    ///
+    /// ```
+    /// # use htmlbuilder::{Element, TreeError};
+    /// let div = Element::new("div", "");
+    /// let p = Element::new("p", "");
+    /// div.try_add(p.clone()).unwrap();
+    /// assert_eq!(p.try_add(div.clone()).unwrap_err(), TreeError::Cycle);
+    ///
+    /// let children = div.children_ref();
+    /// assert_eq!(div.try_add(Element::new("p", "")).unwrap_err(), TreeError::Borrowed);
+    /// # drop(children);
+    /// ```
+    pub fn try_add(&self, elem: impl Into<Element>) -> Result<&Self, TreeError> {
+        let elem = elem.into();
+        self.check_writable()?;
+        if self.inner.borrow().is_void() {
+            return Err(TreeError::VoidElement);
+        }
+        let mut ancestor = Some(self.clone());
+        while let Some(a) = ancestor {
+            if a == elem {
+                return Err(TreeError::Cycle);
+            }
+            ancestor = a.parent();
+        }
+        let cached = self.inner.borrow().cache.is_some();
+        if !subtree_free(&elem, cached) || elem.inner.try_borrow_mut().is_err() {
+            return Err(TreeError::Borrowed);
+        }
+        Ok(self.add(elem))
+    }
+
+    /// 设置一个属性，不会panic
+    pub fn try_set_attr(&self, name: impl Into<Cow<'static, str>>, value: impl AttrValue) -> Result<(), TreeError> {
+        self.check_writable()?;
+        self.set_attr(name, value);
+        Ok(())
+    }
+
+    /// 设置内容，不会panic
+    pub fn try_configcnt(&self, content: impl Into<String>) -> Result<&Self, TreeError> {
+        self.check_writable()?;
+        Ok(self.configcnt(content))
+    }
+
+    /// 移除指定位置子元素，不会panic
+    pub fn try_remove_child(&self, index: usize) -> Result<Option<Element>, TreeError> {
+        self.check_writable()?;
+        if let Some(child) = self.child(index)
+            && child.inner.try_borrow_mut().is_err()
+        {
+            return Err(TreeError::Borrowed);
+        }
+        Ok(self.remove_child(index))
+    }
+
+    /// 获取子元素，不会panic
+    pub fn try_children(&self) -> Result<Vec<Element>, TreeError> {
+        let inner = self.inner.try_borrow().map_err(|_| TreeError::Borrowed)?;
+        Ok(inner.children.clone())
+    }
+
+    /// 渲染为html字符串，不会panic
+    ///
+    /// `<head>`会读取整个文档的作用域样式，因此需要整个文档都可以借用
+    pub fn try_render(&self, split_s: &str) -> Result<String, TreeError> {
+        let mut root = self.clone();
+        while let Some(parent) = try_parent(&root)? {
+            root = parent;
+        }
+        if !subtree_free(&root, false) {
+            return Err(TreeError::Borrowed);
+        }
+        Ok(self.render(split_s))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_api() {
+        let body = Element::new("body", "");
+        let ul = Element::ul_from(["a", "b"]);
+        body.try_add(ul.clone()).unwrap();
+        assert_eq!(ul.try_add(body.clone()).unwrap_err(), TreeError::Cycle);
+        assert_eq!(ul.try_add(ul.clone()).unwrap_err(), TreeError::Cycle);
+        body.try_add("footer").unwrap();
+
+        let li = ul.child(0).unwrap();
+        {
+            // 只读借用父元素时可以修改子元素
+            let items = ul.children_ref();
+            li.try_set_attr("class", "first").unwrap();
+            assert_eq!(ul.try_set_attr("id", "x"), Err(TreeError::Borrowed));
+            assert_eq!(ul.try_remove_child(0).unwrap_err(), TreeError::Borrowed);
+            assert!(body.try_render("").is_ok());
+            drop(items);
+        }
+        {
+            let guard = li.inner.borrow_mut();
+            assert_eq!(li.try_children().unwrap_err(), TreeError::Borrowed);
+            assert_eq!(body.try_render("").unwrap_err(), TreeError::Borrowed);
+            assert_eq!(ul.try_remove_child(0).unwrap_err(), TreeError::Borrowed);
+            assert_eq!(li.try_configcnt("x").unwrap_err(), TreeError::Borrowed);
+            drop(guard);
+        }
+        assert_eq!(body.try_render("").unwrap(), r#"<body><ul><li class="first">a</li><li>b</li></ul>footer</body>"#);
+        assert_eq!(ul.try_remove_child(5), Ok(None));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_void_element() {
+        let br = Element::new("br", "");
+        let input = Element::new("x-input", "").onetag(true);
+        assert_eq!(br.try_add("x").unwrap_err(), TreeError::VoidElement);
+        assert_eq!(input.try_add(Element::new("", "x")).unwrap_err(), TreeError::VoidElement);
+        assert!(!Element::new("", "x").move_to(&br));
+
+        let result = std::panic::catch_unwind(|| {
+            Element::new("img", "").onetag(true).add_with(Element::new("", "caption"));
+        });
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.starts_with("cannot add a child to void element `<img>`"));
+    }
+}
@@ -0,0 +1,74 @@
+//! 组件
+
+use crate::Element;
+
+/// 可复用的视图类型
+///
+/// 实现`Component`的类型可以直接传给`add`、`add_with`等方法
+///
+/// ```
+/// use htmlbuilder::{Component, Element};
+///
+/// struct Card<'a> {
+///     title: &'a str,
+///     body: &'a str,
+/// }
+///
+/// impl Component for Card<'_> {
+///     fn view(&self) -> Element {
+///         Element::new("div", "")
+///             .attrs(&[("class", "card")])
+///             .add_with(("h2", self.title))
+///             .add_with(("p", self.body))
+///     }
+/// }
+///
+/// let page = Element::new("main", "").add_with(Card { title: "Hi", body: "Hello" });
+/// assert_eq!(page.render(""), r#"<main><div class="card"><h2>Hi</h2><p>Hello</p></div></main>"#);
+/// ```
+pub trait Component {
+    /// 创建组件的元素树
+    fn view(&self) -> Element;
+}
+
+impl<C: Component> From<C> for Element {
+    fn from(component: C) -> Self {
+        component.view()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    struct Navbar {
+        links: Vec<(String, String)>,
+        active: usize,
+    }
+
+    impl Component for Navbar {
+        fn view(&self) -> Element {
+            Element::new("nav", "").with_children(self.links.iter().enumerate().map(|(i, (href, text))| {
+                Element::new("a", text.as_str()).attrs(&[("href", href)]).class_if(i == self.active, "active")
+            }))
+        }
+    }
+
+    #[test]
+    fn test_component() {
+        let navbar = Navbar {
+            links: Vec::from([("/".to_string(), "Home".to_string()), ("/about".to_string(), "About".to_string())]),
+            active: 1,
+        };
+        let header = Element::new("header", "");
+        header.add(navbar);
+        crate::assert_html_eq!(
+            header,
+            r#"<header><nav><a href="/">Home</a><a href="/about" class="active">About</a></nav></header>"#,
+        );
+    }
+}
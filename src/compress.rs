@@ -0,0 +1,94 @@
+//! 压缩渲染
+
+use alloc::vec::Vec;
+use std::io::Write;
+
+use crate::Element;
+use crate::stream::Pieces;
+
+/// 压缩格式，用于`Element::render_compressed`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// gzip，压缩级别9
+    Gzip,
+    /// brotli，压缩级别11
+    Brotli,
+}
+
+impl Encoding {
+    /// `Content-Encoding`中的名称
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Brotli => "br",
+        }
+    }
+
+    /// 预压缩文件的扩展名，如`index.html.gz`
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gz",
+            Encoding::Brotli => "br",
+        }
+    }
+}
+
+impl Element {
+    /// 渲染并压缩，需要`compress`特性
+    ///
+    /// html片段逐个写入压缩器，不生成完整的html字符串，用于直接生成预压缩的静态文件
+    ///
+    /// ```
+    /// # use htmlbuilder::{Element, Encoding};
+    /// let page = Element::new("p", "hello ".repeat(100));
+    /// let gz = page.render_compressed("", Encoding::Gzip);
+    /// assert_eq!(&gz[..2], &[0x1f, 0x8b]);
+    /// assert!(gz.len() < page.render("").len());
+    /// ```
+    pub fn render_compressed(&self, split_s: &str, encoding: Encoding) -> Vec<u8> {
+        let pieces = Pieces::new(self, split_s);
+        match encoding {
+            Encoding::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+                for piece in pieces {
+                    encoder.write_all(piece.as_bytes()).expect("writing to Vec cannot fail");
+                }
+                encoder.finish().expect("writing to Vec cannot fail")
+            }
+            Encoding::Brotli => {
+                let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+                for piece in pieces {
+                    encoder.write_all(piece.as_bytes()).expect("writing to Vec cannot fail");
+                }
+                encoder.into_inner()
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::String;
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn test_render_compressed() {
+        let page = Element::new("div", "")
+            .add_with(Element::new("h1", "<title>"))
+            .add_with(Element::new("ul", "").with_children((0..50).map(|i| Element::new("li", format!("item {}", i)))));
+        let html = page.render("\n");
+
+        let mut out = String::new();
+        flate2::read::GzDecoder::new(&page.render_compressed("\n", Encoding::Gzip)[..]).read_to_string(&mut out).unwrap();
+        assert_eq!(out, html);
+
+        let mut out = String::new();
+        brotli::Decompressor::new(&page.render_compressed("\n", Encoding::Brotli)[..], 4096).read_to_string(&mut out).unwrap();
+        assert_eq!(out, html);
+        assert_eq!(Encoding::Brotli.as_str(), "br");
+    }
+}
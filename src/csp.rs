@@ -0,0 +1,151 @@
+//! 内容安全策略（CSP）
+
+#[cfg(feature = "csp-hash")]
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "csp-hash")]
+use alloc::vec::Vec;
+
+use crate::Element;
+
+/// 是否为内联的`<script>`或`<style>`
+fn is_inline(elem: &Element) -> bool {
+    let inner = elem.inner.borrow();
+    match inner.tag.to_ascii_lowercase().as_str() {
+        "script" => !inner.kws.contains_key("src"),
+        "style" => true,
+        _ => false,
+    }
+}
+
+/// 文档中已设置的nonce，用于渲染时生成的`<style>`
+pub(crate) fn document_nonce(root: &Element) -> Option<String> {
+    if is_inline(root) {
+        let inner = root.inner.borrow();
+        if let Some(nonce) = inner.kws.get("nonce") {
+            return Some(if inner.pre { nonce.clone() } else { crate::un_escape_ascii(nonce) });
+        }
+    }
+    root.children().iter().find_map(document_nonce)
+}
+
+/// 渲染后的元素内容
+#[cfg(feature = "csp-hash")]
+fn inline_text(elem: &Element) -> String {
+    let inner = elem.inner.borrow();
+    let mut text = inner.content.clone().into_owned();
+    for child in &inner.children {
+        text.push_str(&child.render(""));
+    }
+    text
+}
+
+#[cfg(feature = "csp-hash")]
+fn collect_hashes(elem: &Element, scoped: &crate::scoped::ScopedStyle, hashes: &mut Vec<String>) {
+    use base64::Engine;
+    use sha2::{Digest, Sha256};
+
+    let mut texts = Vec::new();
+    if is_inline(elem) {
+        texts.push(inline_text(elem));
+    }
+    if let Some(style) = scoped.for_tag(&elem.inner.borrow().tag) {
+        texts.push(inline_text(style));
+    }
+    for text in texts {
+        let digest = Sha256::digest(text.as_bytes());
+        let hash = format!("'sha256-{}'", base64::engine::general_purpose::STANDARD.encode(digest));
+        if !hashes.contains(&hash) {
+            hashes.push(hash);
+        }
+    }
+    for child in elem.children() {
+        collect_hashes(&child, scoped, hashes);
+    }
+}
+
+impl Element {
+    /// 计算树中全部内联`<script>`和`<style>`内容的SHA-256哈希，格式为`'sha256-...'`，
+    /// 可直接用于`Content-Security-Policy`的`script-src`、`style-src`。需要`csp-hash`特性
+    ///
+    /// 按文档顺序返回，相同的内容只出现一次。包含子元素时，哈希对应`render("")`的输出
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// # use htmlbuilder::script::Script;
+    /// let body = Element::new("body", "").add_with(Script::inline("alert(1)"));
+    /// assert_eq!(body.collect_csp_hashes(), ["'sha256-bhHHL3z2vDgxUt0W3dWQOrprscmda2Y5pLsLg4GF+pI='"]);
+    /// ```
+    #[cfg(feature = "csp-hash")]
+    pub fn collect_csp_hashes(&self) -> Vec<String> {
+        let mut hashes = Vec::new();
+        collect_hashes(self, &crate::scoped::ScopedStyle::new(self), &mut hashes);
+        hashes
+    }
+
+    /// 为树中全部内联`<script>`和`<style>`设置`nonce`属性
+    ///
+    /// `<head>`中生成的作用域样式使用树中已有的nonce
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// # use htmlbuilder::script::Script;
+    /// let body = Element::new("body", "")
+    ///     .add_with(Script::inline("init()"))
+    ///     .add_with(Script::external("/app.js"));
+    /// body.apply_csp_nonce("r4nd0m");
+    /// assert_eq!(
+    ///     body.render(""),
+    ///     r#"<body><script nonce="r4nd0m">init()</script><script src="/app.js"></script></body>"#,
+    /// );
+    /// ```
+    pub fn apply_csp_nonce(&self, nonce: &str) {
+        if is_inline(self) {
+            self.set_attr("nonce", nonce);
+        }
+        for child in self.children() {
+            child.apply_csp_nonce(nonce);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+    use crate::css::Stylesheet;
+
+    #[test]
+    fn test_apply_csp_nonce() {
+        let head = Element::new("head", "").add_with(Element::style_sheet("p{}"));
+        let body = Element::new("body", "");
+        let html = Element::new("html", "").add_with(head.clone()).add_with(body.clone());
+        let class = body.scoped_style(Stylesheet::new().rule("&", &[("color", "red")]));
+
+        html.apply_csp_nonce("a\"b");
+        assert_eq!(
+            html.render("").split("<body").next().unwrap(),
+            format!(
+                "<html><head><style nonce=\"a&quot;b\">p{{}}</style><style nonce=\"a&quot;b\">.{}{{color:red}}</style></head>",
+                class
+            ),
+        );
+    }
+
+    #[cfg(feature = "csp-hash")]
+    #[test]
+    fn test_collect_csp_hashes() {
+        let head = Element::new("head", "").add_with(Element::style_sheet(""));
+        let body = Element::new("body", "")
+            .add_with(crate::script::Script::external("/a.js"))
+            .add_with(Element::style_sheet(""));
+        let html = Element::new("html", "").add_with(head).add_with(body.clone());
+        let empty = "'sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU='";
+        assert_eq!(html.collect_csp_hashes(), [empty]);
+
+        body.scoped_style(Stylesheet::new().rule("&", &[("color", "red")]));
+        assert_eq!(html.collect_csp_hashes().len(), 2);
+    }
+}
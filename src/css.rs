@@ -0,0 +1,199 @@
+//! css样式表构建
+//!
+//! ```
+//! use htmlbuilder::css::Stylesheet;
+//!
+//! let sheet = Stylesheet::new()
+//!     .rule(".card", &[("color", "red"), ("padding", "4px")])
+//!     .media("(max-width: 600px)", Stylesheet::new().rule(".card", &[("padding", "0")]));
+//! assert_eq!(
+//!     sheet.to_css(),
+//!     ".card{color:red;padding:4px}@media (max-width: 600px){.card{padding:0}}",
+//! );
+//! let style = sheet.element();
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Element;
+
+/// 一条样式规则
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub selector: String,
+    pub declarations: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    Rule(Rule),
+    Media(String, Stylesheet),
+}
+
+/// 样式表
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stylesheet {
+    blocks: Vec<Block>,
+}
+
+impl Stylesheet {
+    /// 创建空样式表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加规则
+    ///
+    /// selector: 选择器
+    ///
+    /// declarations: `(属性, 值)`列表
+    pub fn rule(mut self, selector: impl Into<String>, declarations: &[(&str, &str)]) -> Self {
+        self.blocks.push(Block::Rule(Rule {
+            selector: selector.into(),
+            declarations: declarations
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }));
+        self
+    }
+
+    /// 添加`@media`块
+    pub fn media(mut self, query: impl Into<String>, sheet: Stylesheet) -> Self {
+        self.blocks.push(Block::Media(query.into(), sheet));
+        self
+    }
+
+    /// 合并另一个样式表的全部规则
+    pub fn extend(mut self, other: Stylesheet) -> Self {
+        self.blocks.extend(other.blocks);
+        self
+    }
+
+    /// 顶层规则（不含`@media`块中的规则）
+    pub fn rules(&self) -> impl Iterator<Item = &Rule> {
+        self.blocks.iter().filter_map(|block| match block {
+            Block::Rule(rule) => Some(rule),
+            Block::Media(..) => None,
+        })
+    }
+
+    /// 是否没有任何规则
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// 将全部选择器限定在scope之下，`&`替换为scope本身
+    pub(crate) fn scoped(&self, scope: &str) -> Stylesheet {
+        let blocks = self.blocks.iter().map(|block| match block {
+            Block::Rule(rule) => {
+                let selector = split_selectors(&rule.selector)
+                    .into_iter()
+                    .map(|sel| {
+                        let sel = sel.trim();
+                        if sel.contains('&') {
+                            sel.replace('&', scope)
+                        } else {
+                            format!("{} {}", scope, sel)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",");
+                Block::Rule(Rule { selector, declarations: rule.declarations.clone() })
+            }
+            Block::Media(query, sheet) => Block::Media(query.clone(), sheet.scoped(scope)),
+        });
+        Stylesheet { blocks: blocks.collect() }
+    }
+
+    /// 输出为css字符串
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+
+    /// 输出为`<style>`元素
+    pub fn element(&self) -> Element {
+        Element::style_sheet(self.to_css())
+    }
+}
+
+/// 按顶层的`,`拆分选择器列表，括号（如`:is(a, b)`）和引号中的`,`不拆分
+fn split_selectors(selector: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in selector.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, c) {
+            (_, '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                parts.push(&selector[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&selector[start..]);
+    parts
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{{", self.selector)?;
+        for (i, (k, v)) in self.declarations.iter().enumerate() {
+            if i > 0 {
+                f.write_str(";")?;
+            }
+            write!(f, "{}:{}", k, v)?;
+        }
+        f.write_str("}")
+    }
+}
+
+impl fmt::Display for Stylesheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for block in &self.blocks {
+            match block {
+                Block::Rule(rule) => write!(f, "{}", rule)?,
+                Block::Media(query, sheet) => write!(f, "@media {}{{{}}}", query, sheet)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stylesheet() {
+        let sheet = Stylesheet::new()
+            .rule("ul > li", &[("color", "red")])
+            .extend(Stylesheet::new().rule("p", &[]));
+        assert_eq!(sheet.rules().count(), 2);
+        assert_eq!(sheet.element().render(""), "<style>ul > li{color:red}p{}</style>");
+    }
+
+    #[test]
+    fn test_scoped_selectors() {
+        let sheet = Stylesheet::new()
+            .rule("&:is(a, b), [title=\"a,b\"] > p", &[("color", "red")])
+            .scoped(".s");
+        assert_eq!(sheet.to_css(), ".s:is(a, b),.s [title=\"a,b\"] > p{color:red}");
+    }
+}
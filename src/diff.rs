@@ -0,0 +1,344 @@
+//! 元素树差异比较
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::diff::{diff, Patch};
+//!
+//! let old = Element::new("ul", "").add_with(Element::new("li", "a"));
+//! let new = Element::new("ul", "").add_with(Element::new("li", "b"));
+//! let patches = diff(&old, &new);
+//! assert_eq!(patches, vec![Patch::SetText { path: vec![0], text: "b".to_string() }]);
+//!
+//! old.apply(&patches).unwrap();
+//! assert_eq!(old.render(""), new.render(""));
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Element, attr_name, escape_ascii};
+
+/// 修改操作
+///
+/// path为从根元素开始逐层的子元素下标，空path表示根元素本身。
+/// 按顺序应用时，每个操作的path都基于前面操作已经完成后的树
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Patch {
+    /// 设置属性（值未转义）
+    SetAttr { path: Vec<usize>, name: String, value: String },
+    /// 删除属性
+    RemoveAttr { path: Vec<usize>, name: String },
+    /// 设置元素自身的内容（未转义）
+    SetText { path: Vec<usize>, text: String },
+    /// 在index处插入子元素
+    InsertChild { path: Vec<usize>, index: usize, node: Element },
+    /// 删除index处的子元素
+    RemoveChild { path: Vec<usize>, index: usize },
+    /// 替换整个元素
+    ReplaceNode { path: Vec<usize>, node: Element },
+}
+
+/// 元素自身（不含子元素）是否可以原地修改，作用域样式没有对应的操作，不同时整个替换
+fn same_kind(a: &Element, b: &Element) -> bool {
+    let (a, b) = (a.inner.borrow(), b.inner.borrow());
+    a.tag == b.tag && a.onetag == b.onetag && a.pre == b.pre && a.rawtext == b.rawtext && a.scoped_css == b.scoped_css
+}
+
+/// 两棵树结构和内容是否完全相同
+fn same_tree(a: &Element, b: &Element) -> bool {
+    if !same_kind(a, b) {
+        return false;
+    }
+    let (ai, bi) = (a.inner.borrow(), b.inner.borrow());
+    ai.content == bi.content
+        && ai.kws == bi.kws
+        && ai.children.len() == bi.children.len()
+        && ai.children.iter().zip(&bi.children).all(|(x, y)| same_tree(x, y))
+}
+
+fn diff_node(old: &Element, new: &Element, path: &mut Vec<usize>, patches: &mut Vec<Patch>) {
+    if !same_kind(old, new) {
+        patches.push(Patch::ReplaceNode { path: path.clone(), node: new.deep_clone() });
+        return;
+    }
+
+    {
+        let (o, n) = (old.inner.borrow(), new.inner.borrow());
+
+        // 属性
+        let mut names: Vec<&Cow<'static, str>> = n.kws.keys().collect();
+        names.sort();
+        for name in names {
+            let value = &n.kws[name];
+            if o.kws.get(name) != Some(value) {
+                let value = if n.pre { value.clone() } else { crate::un_escape_ascii(value) };
+                patches.push(Patch::SetAttr { path: path.clone(), name: name.to_string(), value });
+            }
+        }
+        let mut removed: Vec<&Cow<'static, str>> = o.kws.keys().filter(|k| !n.kws.contains_key(*k)).collect();
+        removed.sort();
+        for name in removed {
+            patches.push(Patch::RemoveAttr { path: path.clone(), name: name.to_string() });
+        }
+
+        // 内容
+        if o.content != n.content {
+            patches.push(Patch::SetText { path: path.clone(), text: n.text_content() });
+        }
+    }
+
+    // 子元素：跳过相同的前缀与后缀，中间部分按下标比较
+    let old_children = old.children();
+    let new_children = new.children();
+    let (ol, nl) = (old_children.len(), new_children.len());
+
+    let mut start = 0;
+    while start < ol.min(nl) && same_tree(&old_children[start], &new_children[start]) {
+        start += 1;
+    }
+    let mut suffix = 0;
+    while suffix < (ol - start).min(nl - start)
+        && same_tree(&old_children[ol - 1 - suffix], &new_children[nl - 1 - suffix])
+    {
+        suffix += 1;
+    }
+    let (old_end, new_end) = (ol - suffix, nl - suffix);
+    let paired = (old_end - start).min(new_end - start);
+
+    for i in start..start + paired {
+        path.push(i);
+        diff_node(&old_children[i], &new_children[i], path, patches);
+        path.pop();
+    }
+    for (i, node) in new_children.iter().enumerate().take(new_end).skip(start + paired) {
+        patches.push(Patch::InsertChild { path: path.clone(), index: i, node: node.deep_clone() });
+    }
+    for _ in start + paired..old_end {
+        patches.push(Patch::RemoveChild { path: path.clone(), index: start + paired });
+    }
+}
+
+/// 比较两棵元素树，返回将old修改为new所需的操作
+pub fn diff(old: &Element, new: &Element) -> Vec<Patch> {
+    let mut patches = Vec::new();
+    diff_node(old, new, &mut Vec::new(), &mut patches);
+    patches
+}
+
+/// 应用修改操作失败
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchError {
+    /// 第n个操作的path不存在
+    PathNotFound(usize),
+    /// 第n个操作的子元素下标越界
+    IndexOutOfRange(usize),
+    /// 第n个操作向空元素插入子元素
+    VoidParent(usize),
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::PathNotFound(n) => write!(f, "patch {}: path not found", n),
+            PatchError::IndexOutOfRange(n) => write!(f, "patch {}: child index out of range", n),
+            PatchError::VoidParent(n) => write!(f, "patch {}: cannot insert into a void element", n),
+        }
+    }
+}
+
+impl core::error::Error for PatchError {}
+
+/// 用node的内容替换elem，elem在树中的位置不变
+fn replace_with(elem: &Element, node: &Element) {
+    let node = node.deep_clone();
+    elem.remove_all_children();
+    {
+        let mut target = elem.inner.borrow_mut();
+        let source = node.inner.borrow();
+        target.tag = source.tag.clone();
+        target.content = source.content.clone();
+        target.kws = source.kws.clone();
+        target.onetag = source.onetag;
+        target.pre = source.pre;
+        target.rawtext = source.rawtext;
+        target.scoped_css = source.scoped_css.clone();
+        target.invalidate();
+    }
+    for child in node.children() {
+        elem.push_child(child);
+    }
+}
+
+impl Element {
+    /// 按顺序应用修改操作，使元素树与`diff`的目标一致
+    ///
+    /// 插入和替换使用节点的拷贝，同一组操作可以应用到多棵树上。
+    /// 遇到无效的path或下标时停止，已应用的操作不会撤销
+    pub fn apply(&self, patches: &[Patch]) -> Result<(), PatchError> {
+        for (n, patch) in patches.iter().enumerate() {
+            let path = match patch {
+                Patch::SetAttr { path, .. }
+                | Patch::RemoveAttr { path, .. }
+                | Patch::SetText { path, .. }
+                | Patch::InsertChild { path, .. }
+                | Patch::RemoveChild { path, .. }
+                | Patch::ReplaceNode { path, .. } => path,
+            };
+            let elem = Element::at_path(self, path).ok_or(PatchError::PathNotFound(n))?;
+            match patch {
+                Patch::SetAttr { name, value, .. } => {
+                    let mut inner = elem.inner.borrow_mut();
+                    let value = if inner.pre { value.clone() } else { escape_ascii(value) };
+                    inner.kws.insert(attr_name(name.clone()), value);
+                    inner.invalidate();
+                }
+                Patch::RemoveAttr { name, .. } => {
+                    let mut inner = elem.inner.borrow_mut();
+                    inner.kws.remove(name.as_str());
+                    inner.invalidate();
+                }
+                Patch::SetText { text, .. } => {
+                    elem.configcnt(text.as_str());
+                }
+                Patch::InsertChild { index, node, .. } => {
+                    if elem.inner.borrow().is_void() {
+                        return Err(PatchError::VoidParent(n));
+                    }
+                    if !node.deep_clone().move_to_index(&elem, *index) {
+                        return Err(PatchError::IndexOutOfRange(n));
+                    }
+                }
+                Patch::RemoveChild { index, .. } => {
+                    elem.remove_child(*index).ok_or(PatchError::IndexOutOfRange(n))?;
+                }
+                Patch::ReplaceNode { node, .. } => replace_with(&elem, node),
+            }
+        }
+        Ok(())
+    }
+
+    /// 深拷贝整棵子树，新的根元素没有父元素
+    pub fn deep_clone(&self) -> Element {
+        let inner = self.inner.borrow();
+        let elem = Element::new("", "");
+        {
+            let mut copy = elem.inner.borrow_mut();
+            copy.tag = inner.tag.clone();
+            copy.content = inner.content.clone();
+            copy.kws = inner.kws.clone();
+            copy.onetag = inner.onetag;
+            copy.pre = inner.pre;
+            copy.rawtext = inner.rawtext;
+            copy.scoped_css = inner.scoped_css.clone();
+            #[cfg(feature = "track-source")]
+            {
+                copy.source = inner.source.clone();
+            }
+        }
+        for child in &inner.children {
+            elem.push_child(child.deep_clone());
+        }
+        elem
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    fn list(items: &[&str]) -> Element {
+        Element::ul_from(items.iter().copied())
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff(&list(&["a", "b"]), &list(&["a", "b"])), vec![]);
+
+        // 中间插入只产生一个操作
+        let patches = diff(&list(&["a", "c"]), &list(&["a", "b", "c"]));
+        assert_eq!(patches.len(), 1);
+        assert!(matches!(&patches[0], Patch::InsertChild { path, index: 1, .. } if path.is_empty()));
+
+        let patches = diff(&list(&["a", "b", "c"]), &list(&["a"]));
+        assert_eq!(patches, vec![
+            Patch::RemoveChild { path: vec![], index: 1 },
+            Patch::RemoveChild { path: vec![], index: 1 },
+        ]);
+
+        let old = Element::new("div", "").attrs(&[("id", "x"), ("class", "a")]);
+        let new = Element::new("div", "").attrs(&[("id", "x"), ("title", "<t>")]);
+        assert_eq!(diff(&old, &new), vec![
+            Patch::SetAttr { path: vec![], name: "title".to_string(), value: "<t>".to_string() },
+            Patch::RemoveAttr { path: vec![], name: "class".to_string() },
+        ]);
+
+        let patches = diff(&Element::new("div", ""), &Element::new("section", ""));
+        assert!(matches!(&patches[..], [Patch::ReplaceNode { .. }]));
+    }
+
+    #[test]
+    fn test_apply() {
+        let cases = [
+            (list(&["a", "b", "c"]), list(&["a", "x", "c", "d"])),
+            (list(&["a", "b", "c", "d"]), list(&["b"])),
+            (list(&[]), list(&["a", "b"])),
+            (
+                Element::new("div", "t").attrs(&[("id", "x"), ("class", "a&b")])
+                    .add_with(Element::new("p", "1").add_with(Element::new("em", "e"))),
+                Element::new("div", "<t>").attrs(&[("title", "\"q\"")])
+                    .add_with(Element::new("p", "1").add_with(Element::new("strong", "e")))
+                    .add_with(Element::new("br", "").onetag(true)),
+            ),
+            (Element::new("div", "").add_with(Element::new("p", "")), Element::new("section", "s")),
+        ];
+        for (old, new) in cases {
+            let patches = diff(&old, &new);
+            old.apply(&patches).unwrap();
+            assert!(same_tree(&old, &new), "{}", old.render(""));
+            assert_eq!(diff(&old, &new), vec![]);
+        }
+
+        // 插入的是拷贝，父元素指向新树
+        let old = list(&[]);
+        let new = list(&["a"]);
+        old.apply(&diff(&list(&[]), &new)).unwrap();
+        assert_eq!(old.children()[0].parent(), Some(old.clone()));
+        assert_ne!(old.children()[0], new.children()[0]);
+
+        // 添加子元素后才设为单标签的树也可以拷贝和替换
+        let br = Element::new("div", "").add_with(Element::new("b", "x")).onetag(true);
+        assert_eq!(br.deep_clone().child_count(), 1);
+        let target = Element::new("p", "");
+        target.apply(&[Patch::ReplaceNode { path: vec![], node: br }]).unwrap();
+        assert_eq!(target.child_count(), 1);
+
+        let err = old.apply(&[Patch::RemoveChild { path: vec![5], index: 0 }]);
+        assert_eq!(err, Err(PatchError::PathNotFound(0)));
+        let err = old.apply(&[Patch::InsertChild { path: vec![], index: 3, node: Element::new("li", "") }]);
+        assert_eq!(err, Err(PatchError::IndexOutOfRange(0)));
+        let img = Element::new("img", "");
+        let err = img.apply(&[Patch::InsertChild { path: vec![], index: 0, node: Element::new("b", "") }]);
+        assert_eq!(err, Err(PatchError::VoidParent(0)));
+        assert_eq!(img.child_count(), 0);
+
+        // 插入后缓存失效
+        let cached = list(&["a"]);
+        cached.cache_render(true);
+        assert_eq!(cached.render(""), "<ul><li>a</li></ul>");
+        cached.apply(&[Patch::InsertChild { path: vec![], index: 0, node: Element::new("li", "b") }]).unwrap();
+        assert_eq!(cached.render(""), "<ul><li>b</li><li>a</li></ul>");
+
+        // 作用域样式不同时整个替换
+        let old = Element::new("div", "");
+        let new = Element::new("div", "");
+        new.inner.borrow_mut().scoped_css = Some(".s p{color:red}".to_string());
+        assert!(!same_tree(&old, &new));
+        assert!(matches!(&diff(&old, &new)[..], [Patch::ReplaceNode { .. }]));
+    }
+}
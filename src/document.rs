@@ -0,0 +1,92 @@
+//! html5文档骨架
+
+use alloc::string::String;
+
+use crate::Element;
+use crate::tags::{body, head, html, meta, text, title};
+
+/// 完整的html5文档：`<!DOCTYPE html>`以及`<html>`、`<head>`、`<body>`
+///
+/// ```
+/// # use htmlbuilder::{Document, Element};
+/// let doc = Document::html5().lang("zh-CN").title("首页");
+/// doc.body().add(Element::new("h1", "Hello"));
+/// let html = doc.render("");
+/// assert!(html.starts_with(r#"<!DOCTYPE html><html lang="zh-CN"><head><meta charset="utf-8"><meta "#));
+/// assert!(html.ends_with("<title>首页</title></head><body><h1>Hello</h1></body></html>"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Document {
+    html: Element,
+    head: Element,
+    body: Element,
+}
+
+impl Document {
+    /// 创建文档，`lang`为`en`，`head`中包含`charset`和`viewport`
+    pub fn html5() -> Self {
+        let charset = meta();
+        charset.set_attr("charset", "utf-8");
+        let viewport = meta();
+        viewport.set_attr("name", "viewport");
+        viewport.set_attr("content", "width=device-width, initial-scale=1");
+
+        let head = head().add_with(charset).add_with(viewport);
+        let body = body();
+        let html = html().add_with(head.clone()).add_with(body.clone());
+        html.set_attr("lang", "en");
+        Self { html, head, body }
+    }
+
+    /// 设置`<html>`的`lang`属性
+    pub fn lang(self, lang: impl Into<String>) -> Self {
+        self.html.set_attr("lang", lang.into());
+        self
+    }
+
+    /// 在`head`中添加`<title>`
+    pub fn title(self, t: impl Into<String>) -> Self {
+        self.head.add(title().add_with(text(t)));
+        self
+    }
+
+    /// `<html>`元素
+    pub fn html(&self) -> Element {
+        self.html.clone()
+    }
+
+    /// `<head>`元素
+    pub fn head(&self) -> Element {
+        self.head.clone()
+    }
+
+    /// `<body>`元素
+    pub fn body(&self) -> Element {
+        self.body.clone()
+    }
+
+    /// 渲染整个文档，包括`<!DOCTYPE html>`
+    pub fn render(&self, split_s: &str) -> String {
+        let mut htmltext = String::with_capacity(15 + split_s.len());
+        htmltext.push_str("<!DOCTYPE html>");
+        htmltext.push_str(split_s);
+        self.html.render_into(split_s, &mut htmltext);
+        htmltext
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html5() {
+        let doc = Document::html5();
+        doc.head().add(Element::style_sheet("p {}"));
+        assert_eq!(doc.html().child_count(), 2);
+        assert_eq!(doc.body().parent(), Some(doc.html()));
+        assert_eq!(doc.render("\n").lines().next(), Some("<!DOCTYPE html>"));
+        assert!(doc.render("").ends_with("<style>p {}</style></head><body></body></html>"));
+    }
+}
@@ -0,0 +1,110 @@
+//! 浏览器DOM互操作
+
+use alloc::format;
+
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::scoped::ScopedStyle;
+use crate::{Element, is_void_tag};
+
+/// 将元素转为DOM节点
+fn to_node(elem: &Element, scoped: &ScopedStyle, document: &web_sys::Document) -> Result<web_sys::Node, JsValue> {
+    let inner = elem.inner.borrow();
+    if inner.tag.is_empty() {
+        if inner.pre {
+            // 原样输出的html交给浏览器解析
+            let template: web_sys::HtmlTemplateElement = document.create_element("template")?.unchecked_into();
+            template.set_inner_html(&inner.content);
+            return Ok(template.content().into());
+        }
+        return Ok(document.create_text_node(&inner.text_content()).into());
+    }
+
+    let node = document.create_element(&inner.tag)?;
+    for (k, v) in &inner.kws {
+        let value = if inner.pre { v.clone() } else { crate::un_escape_ascii(v) };
+        node.set_attribute(k, &value)?;
+    }
+    let text = inner.text_content();
+    if !text.is_empty() {
+        node.append_child(&document.create_text_node(&text))?;
+    }
+    for child in &inner.children {
+        node.append_child(&to_node(child, scoped, document)?)?;
+    }
+    if let Some(style) = scoped.for_tag(&inner.tag) {
+        node.append_child(&to_node(style, scoped, document)?)?;
+    }
+    Ok(node.into())
+}
+
+/// 将DOM节点转为元素，不支持的节点类型返回`None`
+fn from_node(node: &web_sys::Node) -> Option<Element> {
+    match node.node_type() {
+        web_sys::Node::TEXT_NODE | web_sys::Node::CDATA_SECTION_NODE => {
+            Some(Element::new("", node.text_content().unwrap_or_default()))
+        }
+        web_sys::Node::COMMENT_NODE => {
+            let comment = Element::new("", "").pre(true);
+            comment.configcnt(format!("<!--{}-->", node.text_content().unwrap_or_default()));
+            Some(comment)
+        }
+        web_sys::Node::ELEMENT_NODE => {
+            let dom: &web_sys::Element = node.unchecked_ref();
+            let tag = dom.local_name();
+            let raw = matches!(tag.as_str(), "script" | "style");
+            let elem = Element::new(tag.clone(), "").onetag(is_void_tag(&tag)).rawtext(raw);
+            for name in dom.get_attribute_names().iter().filter_map(|n| n.as_string()) {
+                if let Some(value) = dom.get_attribute(&name) {
+                    elem.set_attr(name, value);
+                }
+            }
+            if raw {
+                elem.configcnt(node.text_content().unwrap_or_default());
+                return Some(elem);
+            }
+            // <template>的内容在content中
+            let children = match dom.dyn_ref::<web_sys::HtmlTemplateElement>() {
+                Some(template) => template.content().child_nodes(),
+                None => node.child_nodes(),
+            };
+            for i in 0..children.length() {
+                if let Some(child) = children.item(i).as_ref().and_then(from_node) {
+                    elem.push_child(child);
+                }
+            }
+            Some(elem)
+        }
+        _ => None,
+    }
+}
+
+impl Element {
+    /// 读取浏览器DOM子树，重建为元素树。需要`wasm`特性
+    ///
+    /// 元素、文本和注释节点会被转换；`Document`和`DocumentFragment`转为包含其子节点的`<div>`
+    pub fn from_dom(node: &web_sys::Node) -> Element {
+        if let Some(elem) = from_node(node) {
+            return elem;
+        }
+        let div = Element::new("div", "");
+        let children = node.child_nodes();
+        for i in 0..children.length() {
+            if let Some(child) = children.item(i).as_ref().and_then(from_node) {
+                div.push_child(child);
+            }
+        }
+        div
+    }
+
+    /// 用`createElement`、`setAttribute`、`appendChild`创建对应的DOM元素。需要`wasm`特性
+    ///
+    /// 不经过`innerHTML`，只有`pre`文本节点中原样输出的html由浏览器解析。
+    /// 当前元素为文本节点（标签名为空）时返回错误
+    pub fn to_dom(&self, document: &web_sys::Document) -> Result<web_sys::Element, JsValue> {
+        if self.inner.borrow().tag.is_empty() {
+            return Err(JsValue::from_str("a text node cannot be converted to an element"));
+        }
+        Ok(to_node(self, &ScopedStyle::new(self), document)?.unchecked_into())
+    }
+}
@@ -0,0 +1,75 @@
+//! 元素树的缩进输出，用于调试
+
+use alloc::format;
+use alloc::string::String;
+
+use crate::Element;
+
+/// 文本和属性值超过此长度（字符）时截断
+const MAX_TEXT: usize = 40;
+
+fn truncate(s: &str) -> String {
+    match s.char_indices().nth(MAX_TEXT) {
+        Some((end, _)) => format!("{:?}…", &s[..end]),
+        None => format!("{:?}", s),
+    }
+}
+
+fn dump(elem: &Element, depth: usize, out: &mut String) {
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+    let text = elem.content();
+    if elem.inner.borrow().tag.is_empty() {
+        out.push_str(&truncate(&text));
+    } else {
+        out.push('<');
+        out.push_str(&elem.inner.borrow().tag);
+        for (k, v) in elem.attr_list() {
+            out.push_str(&format!(" {}={}", k, truncate(&v)));
+        }
+        out.push('>');
+        if !text.is_empty() {
+            out.push(' ');
+            out.push_str(&truncate(&text));
+        }
+    }
+    out.push('\n');
+    elem.for_each_child(|child| dump(child, depth + 1, out));
+}
+
+impl Element {
+    /// 以缩进格式输出整个元素树，每行一个节点，属性按名称排序，过长的文本会被截断
+    ///
+    /// `{:#?}`输出相同的内容
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b"]).attrs(&[("id", "nav")]);
+    /// assert_eq!(ul.dump_tree(), "<ul id=\"nav\">\n  <li> \"a\"\n  <li> \"b\"\n");
+    /// ```
+    pub fn dump_tree(&self) -> String {
+        let mut out = String::new();
+        dump(self, 0, &mut out);
+        out
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_dump_tree() {
+        let long = "x".repeat(50);
+        let div = Element::new("div", "")
+            .add_with(Element::new("p", "").add_with(Element::new("", long.as_str())))
+            .add_with(Element::new("img", "").attrs(&[("src", "a.png"), ("alt", "a \"b\"")]).onetag(true));
+        let expected = "<div>\n  <p>\n    \"".to_string() + &long[..40] + "\"…\n  <img alt=\"a \\\"b\\\"\" src=\"a.png\">\n";
+        assert_eq!(div.dump_tree(), expected);
+        assert_eq!(format!("{:#?}", div), expected);
+    }
+}
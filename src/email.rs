@@ -0,0 +1,189 @@
+//! 邮件html
+//!
+//! 多数邮件客户端会删除`<style>`，因此需要将样式内联到每个元素的`style`属性中
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::css::Stylesheet;
+//!
+//! let body = Element::new("body", "");
+//! let img = Element::new("img", "").onetag(true);
+//! body.add(img.clone());
+//!
+//! body.inline_styles(&Stylesheet::new().rule("img", &[("width", "120px"), ("margin", "0 auto")]));
+//! body.prepare_email();
+//! assert!(img.render("").contains("width=\"120\""));
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::css::Stylesheet;
+use crate::selector::SelectorList;
+use crate::{Element, un_escape_ascii};
+
+fn parse_style(style: &str) -> Vec<(String, String)> {
+    style.split(';')
+        .filter_map(|decl| {
+            let (k, v) = decl.split_once(':')?;
+            let (k, v) = (k.trim(), v.trim());
+            if k.is_empty() { None } else { Some((k.to_ascii_lowercase(), v.to_string())) }
+        })
+        .collect()
+}
+
+fn merge(decls: &mut Vec<(String, String)>, key: &str, value: &str) {
+    match decls.iter_mut().find(|(k, _)| k == key) {
+        Some(decl) => decl.1 = value.to_string(),
+        None => decls.push((key.to_string(), value.to_string())),
+    }
+}
+
+fn format_style(decls: &[(String, String)]) -> String {
+    decls.iter()
+        .map(|(k, v)| format!("{}: {}", k, v))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn style_of(elem: &Element) -> Option<String> {
+    let inner = elem.inner.borrow();
+    inner.kws.get("style").map(|v| if inner.pre { v.clone() } else { un_escape_ascii(v) })
+}
+
+/// 展开`margin`/`padding`简写为四个方向的属性
+fn expand_shorthand(decls: Vec<(String, String)>) -> Vec<(String, String)> {
+    let mut result = Vec::with_capacity(decls.len());
+    for (k, v) in decls {
+        if k != "margin" && k != "padding" {
+            result.push((k, v));
+            continue;
+        }
+        let values: Vec<&str> = v.split_whitespace().collect();
+        let [top, right, bottom, left] = match values[..] {
+            [a] => [a, a, a, a],
+            [a, b] => [a, b, a, b],
+            [a, b, c] => [a, b, c, b],
+            [a, b, c, d] => [a, b, c, d],
+            _ => {
+                result.push((k, v));
+                continue;
+            }
+        };
+        for (side, value) in [("top", top), ("right", right), ("bottom", bottom), ("left", left)] {
+            result.push((format!("{}-{}", k, side), value.to_string()));
+        }
+    }
+    result
+}
+
+/// css尺寸转为html属性值：`120px`→`120`，百分比保持不变
+fn dimension_attr(value: &str) -> Option<String> {
+    let value = value.trim();
+    let number = value.strip_suffix("px").unwrap_or(value);
+    if number.parse::<f64>().is_ok() {
+        Some(number.to_string())
+    } else if value.ends_with('%') && value[..value.len() - 1].parse::<f64>().is_ok() {
+        Some(value.to_string())
+    } else {
+        None
+    }
+}
+
+impl Element {
+    /// 将样式表规则内联到匹配元素的`style`属性中
+    ///
+    /// 按选择器优先级和规则顺序合并，元素原有的`style`优先级最高。
+    /// 无法内联的规则（伪类、`@media`等）会被忽略
+    pub fn inline_styles(&self, sheet: &Stylesheet) {
+        let rules: Vec<_> = sheet.rules()
+            .filter_map(|rule| Some((SelectorList::parse(&rule.selector).ok()?, rule)))
+            .collect();
+
+        fn walk(elem: &Element, rules: &[(SelectorList, &crate::css::Rule)]) {
+            let mut matched: Vec<_> = rules.iter()
+                .enumerate()
+                .filter_map(|(order, (sel, rule))| Some((sel.match_specificity(elem)?, order, *rule)))
+                .collect();
+            if !matched.is_empty() {
+                matched.sort_by_key(|(spec, order, _)| (*spec, *order));
+                let mut decls = Vec::new();
+                for (_, _, rule) in matched {
+                    for (k, v) in &rule.declarations {
+                        merge(&mut decls, k, v);
+                    }
+                }
+                for (k, v) in parse_style(&style_of(elem).unwrap_or_default()) {
+                    merge(&mut decls, &k, &v);
+                }
+                elem.set_attr("style", format_style(&decls));
+            }
+            for child in elem.children() {
+                walk(&child, rules);
+            }
+        }
+        walk(self, &rules);
+    }
+
+    /// 调整为适合邮件客户端的形式
+    ///
+    /// - `margin`/`padding`简写展开为四个方向
+    /// - `img`、`table`、`td`、`th`的css宽高同时写入`width`/`height`属性
+    pub fn prepare_email(&self) {
+        if let Some(style) = style_of(self) {
+            let decls = expand_shorthand(parse_style(&style));
+            let tag = self.inner.borrow().tag.clone();
+            if matches!(tag.as_str(), "img" | "table" | "td" | "th") {
+                for (name, key) in [("width", "width"), ("height", "height")] {
+                    let has_attr = self.inner.borrow().kws.contains_key(name);
+                    if has_attr {
+                        continue;
+                    }
+                    if let Some(value) = decls.iter().find(|(k, _)| k == key).and_then(|(_, v)| dimension_attr(v)) {
+                        self.set_attr(name, value);
+                    }
+                }
+            }
+            self.set_attr("style", format_style(&decls));
+        }
+        for child in self.children() {
+            child.prepare_email();
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attr(elem: &Element, name: &str) -> Option<String> {
+        elem.inner.borrow().kws.get(name).cloned()
+    }
+
+    #[test]
+    fn test_inline_styles() {
+        let root = Element::new("div", "");
+        let p = Element::new("p", "").attrs(&[("class", "note"), ("style", "color: green")]);
+        let td = Element::new("td", "");
+        root.add(p.clone());
+        root.add(Element::new("table", "").add_with(Element::new("tr", "").add_with(td.clone())));
+
+        let sheet = Stylesheet::new()
+            .rule("p.note", &[("font-weight", "bold")])
+            .rule("p", &[("color", "red"), ("font-weight", "normal")])
+            .rule("a:hover", &[("color", "blue")])
+            .rule("td", &[("padding", "1px 2px"), ("width", "50%")]);
+        root.inline_styles(&sheet);
+        root.prepare_email();
+
+        // 更具体的选择器优先，原有style优先级最高
+        assert_eq!(attr(&p, "style").as_deref(), Some("color: green; font-weight: bold"));
+        assert_eq!(
+            attr(&td, "style").as_deref(),
+            Some("padding-top: 1px; padding-right: 2px; padding-bottom: 1px; padding-left: 2px; width: 50%"),
+        );
+        assert_eq!(attr(&td, "width").as_deref(), Some("50%"));
+    }
+}
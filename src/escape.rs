@@ -0,0 +1,169 @@
+//! 转义策略
+//!
+//! 内容和属性在设置时按默认规则转义保存，渲染时可以通过`RenderOptions::escaper`换用其他规则。
+//! 原文本（`pre`）和原始文本元素（`style`、`script`）的内容不受影响
+//!
+//! ```
+//! use htmlbuilder::{Element, RenderOptions};
+//! use htmlbuilder::escape::StrictEscaper;
+//!
+//! let a = Element::new("a", "1 / 2").attrs(&[("title", "`x`")]);
+//! let opts = RenderOptions::new().escaper(&StrictEscaper);
+//! assert_eq!(a.render_with("", &opts), r#"<a title="&#x60;x&#x60;">1 &#x2F; 2</a>"#);
+//! ```
+
+use alloc::string::String;
+use core::fmt;
+
+/// 转义规则，`text`和`value`都是未转义的原文
+pub trait Escaper {
+    /// 转义文本内容
+    fn escape_text(&self, text: &str, out: &mut String);
+
+    /// 转义双引号中的属性值
+    fn escape_attr(&self, value: &str, out: &mut String);
+}
+
+fn escape_with(s: &str, out: &mut String, entity: impl Fn(char) -> Option<&'static str>) {
+    out.reserve(s.len());
+    for c in s.chars() {
+        match entity(c) {
+            Some(e) => out.push_str(e),
+            None => out.push(c),
+        }
+    }
+}
+
+/// 默认规则：转义`"`、`'`、`&`、`<`、`>`，与`render`相同
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultEscaper;
+
+fn default_entity(c: char) -> Option<&'static str> {
+    match c {
+        '"' => Some("&quot;"),
+        '\'' => Some("&apos;"),
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        _ => None,
+    }
+}
+
+impl Escaper for DefaultEscaper {
+    fn escape_text(&self, text: &str, out: &mut String) {
+        escape_with(text, out, default_entity);
+    }
+
+    fn escape_attr(&self, value: &str, out: &mut String) {
+        escape_with(value, out, default_entity);
+    }
+}
+
+/// 最少转义：文本只转义`&`和`<`，属性值只转义`&`和`"`，用于可信内容，输出更短
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinimalEscaper;
+
+impl Escaper for MinimalEscaper {
+    fn escape_text(&self, text: &str, out: &mut String) {
+        escape_with(text, out, |c| match c {
+            '&' => Some("&amp;"),
+            '<' => Some("&lt;"),
+            _ => None,
+        });
+    }
+
+    fn escape_attr(&self, value: &str, out: &mut String) {
+        escape_with(value, out, |c| match c {
+            '&' => Some("&amp;"),
+            '"' => Some("&quot;"),
+            _ => None,
+        });
+    }
+}
+
+/// 严格转义：在默认规则之外还转义`/`、`` ` ``和`=`，用于安全要求高的场景
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictEscaper;
+
+fn strict_entity(c: char) -> Option<&'static str> {
+    match c {
+        '/' => Some("&#x2F;"),
+        '`' => Some("&#x60;"),
+        '=' => Some("&#x3D;"),
+        _ => default_entity(c),
+    }
+}
+
+impl Escaper for StrictEscaper {
+    fn escape_text(&self, text: &str, out: &mut String) {
+        escape_with(text, out, strict_entity);
+    }
+
+    fn escape_attr(&self, value: &str, out: &mut String) {
+        escape_with(value, out, strict_entity);
+    }
+}
+
+/// 只转义属性值，文本内容原样输出，用于内容已经是html的场景
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AttrOnlyEscaper;
+
+impl Escaper for AttrOnlyEscaper {
+    fn escape_text(&self, text: &str, out: &mut String) {
+        out.push_str(text);
+    }
+
+    fn escape_attr(&self, value: &str, out: &mut String) {
+        escape_with(value, out, default_entity);
+    }
+}
+
+/// `RenderOptions`中的转义规则，按地址比较
+#[derive(Clone, Copy)]
+pub(crate) struct EscaperRef(pub(crate) &'static dyn Escaper);
+
+impl fmt::Debug for EscaperRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Escaper")
+    }
+}
+
+impl PartialEq for EscaperRef {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::addr_eq(self.0, other.0)
+    }
+}
+
+impl Eq for EscaperRef {}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{Element, RenderOptions};
+
+    use super::*;
+
+    #[test]
+    fn test_escapers() {
+        let div = Element::new("div", "")
+            .attrs(&[("title", "a=\"1\" & 'b'")])
+            .add_with(Element::new("", "x < y > z & </b>"))
+            .add_with(Element::new("pre", "<i>raw</i>").pre(true))
+            .add_with(Element::style_sheet("a > b {}"));
+        let render = |escaper: &'static dyn Escaper| div.render_with("", &RenderOptions::new().escaper(escaper));
+
+        assert_eq!(render(&DefaultEscaper), div.render(""));
+        assert_eq!(
+            render(&MinimalEscaper),
+            "<div title=\"a=&quot;1&quot; &amp; 'b'\">x &lt; y > z &amp; &lt;/b><pre><i>raw</i></pre><style>a > b {}</style></div>"
+        );
+        assert_eq!(
+            render(&StrictEscaper),
+            "<div title=\"a&#x3D;&quot;1&quot; &amp; &apos;b&apos;\">x &lt; y &gt; z &amp; &lt;&#x2F;b&gt;<pre><i>raw</i></pre><style>a > b {}</style></div>"
+        );
+        assert_eq!(
+            render(&AttrOnlyEscaper),
+            "<div title=\"a=&quot;1&quot; &amp; &apos;b&apos;\">x < y > z & </b><pre><i>raw</i></pre><style>a > b {}</style></div>"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! RSS 2.0与Atom订阅源
+//!
+//! 条目内容由元素树渲染，放在CDATA中
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::feed::{Feed, Item};
+//!
+//! let feed = Feed::new("Blog", "https://example.com/")
+//!     .description("News & notes")
+//!     .item(
+//!         Item::new("Hello", "https://example.com/hello")
+//!             .published("Wed, 01 May 2024 08:00:00 GMT")
+//!             .content(Element::new("p", "First <post>")),
+//!     );
+//! assert_eq!(feed.render_rss(), r#"<?xml version="1.0" encoding="UTF-8"?>
+//! <rss version="2.0">
+//! <channel>
+//! <title>Blog</title>
+//! <link>https://example.com/</link>
+//! <description>News &amp; notes</description>
+//! <item>
+//! <title>Hello</title>
+//! <link>https://example.com/hello</link>
+//! <guid>https://example.com/hello</guid>
+//! <pubDate>Wed, 01 May 2024 08:00:00 GMT</pubDate>
+//! <description><![CDATA[<p>First &lt;post&gt;</p>]]></description>
+//! </item>
+//! </channel>
+//! </rss>"#);
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::sitemap::XML_DECLARATION;
+use crate::{Element, RenderOptions};
+
+/// 内容为CDATA的元素，见`Element::cdata`
+fn cdata_elem(tag: &'static str, text: &str) -> Element {
+    let elem = Element::new_static(tag, "").pre(true);
+    elem.configcnt(Element::cdata(text).content());
+    elem
+}
+
+fn text_elem(tag: &'static str, text: &str) -> Element {
+    Element::new(tag, text)
+}
+
+/// 订阅源中的条目
+pub struct Item {
+    title: String,
+    link: String,
+    id: Option<String>,
+    published: Option<String>,
+    content: Option<Element>,
+}
+
+impl Item {
+    /// 创建条目
+    pub fn new(title: impl Into<String>, link: impl Into<String>) -> Self {
+        Self { title: title.into(), link: link.into(), id: None, published: None, content: None }
+    }
+
+    /// 唯一标识（RSS的`guid`、Atom的`id`），默认使用链接
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// 发布时间，RSS使用RFC 822格式，Atom使用RFC 3339格式
+    pub fn published(mut self, date: impl Into<String>) -> Self {
+        self.published = Some(date.into());
+        self
+    }
+
+    /// html内容
+    pub fn content(mut self, content: impl Into<Element>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    fn id_or_link(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.link)
+    }
+
+    fn html(&self) -> Option<String> {
+        self.content.as_ref().map(|content| content.render(""))
+    }
+}
+
+/// 订阅源构建器
+pub struct Feed {
+    title: String,
+    link: String,
+    description: Option<String>,
+    updated: Option<String>,
+    items: Vec<Item>,
+}
+
+impl Feed {
+    /// 创建订阅源，`link`为网站地址
+    pub fn new(title: impl Into<String>, link: impl Into<String>) -> Self {
+        Self { title: title.into(), link: link.into(), description: None, updated: None, items: Vec::new() }
+    }
+
+    /// 描述
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// 最后更新时间，RSS使用RFC 822格式，Atom使用RFC 3339格式
+    pub fn updated(mut self, date: impl Into<String>) -> Self {
+        self.updated = Some(date.into());
+        self
+    }
+
+    /// 添加条目
+    pub fn item(mut self, item: Item) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// 生成RSS 2.0元素树
+    pub fn to_rss(&self) -> Element {
+        let channel = Element::new("channel", "")
+            .add_with(text_elem("title", &self.title))
+            .add_with(text_elem("link", &self.link))
+            .add_with(text_elem("description", self.description.as_deref().unwrap_or("")));
+        if let Some(date) = &self.updated {
+            channel.add(text_elem("lastBuildDate", date));
+        }
+        for item in &self.items {
+            let elem = Element::new("item", "")
+                .add_with(text_elem("title", &item.title))
+                .add_with(text_elem("link", &item.link))
+                .add_with(text_elem("guid", item.id_or_link()));
+            if let Some(date) = &item.published {
+                elem.add(text_elem("pubDate", date));
+            }
+            if let Some(html) = item.html() {
+                elem.add(cdata_elem("description", &html));
+            }
+            channel.add(elem);
+        }
+        Element::new("rss", "").attrs(&[("version", "2.0")]).add_with(channel)
+    }
+
+    /// 生成Atom元素树
+    pub fn to_atom(&self) -> Element {
+        let link = |href: &str| Element::new("link", "").attrs(&[("href", href)]);
+        let feed = Element::new("feed", "")
+            .attrs(&[("xmlns", "http://www.w3.org/2005/Atom")])
+            .add_with(text_elem("title", &self.title))
+            .add_with(link(&self.link))
+            .add_with(text_elem("id", &self.link));
+        if let Some(description) = &self.description {
+            feed.add(text_elem("subtitle", description));
+        }
+        if let Some(date) = &self.updated {
+            feed.add(text_elem("updated", date));
+        }
+        for item in &self.items {
+            let entry = Element::new("entry", "")
+                .add_with(text_elem("title", &item.title))
+                .add_with(link(&item.link))
+                .add_with(text_elem("id", item.id_or_link()));
+            if let Some(date) = &item.published {
+                entry.add(text_elem("updated", date));
+            }
+            if let Some(html) = item.html() {
+                let content = cdata_elem("content", &html);
+                content.set_attr("type", "html");
+                entry.add(content);
+            }
+            feed.add(entry);
+        }
+        feed
+    }
+
+    /// 渲染为RSS 2.0文档
+    pub fn render_rss(&self) -> String {
+        render_xml(&self.to_rss())
+    }
+
+    /// 渲染为Atom文档
+    pub fn render_atom(&self) -> String {
+        render_xml(&self.to_atom())
+    }
+}
+
+fn render_xml(root: &Element) -> String {
+    let xml = root.render_with("\n", &RenderOptions::new().xml(true));
+    format!("{}\n{}", XML_DECLARATION, xml)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atom() {
+        let feed = Feed::new("Blog", "https://example.com/")
+            .updated("2024-05-01T08:00:00Z")
+            .item(Item::new("A", "/a").id("urn:a").content(Element::new("", "x ]]> y").pre(true)));
+        let atom = feed.render_atom();
+        assert!(atom.contains("<link href=\"https://example.com/\"/>\n<id>https://example.com/</id>\n<updated>2024-05-01T08:00:00Z</updated>"));
+        assert!(atom.contains("<id>urn:a</id>"));
+        crate::assert_html_eq!(
+            feed.to_atom().children()[4].children()[3].render(""),
+            r#"<content type="html"><![CDATA[x ]]]]><![CDATA[> y]]></content>"#,
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! 写入文件
+
+use alloc::format;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::{Element, RenderOptions};
+
+/// 临时文件序号，同一进程中同时写入同一文件时避免冲突
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+impl Element {
+    /// 按选项渲染并写入文件，需要`std`特性
+    ///
+    /// 先写入同一目录下的临时文件并同步到磁盘，再重命名覆盖目标文件，
+    /// 写入中途出错或进程崩溃时不会留下不完整的目标文件
+    ///
+    /// ```no_run
+    /// # use htmlbuilder::{Element, RenderOptions};
+    /// let page = Element::new("html", "");
+    /// page.write_to_file("public/index.html", "\n", &RenderOptions::new())?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn write_to_file(&self, path: impl AsRef<Path>, split_s: &str, opts: &RenderOptions) -> io::Result<()> {
+        let path = path.as_ref();
+        let name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+        let temp = path.with_file_name(format!(
+            ".{}.{}-{}.tmp",
+            name.to_string_lossy(),
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed),
+        ));
+        let html = self.render_with(split_s, opts);
+        let result = File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(html.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&temp, path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        result
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_to_file() {
+        let dir = std::env::temp_dir().join("htmlbuilder-write-to-file");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.html");
+        let ul = Element::ul_from(["a"]);
+        ul.write_to_file(&path, "\n", &RenderOptions::new()).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), ul.render("\n"));
+
+        ul.add(Element::new("li", "b"));
+        ul.write_to_file(&path, "", &RenderOptions::new()).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "<ul><li>a</li><li>b</li></ul>");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        // 目录不存在时出错，不留下临时文件
+        assert!(ul.write_to_file(dir.join("missing/index.html"), "", &RenderOptions::new()).is_err());
+        assert!(ul.write_to_file("", "", &RenderOptions::new()).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
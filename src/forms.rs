@@ -0,0 +1,501 @@
+//! 表单构建
+//!
+//! 自动为每个控件生成`id`，并与`<label for>`关联
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::forms::Form;
+//!
+//! let form: Element = Form::new("/login", "post")
+//!     .text_input("user", "用户名")
+//!     .password("pass", "密码")
+//!     .checkbox("remember", "记住我")
+//!     .submit("登录")
+//!     .into();
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::Element;
+use crate::tags::{button, datalist, div, input, label, optgroup, option, select, textarea};
+
+/// 表单构建器
+pub struct Form {
+    form: Element,
+    id_prefix: String,
+}
+
+impl Form {
+    /// 创建表单
+    ///
+    /// action: 提交地址
+    ///
+    /// method: 提交方法（`get`/`post`）
+    pub fn new(action: impl Into<String>, method: impl Into<String>) -> Self {
+        let form = crate::tags::form();
+        form.set_attr("action", action.into());
+        form.set_attr("method", method.into());
+        Self { form, id_prefix: String::new() }
+    }
+
+    /// 设置控件id前缀，同一页面有多个表单时避免id重复
+    ///
+    /// 控件id为`前缀-name`
+    pub fn id_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.id_prefix = prefix.into();
+        self
+    }
+
+    fn field_id(&self, name: &str) -> String {
+        if self.id_prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}-{}", self.id_prefix, name)
+        }
+    }
+
+    /// 添加带标签的控件，控件设置name和id
+    fn labeled(self, name: &str, text: &str, control: Element, label_after: bool) -> Self {
+        let id = self.field_id(name);
+        control.set_attr("name", name);
+        control.set_attr("id", id.clone());
+
+        let lbl = label().add_with(Element::new("", text));
+        lbl.set_attr("for", id);
+
+        let field = div();
+        if label_after {
+            field.add(control);
+            field.add(lbl);
+        } else {
+            field.add(lbl);
+            field.add(control);
+        }
+        self.form.add(field);
+        self
+    }
+
+    /// 添加带标签的自定义控件，控件设置name和id；复选框和单选框的标签位于控件之后
+    pub fn field(self, name: &str, label: &str, control: Element) -> Self {
+        let label_after = matches!(
+            control.inner.borrow().kws.get("type").map(|t| t.as_str()),
+            Some("checkbox" | "radio")
+        );
+        self.labeled(name, label, control, label_after)
+    }
+
+    /// 添加文本输入框
+    pub fn text_input(self, name: &str, label: &str) -> Self {
+        self.labeled(name, label, input("text"), false)
+    }
+
+    /// 添加密码输入框
+    pub fn password(self, name: &str, label: &str) -> Self {
+        self.labeled(name, label, input("password"), false)
+    }
+
+    /// 添加复选框，选中时提交`name=on`，标签位于复选框之后
+    pub fn checkbox(self, name: &str, label: &str) -> Self {
+        let control = input("checkbox");
+        control.set_attr("value", "on");
+        self.labeled(name, label, control, true)
+    }
+
+    /// 添加下拉选择框
+    ///
+    /// options: `(value, 显示文本)`列表
+    pub fn select(self, name: &str, label: &str, options: &[(&str, &str)]) -> Self {
+        let control = Select::new().options(options.iter().copied()).into();
+        self.labeled(name, label, control, false)
+    }
+
+    /// 添加自定义的下拉选择框
+    pub fn select_with(self, name: &str, label: &str, select: Select) -> Self {
+        self.labeled(name, label, select.into(), false)
+    }
+
+    /// 添加多行文本框
+    pub fn textarea(self, name: &str, label: &str) -> Self {
+        self.labeled(name, label, textarea(), false)
+    }
+
+    /// 添加提交按钮
+    pub fn submit(self, label: &str) -> Self {
+        let btn = button().add_with(Element::new("", label));
+        btn.set_attr("type", "submit");
+        self.form.add(btn);
+        self
+    }
+
+    /// 获取表单元素
+    pub fn element(&self) -> Element {
+        self.form.clone()
+    }
+}
+
+impl From<Form> for Element {
+    fn from(form: Form) -> Self {
+        form.form
+    }
+}
+
+/// 由结构体生成表单，可以用`#[derive(HtmlForm)]`实现（需要`macros`特性）
+///
+/// 每个字段生成一个带标签的控件，字段名作为控件的name，字段的当前值作为控件的值。
+/// 字段属性`#[form(...)]`：
+///
+/// - `label = "..."`：标签文本，默认为首字母大写、下划线替换为空格的字段名
+/// - `type = "..."`：控件类型，如`email`、`textarea`，默认由字段类型决定（见`FormValue`）
+/// - `required`、`placeholder = "..."`：设置同名属性
+/// - `skip`：不生成控件
+///
+/// ```
+/// # #[cfg(feature = "macros")] {
+/// use htmlbuilder::forms::HtmlForm;
+///
+/// #[derive(HtmlForm)]
+/// struct Signup {
+///     #[form(label = "Email address", type = "email", required)]
+///     email: String,
+///     age: Option<u32>,
+///     newsletter: bool,
+///     #[form(skip)]
+///     id: u64,
+/// }
+///
+/// let user = Signup { email: "a@example.com".into(), age: None, newsletter: true, id: 7 };
+/// let form = user.to_form("/signup", "post").submit("Save").element();
+/// assert_eq!(form.child_count(), 4);
+/// # }
+/// ```
+pub trait HtmlForm {
+    /// 生成表单，控件的值为字段的当前值
+    fn to_form(&self, action: &str, method: &str) -> Form;
+}
+
+#[cfg(feature = "macros")]
+pub use htmlbuilder_macros::HtmlForm;
+
+/// 可以作为表单字段的值
+pub trait FormValue {
+    /// 默认的控件类型
+    const INPUT_TYPE: &'static str;
+
+    /// 将值填入控件
+    fn fill(&self, control: &Element);
+}
+
+/// 创建控件，`textarea`生成`<textarea>`，其他类型生成`<input type="...">`
+pub fn control(ty: &str) -> Element {
+    if ty == "textarea" { textarea() } else { input(ty) }
+}
+
+/// 设置控件的值，`<textarea>`设置内容
+fn set_value(control: &Element, value: String) {
+    if control.inner.borrow().tag == "textarea" {
+        control.configcnt(value);
+    } else {
+        control.set_attr("value", value);
+    }
+}
+
+impl FormValue for str {
+    const INPUT_TYPE: &'static str = "text";
+
+    fn fill(&self, control: &Element) {
+        if !self.is_empty() {
+            set_value(control, self.to_string());
+        }
+    }
+}
+
+impl FormValue for String {
+    const INPUT_TYPE: &'static str = "text";
+
+    fn fill(&self, control: &Element) {
+        self.as_str().fill(control);
+    }
+}
+
+impl FormValue for bool {
+    const INPUT_TYPE: &'static str = "checkbox";
+
+    fn fill(&self, control: &Element) {
+        control.set_attr("value", "on");
+        if *self {
+            control.set_attr("checked", "");
+        }
+    }
+}
+
+macro_rules! number_values {
+    ($($ty:ty),*) => {
+        $(
+            impl FormValue for $ty {
+                const INPUT_TYPE: &'static str = "number";
+
+                fn fill(&self, control: &Element) {
+                    set_value(control, self.to_string());
+                }
+            }
+        )*
+    };
+}
+
+number_values!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl<T: FormValue> FormValue for Option<T> {
+    const INPUT_TYPE: &'static str = T::INPUT_TYPE;
+
+    fn fill(&self, control: &Element) {
+        if let Some(value) = self {
+            value.fill(control);
+        }
+    }
+}
+
+impl<T: FormValue + ?Sized> FormValue for &T {
+    const INPUT_TYPE: &'static str = T::INPUT_TYPE;
+
+    fn fill(&self, control: &Element) {
+        (**self).fill(control);
+    }
+}
+
+enum SelectEntry {
+    Option(String, String),
+    Group(String, Vec<(String, String)>),
+}
+
+/// 下拉选择框构建器
+///
+/// 选中值在生成元素时统一处理，`selected`可以在添加选项之前调用
+///
+/// ```
+/// use htmlbuilder::Element;
+/// use htmlbuilder::forms::Select;
+///
+/// let select: Element = Select::new()
+///     .name("fruit")
+///     .option("apple", "Apple")
+///     .group("Citrus", [("orange", "Orange"), ("lemon", "Lemon")])
+///     .selected("lemon")
+///     .into();
+/// ```
+#[derive(Default)]
+pub struct Select {
+    name: Option<String>,
+    entries: Vec<SelectEntry>,
+    selected: Vec<String>,
+    multiple: bool,
+}
+
+impl Select {
+    /// 创建下拉选择框
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置name属性
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// 添加选项
+    pub fn option(mut self, value: impl Into<String>, label: impl Into<String>) -> Self {
+        self.entries.push(SelectEntry::Option(value.into(), label.into()));
+        self
+    }
+
+    /// 批量添加`(value, 显示文本)`选项
+    pub fn options<I, V, L>(mut self, options: I) -> Self
+    where
+        I: IntoIterator<Item = (V, L)>,
+        V: Into<String>,
+        L: Into<String>,
+    {
+        for (value, label) in options {
+            self = self.option(value, label);
+        }
+        self
+    }
+
+    /// 添加选项分组`<optgroup>`
+    pub fn group<I, V, L>(mut self, label: impl Into<String>, options: I) -> Self
+    where
+        I: IntoIterator<Item = (V, L)>,
+        V: Into<String>,
+        L: Into<String>,
+    {
+        let options = options.into_iter().map(|(v, l)| (v.into(), l.into())).collect();
+        self.entries.push(SelectEntry::Group(label.into(), options));
+        self
+    }
+
+    /// 设置选中值
+    ///
+    /// 非多选时只有最后一次设置的值生效
+    pub fn selected(mut self, value: impl Into<String>) -> Self {
+        self.selected.push(value.into());
+        self
+    }
+
+    /// 设置是否允许多选
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    fn make_option(&self, value: &str, label: &str) -> Element {
+        let opt = option().add_with(Element::new("", label));
+        opt.set_attr("value", value);
+        let selected = if self.multiple {
+            self.selected.iter().any(|s| s == value)
+        } else {
+            self.selected.last().is_some_and(|s| s == value)
+        };
+        if selected {
+            opt.set_attr("selected", "");
+        }
+        opt
+    }
+}
+
+impl From<Select> for Element {
+    fn from(sel: Select) -> Self {
+        let elem = select();
+        if let Some(name) = &sel.name {
+            elem.set_attr("name", name.as_str());
+        }
+        if sel.multiple {
+            elem.set_attr("multiple", "");
+        }
+        for entry in &sel.entries {
+            match entry {
+                SelectEntry::Option(value, label) => {
+                    elem.add(sel.make_option(value, label));
+                }
+                SelectEntry::Group(label, options) => {
+                    let group = optgroup();
+                    group.set_attr("label", label.as_str());
+                    for (value, text) in options {
+                        group.add(sel.make_option(value, text));
+                    }
+                    elem.add(group);
+                }
+            }
+        }
+        elem
+    }
+}
+
+/// 创建`<datalist>`，为输入框提供候选值
+///
+/// 输入框通过`list`属性引用datalist的id
+pub fn datalist_from<I, V>(id: impl Into<String>, values: I) -> Element
+where
+    I: IntoIterator<Item = V>,
+    V: Into<String>,
+{
+    let list = datalist();
+    list.set_attr("id", id.into());
+    for value in values {
+        let opt = option();
+        opt.set_attr("value", value.into());
+        list.add(opt);
+    }
+    list
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attr(elem: &Element, name: &str) -> Option<String> {
+        elem.inner.borrow().kws.get(name).cloned()
+    }
+
+    #[test]
+    fn test_form() {
+        let form = Form::new("/login", "post")
+            .id_prefix("login")
+            .text_input("user", "User")
+            .checkbox("remember", "Remember me")
+            .select("lang", "Language", &[("en", "English"), ("zh", "中文")])
+            .submit("Go")
+            .element();
+
+        assert_eq!(attr(&form, "action").as_deref(), Some("/login"));
+        let fields = form.children();
+        assert_eq!(fields.len(), 4);
+
+        let user = fields[0].children();
+        assert_eq!(user[0].render(""), "<label for=\"login-user\">User</label>");
+        assert_eq!(attr(&user[1], "id").as_deref(), Some("login-user"));
+        assert_eq!(attr(&user[1], "name").as_deref(), Some("user"));
+
+        // 复选框标签在后
+        let remember = fields[1].children();
+        assert_eq!(attr(&remember[0], "type").as_deref(), Some("checkbox"));
+        assert_eq!(attr(&remember[1], "for").as_deref(), Some("login-remember"));
+
+        let options = fields[2].children()[1].children();
+        assert_eq!(options[1].render(""), "<option value=\"zh\">中文</option>");
+
+        assert_eq!(fields[3].render(""), "<button type=\"submit\">Go</button>");
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_derive_html_form() {
+        #[derive(HtmlForm)]
+        struct Post {
+            title: String,
+            #[form(type = "textarea", placeholder = "...")]
+            body: String,
+            is_draft: bool,
+        }
+
+        let post = Post { title: "A & B".into(), body: "text".into(), is_draft: false };
+        let fields = post.to_form("/posts", "post").element().children();
+        assert_eq!(fields[0].children()[0].render(""), "<label for=\"title\">Title</label>");
+        assert_eq!(attr(&fields[0].children()[1], "value").as_deref(), Some("A &amp; B"));
+        assert_eq!(fields[1].children()[1].tag(), "textarea");
+        assert_eq!(fields[1].children()[1].content(), "text");
+        assert_eq!(fields[2].children()[1].render(""), "<label for=\"is_draft\">Is draft</label>");
+        assert_eq!(attr(&fields[2].children()[0], "checked"), None);
+    }
+
+    #[test]
+    fn test_select() {
+        let sel: Element = Select::new()
+            .selected("b")
+            .options([("a", "A"), ("b", "B")])
+            .group("More", [("c", "C")])
+            .into();
+        let options = sel.children();
+        assert_eq!(options[0].render(""), "<option value=\"a\">A</option>");
+        assert!(attr(&options[1], "selected").is_some());
+        assert_eq!(
+            options[2].render(""),
+            "<optgroup label=\"More\"><option value=\"c\">C</option></optgroup>"
+        );
+
+        // 多选保留全部选中值，单选仅保留最后一个
+        let multi: Element = Select::new().multiple(true).selected("a").selected("c")
+            .options([("a", "A"), ("b", "B"), ("c", "C")]).into();
+        let selected = multi.children().iter().filter(|o| attr(o, "selected").is_some()).count();
+        assert_eq!(selected, 2);
+        let single: Element = Select::new().selected("a").selected("c")
+            .options([("a", "A"), ("c", "C")]).into();
+        assert!(attr(&single.children()[0], "selected").is_none());
+        assert!(attr(&single.children()[1], "selected").is_some());
+
+        let list = datalist_from("browsers", ["Firefox"]);
+        assert_eq!(list.render(""), "<datalist id=\"browsers\"><option value=\"Firefox\"></option></datalist>");
+    }
+}
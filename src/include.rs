@@ -0,0 +1,75 @@
+//! 引入外部html片段
+
+use alloc::vec::Vec;
+use std::io;
+use std::path::Path;
+
+use crate::Element;
+use crate::parser::parse_fragment;
+
+/// 读取并解析文件，忽略顶层的空白文本
+fn read_fragment(path: &Path) -> io::Result<Vec<Element>> {
+    let html = std::fs::read_to_string(path)?;
+    Ok(parse_fragment(&html)
+        .into_iter()
+        .filter(|node| {
+            let inner = node.inner.borrow();
+            !(inner.tag.is_empty() && !inner.pre && inner.content.trim().is_empty())
+        })
+        .collect())
+}
+
+impl Element {
+    /// 读取并解析html文件，需要`std`特性
+    ///
+    /// 文件只有一个顶层节点时直接返回该节点，否则放在一个`<div>`中；
+    /// 不需要外层元素时使用`add_file`
+    pub fn include_file(path: impl AsRef<Path>) -> io::Result<Element> {
+        let mut nodes = read_fragment(path.as_ref())?;
+        if nodes.len() == 1 {
+            return Ok(nodes.remove(0));
+        }
+        Ok(Element::new("div", "").with_children(nodes))
+    }
+
+    /// 读取并解析html文件，将全部顶层节点添加为子元素，需要`std`特性
+    ///
+    /// ```no_run
+    /// # use htmlbuilder::Element;
+    /// let body = Element::new("body", "");
+    /// body.add_file("partials/analytics.html")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn add_file(&self, path: impl AsRef<Path>) -> io::Result<&Self> {
+        let nodes = read_fragment(path.as_ref())?;
+        Ok(self.add_all(nodes))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_include_file() {
+        let dir = std::env::temp_dir();
+        let single = dir.join("htmlbuilder-include-single.html");
+        let multi = dir.join("htmlbuilder-include-multi.html");
+        std::fs::write(&single, "\n<footer class=\"f\">&#169; 2024 <a href=\"/\">home</a></footer>\n").unwrap();
+        std::fs::write(&multi, "<script>track()</script>\n<noscript><img src=\"t.gif\"></noscript>\n").unwrap();
+
+        let footer = Element::include_file(&single).unwrap();
+        assert_eq!(footer.render(""), "<footer class=\"f\">© 2024 <a href=\"/\">home</a></footer>");
+
+        let wrapped = Element::include_file(&multi).unwrap();
+        assert_eq!(wrapped.render(""), "<div><script>track()</script><noscript><img src=\"t.gif\"></noscript></div>");
+        let body = Element::new("body", "");
+        body.add_file(&multi).unwrap();
+        assert_eq!(body.child_count(), 2);
+
+        assert!(Element::include_file(dir.join("htmlbuilder-include-missing.html")).is_err());
+        std::fs::remove_file(single).unwrap();
+        std::fs::remove_file(multi).unwrap();
+    }
+}
@@ -0,0 +1,158 @@
+//! 子元素迭代
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::Element;
+
+/// 子元素迭代器，由`&Element`的`into_iter`创建
+///
+/// 按位置逐个获取子元素，迭代期间不借用父元素，可以修改元素树
+pub struct ChildIter {
+    parent: Element,
+    index: usize,
+}
+
+impl Iterator for ChildIter {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        let child = self.parent.child(self.index)?;
+        self.index += 1;
+        Some(child)
+    }
+}
+
+/// 遍历子元素
+///
+/// ```
+/// # use htmlbuilder::Element;
+/// let ul = Element::ul_from(["a", "b"]);
+/// let mut items = Vec::new();
+/// for li in &ul {
+///     items.push(li.render(""));
+/// }
+/// assert_eq!(items, ["<li>a</li>", "<li>b</li>"]);
+/// ```
+impl IntoIterator for &Element {
+    type Item = Element;
+    type IntoIter = ChildIter;
+
+    fn into_iter(self) -> ChildIter {
+        ChildIter { parent: self.clone(), index: 0 }
+    }
+}
+
+impl IntoIterator for Element {
+    type Item = Element;
+    type IntoIter = ChildIter;
+
+    fn into_iter(self) -> ChildIter {
+        ChildIter { parent: self, index: 0 }
+    }
+}
+
+/// 深度优先（先序）遍历后代节点的迭代器，由`Element::descendants`创建
+pub struct Descendants {
+    stack: Vec<Element>,
+}
+
+impl Iterator for Descendants {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        let elem = self.stack.pop()?;
+        self.stack.extend(elem.children_ref().iter().rev().cloned());
+        Some(elem)
+    }
+}
+
+/// 广度优先（层序）遍历后代节点的迭代器，由`Element::descendants_bfs`创建
+pub struct DescendantsBfs {
+    queue: VecDeque<Element>,
+}
+
+impl Iterator for DescendantsBfs {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        let elem = self.queue.pop_front()?;
+        self.queue.extend(elem.children_ref().iter().cloned());
+        Some(elem)
+    }
+}
+
+impl Element {
+    /// 按深度优先（文档）顺序遍历全部后代节点（包括文本节点），不包括自身
+    ///
+    /// 子元素在访问父元素时取出，之后对树的修改不影响已取出的部分
+    pub fn descendants(&self) -> Descendants {
+        Descendants { stack: self.children_ref().iter().rev().cloned().collect() }
+    }
+
+    /// 按层序遍历全部后代节点（包括文本节点），不包括自身，适合查找最浅的匹配元素
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let body = Element::new("body", "")
+    ///     .add_with(Element::new("div", "").add_with(Element::new("h1", "deep")))
+    ///     .add_with(Element::new("h1", "shallow"));
+    /// let first = |mut iter: Box<dyn Iterator<Item = Element>>| iter.find(|e| e.tag() == "h1").unwrap().content();
+    /// assert_eq!(first(Box::new(body.descendants())), "deep");
+    /// assert_eq!(first(Box::new(body.descendants_bfs())), "shallow");
+    /// ```
+    pub fn descendants_bfs(&self) -> DescendantsBfs {
+        DescendantsBfs { queue: self.children_ref().iter().cloned().collect() }
+    }
+}
+
+/// 依次添加子元素
+///
+/// ```
+/// # use htmlbuilder::Element;
+/// let mut ul = Element::new("ul", "");
+/// ul.extend((1..=2).map(|i| Element::new("li", i.to_string())));
+/// assert_eq!(ul.render(""), "<ul><li>1</li><li>2</li></ul>");
+/// ```
+impl Extend<Element> for Element {
+    fn extend<I: IntoIterator<Item = Element>>(&mut self, iter: I) {
+        self.add_all(iter);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_iter() {
+        let mut ul = Element::ul_from(["a", "b"]);
+
+        // 迭代期间可以修改父元素
+        let mut seen = Vec::new();
+        for li in &ul {
+            seen.push(li.render(""));
+            if seen.len() == 1 {
+                ul.add(Element::new("li", "c"));
+            }
+        }
+        assert_eq!(seen, ["<li>a</li>", "<li>b</li>", "<li>c</li>"]);
+
+        ul.extend([Element::new("li", "d")]);
+        assert_eq!(ul.into_iter().count(), 4);
+    }
+
+    #[test]
+    fn test_descendants() {
+        let tree = Element::new("div", "")
+            .add_with(Element::new("p", "").add_with(Element::new("b", "")).add_with(Element::new("i", "")))
+            .add_with(Element::new("ul", "").add_with(Element::new("li", "")));
+        let tags = |iter: &mut dyn Iterator<Item = Element>| iter.map(|e| e.tag()).collect::<Vec<_>>();
+        assert_eq!(tags(&mut tree.descendants()), ["p", "b", "i", "ul", "li"]);
+        assert_eq!(tags(&mut tree.descendants_bfs()), ["p", "ul", "b", "i", "li"]);
+        assert_eq!(Element::new("br", "").descendants_bfs().count(), 0);
+    }
+}
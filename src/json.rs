@@ -0,0 +1,65 @@
+//! 在`<script>`中嵌入JSON数据
+
+use alloc::string::String;
+
+use serde::Serialize;
+
+use crate::Element;
+
+/// 序列化为可以安全放入`<script>`的JSON
+///
+/// `<`、`>`、`&`以及U+2028/U+2029被转为`\uXXXX`，JSON语义不变，但无法闭合script或开启注释
+pub(crate) fn to_script_json(value: &impl Serialize) -> serde_json::Result<String> {
+    let json = serde_json::to_string(value)?;
+
+    let mut result = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => result.push_str("\\u003c"),
+            '>' => result.push_str("\\u003e"),
+            '&' => result.push_str("\\u0026"),
+            '\u{2028}' => result.push_str("\\u2028"),
+            '\u{2029}' => result.push_str("\\u2029"),
+            _ => result.push(c),
+        }
+    }
+    Ok(result)
+}
+
+impl Element {
+    /// 创建JSON-LD结构化数据元素`<script type="application/ld+json">`
+    ///
+    /// 需要`json`特性
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// # use serde_json::json;
+    /// let ld = Element::json_ld(&json!({"@type": "Person", "name": "</script>"})).unwrap();
+    /// assert_eq!(
+    ///     ld.render(""),
+    ///     r#"<script type="application/ld+json">{"@type":"Person","name":"\u003c/script\u003e"}</script>"#,
+    /// );
+    /// ```
+    pub fn json_ld(value: &impl Serialize) -> serde_json::Result<Self> {
+        let script = Element::new("script", "").rawtext(true);
+        script.set_attr("type", "application/ld+json");
+        script.configcnt(to_script_json(value)?);
+        Ok(script)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_script_json() {
+        let json = to_script_json(&["<!--", "a&b", "\u{2028}"]).unwrap();
+        assert_eq!(json, r#"["\u003c!--","a\u0026b","\u2028"]"#);
+        let back: Vec<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, ["<!--", "a&b", "\u{2028}"]);
+    }
+}
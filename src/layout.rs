@@ -0,0 +1,183 @@
+//! 布局与插槽
+//!
+//! 在基础文档中放置命名插槽，每个页面填入各自的内容
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::layout::Layout;
+//!
+//! let layout = Layout::new(
+//!     Element::new("body", "")
+//!         .add_with(Element::new("main", "").add_with(Layout::slot("content")))
+//!         .add_with(Element::new("aside", "").add_with(Layout::optional_slot("sidebar"))),
+//! );
+//! let page = layout.page().fill("content", Element::new("h1", "Home")).finish().unwrap();
+//! assert_eq!(page.render(""), "<body><main><h1>Home</h1></main><aside></aside></body>");
+//! ```
+
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Element;
+
+/// 插槽标记使用的属性，插槽本身是不输出内容的文本节点
+const SLOT_ATTR: &str = "data-slot";
+const OPTIONAL_ATTR: &str = "data-slot-optional";
+
+/// 布局错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    /// 必需的插槽没有填充
+    MissingSlot(String),
+    /// 填充了布局中不存在的插槽
+    UnknownSlot(String),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::MissingSlot(name) => write!(f, "required slot `{}` is not filled", name),
+            LayoutError::UnknownSlot(name) => write!(f, "layout has no slot named `{}`", name),
+        }
+    }
+}
+
+impl core::error::Error for LayoutError {}
+
+/// 插槽名称和是否可选
+fn slot_of(elem: &Element) -> Option<(String, bool)> {
+    let inner = elem.inner.borrow();
+    if !inner.tag.is_empty() {
+        return None;
+    }
+    let name = inner.kws.get(SLOT_ATTR)?;
+    Some((crate::un_escape_ascii(name), inner.kws.contains_key(OPTIONAL_ATTR)))
+}
+
+/// 带命名插槽的基础文档
+pub struct Layout {
+    base: Element,
+}
+
+impl Layout {
+    /// 用基础文档创建布局，文档中用`slot`、`optional_slot`标记插槽
+    pub fn new(base: Element) -> Self {
+        Self { base }
+    }
+
+    /// 必需的插槽，页面没有填充时`finish`返回错误
+    pub fn slot(name: &str) -> Element {
+        let slot = Element::new("", "");
+        slot.set_attr(SLOT_ATTR, name);
+        slot
+    }
+
+    /// 可选的插槽，没有填充时不输出内容
+    pub fn optional_slot(name: &str) -> Element {
+        let slot = Self::slot(name);
+        slot.set_attr(OPTIONAL_ATTR, "");
+        slot
+    }
+
+    /// 基于布局创建页面，每个页面使用基础文档的拷贝
+    pub fn page(&self) -> Page {
+        Page { root: self.base.deep_clone(), fills: Vec::new() }
+    }
+}
+
+/// 正在填充的页面，由`Layout::page`创建
+pub struct Page {
+    root: Element,
+    fills: Vec<(String, Element)>,
+}
+
+impl Page {
+    /// 向插槽填入内容，多次填充同一插槽时按顺序排列
+    pub fn fill(mut self, name: impl Into<String>, elem: impl Into<Element>) -> Self {
+        self.fills.push((name.into(), elem.into()));
+        self
+    }
+
+    /// 用填充的内容替换插槽，返回最终的元素树
+    pub fn finish(self) -> Result<Element, LayoutError> {
+        let mut used = Vec::new();
+        replace_slots(&self.root, &self.fills, &mut used)?;
+        match self.fills.into_iter().find(|(name, _)| !used.contains(name)) {
+            Some((name, _)) => Err(LayoutError::UnknownSlot(name)),
+            None => Ok(self.root),
+        }
+    }
+}
+
+fn replace_slots(elem: &Element, fills: &[(String, Element)], used: &mut Vec<String>) -> Result<(), LayoutError> {
+    let mut index = 0;
+    while let Some(child) = elem.child(index) {
+        let Some((name, optional)) = slot_of(&child) else {
+            replace_slots(&child, fills, used)?;
+            index += 1;
+            continue;
+        };
+
+        // 同名插槽出现多次时，之后的插槽使用拷贝
+        let repeated = used.contains(&name);
+        let content: Vec<Element> = fills.iter()
+            .filter(|(n, _)| *n == name)
+            .map(|(_, e)| if repeated { e.deep_clone() } else { e.clone() })
+            .collect();
+        if content.is_empty() && !optional {
+            return Err(LayoutError::MissingSlot(name));
+        }
+        used.push(name);
+
+        let mut inner = elem.inner.borrow_mut();
+        inner.children.remove(index);
+        child.inner.borrow_mut().parent = None;
+        for node in &content {
+            node.inner.borrow_mut().parent = Some(Rc::downgrade(&elem.inner));
+        }
+        let count = content.len();
+        inner.children.splice(index..index, content);
+        inner.invalidate();
+        index += count;
+    }
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        let layout = Layout::new(
+            Element::new("html", "")
+                .add_with(Element::new("head", "").add_with(Layout::optional_slot("head")))
+                .add_with(Element::new("body", "").add_with(Layout::slot("content")).add_with(("footer", "f"))),
+        );
+
+        let page = layout.page()
+            .fill("content", ("h1", "A"))
+            .fill("content", ("p", "a"))
+            .fill("head", ("title", "A"))
+            .finish()
+            .unwrap();
+        assert_eq!(
+            page.render(""),
+            "<html><head><title>A</title></head><body><h1>A</h1><p>a</p><footer>f</footer></body></html>",
+        );
+        assert_eq!(page.children()[1].children()[1].parent(), Some(page.children()[1].clone()));
+
+        // 布局本身不受影响
+        let page = layout.page().fill("content", "text").finish().unwrap();
+        assert_eq!(page.render(""), "<html><head></head><body>text<footer>f</footer></body></html>");
+
+        assert_eq!(layout.page().finish().unwrap_err(), LayoutError::MissingSlot("content".into()));
+        assert_eq!(
+            layout.page().fill("content", "x").fill("sidebar", "y").finish().unwrap_err(),
+            LayoutError::UnknownSlot("sidebar".into()),
+        );
+    }
+}
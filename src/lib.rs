@@ -1,8 +1,115 @@
-use std::collections::HashMap;
-use std::rc::{Rc, Weak};
-use std::cell::RefCell;
-use std::fmt;
+#![no_std]
 
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+use alloc::borrow::Cow;
+use alloc::rc::{Rc, Weak};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::{Ref, RefCell};
+use core::fmt;
+
+// 使过程宏生成的`::htmlbuilder`路径在本crate内可用
+extern crate self as htmlbuilder;
+
+#[macro_use]
+mod macros;
+mod a11y;
+mod attr;
+mod builder;
+mod cache;
+mod checked;
+mod component;
+#[cfg(feature = "compress")]
+mod compress;
+#[cfg(feature = "std")]
+mod file;
+pub mod forms;
+#[cfg(feature = "std")]
+mod include;
+mod iter;
+pub mod layout;
+pub mod links;
+pub mod lists;
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "std")]
+mod memo;
+#[cfg(feature = "minify")]
+mod minify;
+pub mod meta;
+mod name;
+pub mod nav;
+mod options;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod parser;
+mod placeholder;
+mod renderer;
+#[cfg(any(feature = "actix", feature = "axum", feature = "rocket"))]
+mod response;
+#[cfg(feature = "warp")]
+pub mod reply;
+pub mod sanitize;
+pub mod sitemap;
+mod scoped;
+#[cfg(feature = "scraper")]
+mod scraper;
+pub mod script;
+mod selector;
+#[cfg(feature = "track-source")]
+mod source;
+mod csp;
+pub mod css;
+pub mod diff;
+pub mod escape;
+mod document;
+#[cfg(feature = "wasm")]
+mod dom;
+mod dump;
+mod email;
+pub mod feed;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "serde")]
+mod serialize;
+mod stats;
+mod stream;
+pub mod table;
+pub mod tags;
+pub mod testing;
+mod text;
+pub mod transform;
+mod tree;
+pub mod validate;
+#[cfg(feature = "quick-xml")]
+pub mod xml;
+pub mod xpath;
+
+#[doc(hidden)]
+pub use macros::__private;
+#[cfg(feature = "macros")]
+pub use htmlbuilder_macros::template;
+use name::{TagName, attr_name};
+use scoped::ScopedStyle;
+
+pub use attr::{Attr, AttrValue, DisplayText};
+pub use builder::TreeBuilder;
+pub use checked::TreeError;
+pub use component::Component;
+#[cfg(feature = "compress")]
+pub use compress::Encoding;
+pub use document::Document;
+pub use iter::{ChildIter, Descendants, DescendantsBfs};
+#[cfg(feature = "std")]
+pub use memo::MemoCache;
+pub use options::RenderOptions;
+pub use placeholder::FillValue;
+pub use renderer::Renderer;
+pub use stats::TreeStats;
+pub use stream::Chunks;
 
 fn escape_ascii(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
@@ -21,6 +128,15 @@ fn escape_ascii(s: &str) -> String {
     result
 }
 
+/// 转义，无需转义时直接使用原字符串，不复制
+fn escape_cow(s: Cow<'static, str>) -> Cow<'static, str> {
+    if s.contains(['"', '\'', '&', '<', '>']) {
+        Cow::Owned(escape_ascii(&s))
+    } else {
+        s
+    }
+}
+
 fn un_escape_ascii(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
 
@@ -28,7 +144,7 @@ fn un_escape_ascii(s: &str) -> String {
     while let Some(c) = iter.next() {
         if c == '&' {
             let mut name = String::new();
-            while let Some(c) = iter.next() {
+            for c in iter.by_ref() {
                 if c == ';' {
                     break;
                 }
@@ -50,6 +166,49 @@ fn un_escape_ascii(s: &str) -> String {
     result
 }
 
+/// 原始文本元素（style/script）的内容转义
+/// 
+/// 保持内容原样，仅将`</tag`拆为`<\/tag`，防止提前闭合元素
+fn escape_raw_text(s: &str, tag: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let bytes = s.as_bytes();
+    let tag_len = tag.len();
+
+    let mut last = 0;
+    let mut i = 0;
+    while i + 2 + tag_len <= bytes.len() {
+        if bytes[i] == b'<'
+            && bytes[i + 1] == b'/'
+            && bytes[i + 2..i + 2 + tag_len].eq_ignore_ascii_case(tag.as_bytes())
+        {
+            result.push_str(&s[last..i + 1]);
+            result.push('\\');
+            last = i + 1;
+            i += 2 + tag_len;
+        } else {
+            i += 1;
+        }
+    }
+    result.push_str(&s[last..]);
+
+    result
+}
+
+/// 属性表，无`std`时使用`BTreeMap`
+#[cfg(feature = "std")]
+type AttrMap = std::collections::HashMap<Cow<'static, str>, String>;
+#[cfg(not(feature = "std"))]
+type AttrMap = alloc::collections::BTreeMap<Cow<'static, str>, String>;
+
+/// 是否为html空元素（无内容、无闭合标签）
+fn is_void_tag(tag: &str) -> bool {
+    matches!(
+        tag,
+        "area" | "base" | "br" | "col" | "embed" | "hr" | "img" | "input"
+            | "link" | "meta" | "source" | "track" | "wbr"
+    )
+}
+
 
 #[derive(Clone)]
 pub struct Element {
@@ -59,11 +218,94 @@ pub struct Element {
 struct ElementInner {
     parent: Option<Weak<RefCell<ElementInner>>>,
     children: Vec<Element>,
-    tag: String,
-    content: String,
-    kws: HashMap<&'static str, String>,
+    tag: TagName,
+    content: Cow<'static, str>, // 静态字符串不复制
+    kws: AttrMap,
     onetag: bool, // 是否为单标签
     pre: bool, // 是否为原文本内容
+    rawtext: bool, // 是否为原始文本元素（style/script）
+    scoped_css: Option<String>, // 作用域样式
+    cache: Option<RefCell<cache::RenderCache>>, // 渲染缓存，None表示未启用
+    #[cfg(feature = "track-source")]
+    source: source::Source, // 创建元素的位置
+}
+
+/// 向空元素添加子元素时panic
+#[cold]
+#[track_caller]
+fn void_child_panic(elem: &Element) -> ! {
+    let message = alloc::format!("cannot add a child to void element `<{}>`", elem.inner.borrow().tag);
+    #[cfg(feature = "track-source")]
+    let message = source::describe(elem, message);
+    panic!("{}", message);
+}
+
+impl ElementInner {
+    /// 是否为不能有子元素的单标签或html空元素
+    fn is_void(&self) -> bool {
+        self.onetag || is_void_tag(&self.tag)
+    }
+
+    /// 开始标签（含属性）
+    fn open_tag(&self) -> String {
+        let mut htmltext = String::new();
+        self.write_open_tag(&mut htmltext, &RenderOptions::new());
+        htmltext
+    }
+
+    /// 将开始标签写入buf
+    ///
+    /// 开始标签超过`max_line_width`时，从第二个属性起每个属性单独一行，与第一个属性对齐
+    fn write_open_tag(&self, buf: &mut String, opts: &RenderOptions) {
+        let mut attrs: Vec<(&Cow<'static, str>, &String)> = self.kws.iter().collect();
+        if opts.sort_attrs {
+            attrs.sort_unstable_by_key(|(k, _)| *k);
+        }
+        let column = buf[buf.rfind('\n').map_or(0, |i| i + 1)..].chars().count();
+        let wrap = opts.max_line_width.is_some_and(|width| {
+            let len = self.tag.chars().count() + 2
+                + attrs.iter().map(|(k, v)| k.chars().count() + v.chars().count() + 4).sum::<usize>();
+            attrs.len() > 1 && column + len > width
+        });
+
+        buf.push('<');
+        buf.push_str(&self.tag);
+
+        // 处理属性
+        for (i, (k, v)) in attrs.into_iter().enumerate() {
+            if wrap && i > 0 {
+                buf.push('\n');
+                buf.extend(core::iter::repeat_n(' ', column + self.tag.chars().count() + 2));
+            } else {
+                buf.push(' ');
+            }
+            buf.push_str(k);
+            buf.push_str("=\"");
+            match opts.escaper {
+                Some(escaper) if !self.pre => escaper.0.escape_attr(&un_escape_ascii(v), buf),
+                _ => buf.push_str(v),
+            }
+            buf.push('"');
+        }
+        buf.push('>');
+    }
+
+    /// 按选项的转义规则将内容写入buf
+    fn write_content(&self, buf: &mut String, opts: &RenderOptions) {
+        match opts.escaper {
+            Some(escaper) if !self.pre && !self.rawtext => escaper.0.escape_text(&un_escape_ascii(&self.content), buf),
+            _ => buf.push_str(&self.content),
+        }
+    }
+
+    /// 未转义的内容文本
+    fn text_content(&self) -> String {
+        if self.pre || self.rawtext {
+            self.content.to_string()
+        } else {
+            un_escape_ascii(&self.content)
+        }
+    }
 }
 
 impl Element {
@@ -74,51 +316,107 @@ impl Element {
     /// content: 内容
     /// 
     /// ```
+    /// # use htmlbuilder::Element;
     /// let div = Element::new("div", "content");
     /// ```
+    #[track_caller]
     pub fn new(tag: impl Into<String>, content: impl Into<String>) -> Self {
+        Self::from_parts(TagName::new(tag.into()), escape_cow(Cow::Owned(content.into())))
+    }
+    /// 用静态字符串创建元素，标签名和无需转义的内容都不会被复制
+    ///
+    /// 适合在模板中嵌入大段固定文本
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let p = Element::new_static("p", "static text");
+    /// assert_eq!(p.render(""), "<p>static text</p>");
+    /// ```
+    #[track_caller]
+    pub fn new_static(tag: &'static str, content: &'static str) -> Self {
+        Self::from_parts(TagName::Static(tag), escape_cow(Cow::Borrowed(content)))
+    }
+    #[track_caller]
+    fn from_parts(tag: TagName, content: Cow<'static, str>) -> Self {
         Self {
             inner: Rc::new(RefCell::new(ElementInner {
                 parent: None,
                 children: Vec::new(),
-                tag: tag.into(),
-                content: escape_ascii(&content.into()),
+                tag,
+                content,
                 // 默认值
-                kws: HashMap::new(),
+                kws: AttrMap::new(),
                 onetag: false,
                 pre: false,
+                rawtext: false,
+                scoped_css: None,
+                cache: None,
+                #[cfg(feature = "track-source")]
+                source: source::Source::Location(core::panic::Location::caller()),
             }))
         }
     }
-    /// 设置全部属性（HashMap）
+    /// 创建CDATA节点`<![CDATA[...]]>`，内容不转义，其中的`]]>`会被拆到两个CDATA段中
+    ///
+    /// 用于XML输出（订阅源、SVG等）；html中CDATA只在SVG和MathML内有效
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let payload = Element::new("payload", "").add_with(Element::cdata("<b>a & b</b>]]>"));
+    /// assert_eq!(payload.render(""), "<payload><![CDATA[<b>a & b</b>]]]]><![CDATA[>]]></payload>");
+    /// ```
+    pub fn cdata(text: &str) -> Self {
+        let node = Self::new("", "").pre(true);
+        node.configcnt(alloc::format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>")));
+        node
+    }
+    /// 创建样式表元素`<style>`
+    /// 
+    /// css内容不会被转义，但会阻止`</style>`提前闭合元素
     /// 
     /// ```
+    /// # use htmlbuilder::Element;
+    /// let style = Element::style_sheet("ul > li { color: red; }");
+    /// assert_eq!(style.render(""), "<style>ul > li { color: red; }</style>");
+    /// ```
+    pub fn style_sheet(css: impl Into<String>) -> Self {
+        let style = Self::new("style", "").rawtext(true);
+        style.configcnt(css);
+        style
+    }
+    /// 设置全部属性（HashMap或其他`(属性名, 值)`序列）
+    /// 
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use htmlbuilder::Element;
     /// let div = Element::new("div", "content").kws(HashMap::from([("id", "main".to_string())]));
     /// ```
-    pub fn kws(self, mut kws: HashMap<&'static str, String>) -> Self {
-        for (_, v) in &mut kws {
-            *v = escape_ascii(v);
+    pub fn kws(self, kws: impl IntoIterator<Item = (&'static str, String)>) -> Self {
+        {
+            let mut inner = self.inner.borrow_mut();
+            inner.kws = kws.into_iter().map(|(k, v)| (Cow::Borrowed(k), escape_ascii(&v))).collect();
+            inner.invalidate();
         }
-        self.inner.borrow_mut().kws = kws;
         self
     }
     /// 设置全部属性
     /// 
     /// ```
-    /// let div = Element::new("div", "content").attrs([("id", "main"), ("class", "test")]);
+    /// # use htmlbuilder::Element;
+    /// let div = Element::new("div", "content").attrs(&[("id", "main"), ("class", "test")]);
     /// ```
     pub fn attrs(self, attrs: &[(&'static str, &str)]) -> Self {
-        let mut kws: HashMap<&str, String> = HashMap::new();
-        for (k, v) in attrs {
-            kws.insert(k, escape_ascii(v));
-        }
-        self.kws(kws)
+        self.kws(attrs.iter().map(|(k, v)| (*k, v.to_string())))
     }
     /// 设置是否单标签
     /// 
     /// 如果是单标签，输出为字符串时将仅输出标签本身
     pub fn onetag(self, onetag: bool) -> Self {
-        self.inner.borrow_mut().onetag = onetag;
+        {
+            let mut inner = self.inner.borrow_mut();
+            inner.onetag = onetag;
+            inner.invalidate();
+        }
         self
     }
     /// 设置是否为原文本内容
@@ -129,35 +427,192 @@ impl Element {
             let mut inner = self.inner.borrow_mut();
             inner.pre = pre;
             if pre {
-                inner.content = un_escape_ascii(&inner.content);
-                for (_, v) in &mut inner.kws {
+                inner.content = un_escape_ascii(&inner.content).into();
+                for v in inner.kws.values_mut() {
                     *v = un_escape_ascii(v);
                 }
             }
+            inner.invalidate();
         }
         self
     }
-
-    /// 添加子元素
-    pub fn add(&self, elem: Element) -> &Self {
+    /// 设置是否为原始文本元素（如`style`、`script`）
+    /// 
+    /// 原始文本元素的内容不会被转义，但`</标签名`会被拆开，防止内容提前闭合元素
+    pub fn rawtext(self, rawtext: bool) -> Self {
         {
             let mut inner = self.inner.borrow_mut();
-            elem.inner.borrow_mut().parent = Some(Rc::downgrade(&self.inner));
-            inner.children.push(elem);
+            if rawtext && !inner.rawtext {
+                let content = if inner.pre {
+                    inner.content.to_string()
+                } else {
+                    un_escape_ascii(&inner.content)
+                };
+                inner.content = escape_raw_text(&content, &inner.tag).into();
+            }
+            inner.rawtext = rawtext;
+            inner.invalidate();
+        }
+        self
+    }
+
+    /// 添加子元素
+    ///
+    /// 可以直接传入字符串（文本节点）或`(标签名, 内容)`。
+    /// 单标签（`onetag`）或html空元素（如`<br>`）不能有子元素，添加时panic；不希望panic时使用`try_add`
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let div = Element::new("div", "");
+    /// div.add("plain <text>").add(("p", "hello"));
+    /// assert_eq!(div.render(""), "<div>plain &lt;text&gt;<p>hello</p></div>");
+    /// ```
+    #[track_caller]
+    pub fn add(&self, elem: impl Into<Element>) -> &Self {
+        let elem = elem.into();
+        if self.inner.borrow().is_void() {
+            void_child_panic(self);
         }
+        self.push_child(elem);
         self
     }
 
+    /// 添加子元素，不检查空元素，用于按已有的数据（反序列化、XML、DOM、拷贝）重建元素树
+    pub(crate) fn push_child(&self, elem: Element) {
+        if self.inner.borrow().cache.is_some() {
+            elem.cache_render(true);
+        }
+        let mut inner = self.inner.borrow_mut();
+        elem.inner.borrow_mut().parent = Some(Rc::downgrade(&self.inner));
+        inner.children.push(elem);
+        inner.invalidate();
+    }
+
     /// 添加子元素并返回Self
-    pub fn add_with(self, elem: Element) -> Self {
+    #[track_caller]
+    pub fn add_with(self, elem: impl Into<Element>) -> Self {
         self.add(elem);
         self
     }
 
+    /// 依次添加多个子元素
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::new("ul", "");
+    /// ul.add_all((0..2).map(|i| Element::new("li", i.to_string())));
+    /// assert_eq!(ul.render(""), "<ul><li>0</li><li>1</li></ul>");
+    /// ```
+    #[track_caller]
+    pub fn add_all<T: Into<Element>>(&self, elems: impl IntoIterator<Item = T>) -> &Self {
+        for elem in elems {
+            self.add(elem);
+        }
+        self
+    }
+
+    /// 添加多个子元素并返回Self
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let p = Element::new("p", "").with_children(vec![
+    ///     Element::new("", "see "),
+    ///     Element::new("a", "docs").attrs(&[("href", "/docs")]),
+    /// ]);
+    /// assert_eq!(p.render(""), r#"<p>see <a href="/docs">docs</a></p>"#);
+    /// ```
+    #[track_caller]
+    pub fn with_children<T: Into<Element>>(self, elems: impl IntoIterator<Item = T>) -> Self {
+        self.add_all(elems);
+        self
+    }
+
+    /// 条件为真时添加`f`创建的子元素，`f`只在需要时调用
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let logged_in = false;
+    /// let nav = Element::new("nav", "")
+    ///     .add_if(logged_in, || ("a", "Logout"))
+    ///     .add_if(!logged_in, || ("a", "Login"));
+    /// assert_eq!(nav.render(""), "<nav><a>Login</a></nav>");
+    /// ```
+    pub fn add_if<T: Into<Element>>(self, cond: bool, f: impl FnOnce() -> T) -> Self {
+        if cond {
+            self.add(f());
+        }
+        self
+    }
+
+    /// 条件为真时设置属性
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let input = Element::new("input", "").onetag(true).attr_if(true, "checked", "");
+    /// assert_eq!(input.render(""), r#"<input checked="">"#);
+    /// ```
+    pub fn attr_if(self, cond: bool, name: impl Into<Cow<'static, str>>, value: impl AttrValue) -> Self {
+        if cond {
+            self.set_attr(name, value);
+        }
+        self
+    }
+
+    /// 条件为真时在`class`属性中追加一个类名
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let li = Element::new("li", "Home").attrs(&[("class", "item")]).class_if(true, "active");
+    /// assert_eq!(li.render(""), r#"<li class="item active">Home</li>"#);
+    /// ```
+    pub fn class_if(self, cond: bool, class: &str) -> Self {
+        if cond {
+            self.merge_class(class);
+        }
+        self
+    }
+
+    /// 将类名合并到`class`属性中，已有的类名不会重复
+    ///
+    /// `set_attr("class", ..)`会覆盖原有类名，需要保留时使用此方法
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let btn = Element::new("button", "OK").attrs(&[("class", "btn btn-primary")]);
+    /// btn.merge_class("btn  btn-lg");
+    /// assert_eq!(btn.render(""), r#"<button class="btn btn-primary btn-lg">OK</button>"#);
+    /// ```
+    pub fn merge_class(&self, classes: &str) -> &Self {
+        let mut inner = self.inner.borrow_mut();
+        let pre = inner.pre;
+        let old = match inner.kws.get("class") {
+            Some(v) if !pre => un_escape_ascii(v),
+            Some(v) => v.clone(),
+            None => String::new(),
+        };
+        let mut tokens: Vec<&str> = old.split_ascii_whitespace().collect();
+        for class in classes.split_ascii_whitespace() {
+            if !tokens.contains(&class) {
+                tokens.push(class);
+            }
+        }
+        let merged = tokens.join(" ");
+        inner.kws.insert(Cow::Borrowed("class"), if pre { merged } else { escape_ascii(&merged) });
+        inner.invalidate();
+        self
+    }
+
     /// 设置一个属性，不影响原有属性
-    pub fn set_attr(&self, name: &'static str, value: impl Into<String>) {
+    /// 
+    /// name可以是`Attr`或任意属性名字符串（`&'static str`或`String`）；value可以是字符串、数字或`bool`，`false`和`None`删除该属性，见`AttrValue`
+    pub fn set_attr(&self, name: impl Into<Cow<'static, str>>, value: impl AttrValue) {
+        let name = attr_name(name);
         let mut inner = self.inner.borrow_mut();
-        inner.kws.insert(name, escape_ascii(&value.into()));
+        match value.into_attr_value() {
+            Some(value) => inner.kws.insert(name, escape_ascii(&value)),
+            None => inner.kws.remove(&*name),
+        };
+        inner.invalidate();
     }
 
     /// 批量设置属性，不影响原有属性
@@ -166,10 +621,28 @@ impl Element {
         V: AsRef<str>,
     {
         for (k, v) in attrs {
-            self.set_attr(k, v.as_ref());
+            self.set_attr(*k, v.as_ref());
         }
     }
 
+    /// 获取全部属性名和未转义的值，按属性名排序
+    ///
+    /// `attrs`用于设置属性，读取时使用此方法
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let a = Element::new("a", "").attrs(&[("title", "x & y"), ("href", "/")]);
+    /// assert_eq!(a.attr_list(), [("href".to_string(), "/".to_string()), ("title".to_string(), "x & y".to_string())]);
+    /// ```
+    pub fn attr_list(&self) -> Vec<(String, String)> {
+        let inner = self.inner.borrow();
+        let mut attrs: Vec<(String, String)> = inner.kws.iter()
+            .map(|(k, v)| (k.to_string(), if inner.pre { v.clone() } else { un_escape_ascii(v) }))
+            .collect();
+        attrs.sort();
+        attrs
+    }
+
     /// 获取父元素
     pub fn parent(&self) -> Option<Element> {
         self.inner.borrow()
@@ -179,28 +652,84 @@ impl Element {
             .map(|rc| Element { inner: rc })
     }
 
+    /// 创建弱引用，不会让元素保持存活
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let div = Element::new("div", "");
+    /// let weak = div.downgrade();
+    /// assert_eq!(weak.upgrade(), Some(div.clone()));
+    /// drop(div);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn downgrade(&self) -> WeakElement {
+        WeakElement { inner: Rc::downgrade(&self.inner) }
+    }
+
+    /// 获取标签名，文本节点为空字符串
+    pub fn tag(&self) -> String {
+        self.inner.borrow().tag.to_string()
+    }
+
+    /// 修改标签名，保留属性、内容和子元素
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let b = Element::new("b", "bold");
+    /// b.set_tag("strong");
+    /// assert_eq!(b.tag(), "strong");
+    /// assert_eq!(b.render(""), "<strong>bold</strong>");
+    /// ```
+    pub fn set_tag(&self, tag: impl Into<String>) -> &Self {
+        let mut inner = self.inner.borrow_mut();
+        inner.tag = TagName::new(tag.into());
+        inner.invalidate();
+        self
+    }
+
     /// 设置内容
     pub fn configcnt(&self, content: impl Into<String>) -> &Self {
         let mut inner = self.inner.borrow_mut();
-        if inner.pre {
-            inner.content = content.into();
+        if inner.rawtext {
+            inner.content = escape_raw_text(&content.into(), &inner.tag).into();
+        } else if inner.pre {
+            inner.content = Cow::Owned(content.into());
         } else {
-            inner.content = escape_ascii(&content.into());
+            inner.content = escape_cow(Cow::Owned(content.into()));
         }
+        inner.invalidate();
         self
     }
 
+    /// 获取内容，返回未转义的原始文本
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let p = Element::new("p", "a < b");
+    /// p.append_content(" & c");
+    /// assert_eq!(p.content(), "a < b & c");
+    /// assert_eq!(p.render(""), "<p>a &lt; b &amp; c</p>");
+    /// ```
+    pub fn content(&self) -> String {
+        self.inner.borrow().text_content()
+    }
+
+    /// 在内容末尾追加文本，转义规则与`configcnt`相同
+    pub fn append_content(&self, text: &str) -> &Self {
+        let content = self.content() + text;
+        self.configcnt(content)
+    }
+
     /// 设置全部属性
     /// 
     /// 当`pre == true`时，内容将不会被转义
-    pub fn configkws(&self, mut kws: HashMap<&'static str, String>) -> &Self {
+    pub fn configkws(&self, kws: impl IntoIterator<Item = (&'static str, String)>) -> &Self {
         let mut inner = self.inner.borrow_mut();
-        if !inner.pre {
-            for (_, v) in &mut kws {
-                *v = escape_ascii(v);
-            }
-        }
-        inner.kws = kws;
+        let pre = inner.pre;
+        inner.kws = kws.into_iter()
+            .map(|(k, v)| (Cow::Borrowed(k), if pre { v } else { escape_ascii(&v) }))
+            .collect();
+        inner.invalidate();
         self
     }
 
@@ -209,12 +738,56 @@ impl Element {
         self.inner.borrow().children.clone()
     }
 
+    /// 获取指定位置的子元素
+    pub fn child(&self, index: usize) -> Option<Element> {
+        self.inner.borrow().children.get(index).cloned()
+    }
+
+    /// 子元素数量
+    pub fn child_count(&self) -> usize {
+        self.inner.borrow().children.len()
+    }
+
+    /// 依次访问子元素，不复制子元素列表
+    ///
+    /// 每次调用`f`之前释放借用，`f`中可以修改当前元素和子元素；
+    /// 在`f`中增删子元素时，按位置继续访问
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b"]);
+    /// ul.for_each_child(|li| li.set_attr("class", "item"));
+    /// assert_eq!(ul.render(""), r#"<ul><li class="item">a</li><li class="item">b</li></ul>"#);
+    /// ```
+    pub fn for_each_child(&self, mut f: impl FnMut(&Element)) {
+        let mut index = 0;
+        while let Some(child) = self.child(index) {
+            f(&child);
+            index += 1;
+        }
+    }
+
+    /// 借用子元素列表，不复制
+    ///
+    /// 返回的guard存在期间不能修改当前元素及其祖先，否则会panic，适合只读的遍历
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b"]);
+    /// let texts: Vec<String> = ul.children_ref().iter().map(|li| li.render("")).collect();
+    /// assert_eq!(texts, ["<li>a</li>", "<li>b</li>"]);
+    /// ```
+    pub fn children_ref(&self) -> Ref<'_, [Element]> {
+        Ref::map(self.inner.borrow(), |inner| inner.children.as_slice())
+    }
+
     /// 移除指定位置子元素
     pub fn remove_child(&self, index: usize) -> Option<Element> {
         let mut inner = self.inner.borrow_mut();
         if index < inner.children.len() {
             let child = inner.children.remove(index);
             child.inner.borrow_mut().parent = None;
+            inner.invalidate();
             Some(child)
         } else {
             None
@@ -227,6 +800,7 @@ impl Element {
         if let Some(index) = inner.children.iter().position(|x| x == child) {
             inner.children.remove(index);
             child.inner.borrow_mut().parent = None;
+            inner.invalidate();
             true
         } else {
             false
@@ -239,46 +813,203 @@ impl Element {
         for child in inner.children.drain(..) {
             child.inner.borrow_mut().parent = None;
         }
+        inner.invalidate();
     }
 
     /// 渲染为html字符串
+    ///
+    /// 启用`cache_render`时复用未修改子树的结果
     pub fn render(&self, split_s: &str) -> String {
+        let mut htmltext = String::new();
+        self.render_pass(split_s, &RenderOptions::new(), &mut htmltext);
+        htmltext
+    }
+
+    /// 渲染并追加到`buf`末尾，可以在多次渲染之间复用缓冲区
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let mut buf = String::with_capacity(1024);
+    /// for i in 0..3 {
+    ///     buf.clear();
+    ///     Element::new("p", i.to_string()).render_into("", &mut buf);
+    ///     assert_eq!(buf, format!("<p>{}</p>", i));
+    /// }
+    /// ```
+    pub fn render_into(&self, split_s: &str, buf: &mut String) {
+        self.render_pass(split_s, &RenderOptions::new(), buf);
+    }
+
+    /// 一次完整的渲染：作用域样式只收集一次，按估计的长度预先分配缓冲区
+    fn render_pass(&self, split_s: &str, opts: &RenderOptions, buf: &mut String) {
+        let scoped = ScopedStyle::new(self);
+        buf.reserve(self.size_hint(split_s, &scoped));
+        self.render_opts(split_s, opts, &scoped, buf);
+    }
+
+    /// 按选项渲染，只有默认选项使用渲染缓存
+    fn render_opts(&self, split_s: &str, opts: &RenderOptions, scoped: &ScopedStyle, buf: &mut String) {
+        if !opts.is_default() {
+            self.render_fresh(split_s, opts, scoped, buf);
+            return;
+        }
+        if let Some(html) = self.inner.borrow().cached(split_s) {
+            buf.push_str(&html);
+            return;
+        }
+        let start = buf.len();
+        self.render_fresh(split_s, opts, scoped, buf);
+        self.inner.borrow().store(split_s, &buf[start..]);
+    }
+
+    fn render_fresh(&self, split_s: &str, opts: &RenderOptions, scoped: &ScopedStyle, buf: &mut String) {
         let inner = self.inner.borrow();
         if inner.tag.is_empty() {
             // 空标签
-            return inner.content.clone();
+            inner.write_content(buf, opts);
+            return;
         }
-        
-        let mut htmltext = format!("<{}", inner.tag);
 
-        // 处理属性
-        for (k, v) in &inner.kws {
-            htmltext.push_str(&format!(" {}=\"{}\"", k, v));
-        }
-        htmltext.push('>');
+        inner.write_open_tag(buf, opts);
 
-        htmltext.push_str(&inner.content);
+        // head中输出整棵树的作用域样式
+        let style = scoped.for_tag(&inner.tag);
+        if opts.xml && (inner.onetag || (inner.content.is_empty() && inner.children.is_empty() && style.is_none())) {
+            // XML空元素
+            buf.pop();
+            buf.push_str("/>");
+            if inner.onetag {
+                buf.push_str(split_s);
+            }
+            return;
+        }
+        #[cfg(feature = "minify")]
+        let minified = if opts.minify_assets && inner.rawtext { minify::minify_rawtext(&inner) } else { None };
+        #[cfg(not(feature = "minify"))]
+        let minified: Option<String> = None;
+        match minified {
+            Some(minified) => buf.push_str(&minified),
+            None => inner.write_content(buf, opts),
+        }
 
         // 处理子元素
         for item in &inner.children {
-            let subtext = item.render(split_s);
-            htmltext.push_str(split_s);
-            htmltext.push_str(&subtext);
+            buf.push_str(split_s);
+            item.render_opts(split_s, opts, scoped, buf);
+        }
+
+        if let Some(style) = style {
+            buf.push_str(split_s);
+            style.render_opts(split_s, opts, scoped, buf);
         }
 
         if inner.onetag {
             // 单标签
-            htmltext.push_str(split_s);
-        } else if !inner.children.is_empty() {
+            buf.push_str(split_s);
+            return;
+        }
+        if !inner.children.is_empty() || style.is_some() {
             // 有子标签
-            htmltext.push_str(split_s);
-            htmltext.push_str(&format!("</{}>", inner.tag))
-        } else {
-            // 无子标签
-            htmltext.push_str(&format!("</{}>", inner.tag))
+            buf.push_str(split_s);
         }
+        buf.push_str("</");
+        buf.push_str(&inner.tag);
+        buf.push('>');
+    }
 
-        htmltext
+    /// 估计渲染结果的长度（字节），`render`用它预先分配缓冲区
+    ///
+    /// 包括`<head>`中自动生成的作用域样式，结果与实际长度相同
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b"]).attrs(&[("id", "list")]);
+    /// assert_eq!(ul.render_size_hint("\n"), ul.render("\n").len());
+    /// ```
+    pub fn render_size_hint(&self, split_s: &str) -> usize {
+        self.size_hint(split_s, &ScopedStyle::new(self))
+    }
+
+    fn size_hint(&self, split_s: &str, scoped: &ScopedStyle) -> usize {
+        let inner = self.inner.borrow();
+        if let Some(html) = inner.cached(split_s) {
+            return html.len();
+        }
+        if inner.tag.is_empty() {
+            return inner.content.len();
+        }
+
+        // `<tag k="v">`
+        let mut size = inner.tag.len() + 2 + inner.content.len();
+        size += inner.kws.iter().map(|(k, v)| k.len() + v.len() + 4).sum::<usize>();
+        size += inner.children.iter()
+            .map(|child| split_s.len() + child.size_hint(split_s, scoped))
+            .sum::<usize>();
+        let style = scoped.for_tag(&inner.tag);
+        size += style.map_or(0, |style| split_s.len() + style.size_hint(split_s, scoped));
+        size += split_s.len();
+        if !inner.onetag {
+            if inner.children.is_empty() && style.is_none() {
+                size -= split_s.len();
+            }
+            // `</tag>`
+            size += inner.tag.len() + 3;
+        }
+        size
+    }
+}
+
+/// 元素的弱引用，用于索引表、缓存等长期保存的结构，不会造成引用循环
+#[derive(Clone, Default)]
+pub struct WeakElement {
+    inner: Weak<RefCell<ElementInner>>,
+}
+
+impl WeakElement {
+    /// 创建不指向任何元素的弱引用
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取元素，元素已释放时返回None
+    pub fn upgrade(&self) -> Option<Element> {
+        self.inner.upgrade().map(|rc| Element { inner: rc })
+    }
+}
+
+impl PartialEq for WeakElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.ptr_eq(&other.inner)
+    }
+}
+
+impl fmt::Debug for WeakElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.upgrade() {
+            Some(elem) => write!(f, "WeakElement[{:?}]", elem.inner.borrow().tag),
+            None => write!(f, "WeakElement[dropped]"),
+        }
+    }
+}
+
+/// 文本节点
+impl From<&str> for Element {
+    fn from(text: &str) -> Self {
+        Element::new("", text)
+    }
+}
+
+/// 文本节点
+impl From<String> for Element {
+    fn from(text: String) -> Self {
+        Element::new("", text)
+    }
+}
+
+/// `(标签名, 内容)`
+impl From<(&str, &str)> for Element {
+    fn from((tag, content): (&str, &str)) -> Self {
+        Element::new(tag, content)
     }
 }
 
@@ -290,6 +1021,10 @@ impl PartialEq for Element {
 
 impl fmt::Debug for Element {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `{:#?}`输出整个元素树
+        if f.alternate() {
+            return f.write_str(&self.dump_tree());
+        }
         writeln!(f, "Element[{:?}]", self.inner.borrow().tag)?;
         if self.inner.borrow().parent.is_some() {
             writeln!(f, "  parent: HAS")?;
@@ -305,16 +1040,21 @@ impl fmt::Debug for Element {
         if !self.inner.borrow().children.is_empty() {
             writeln!(f, "  children<{}>", self.inner.borrow().children.len())?;
         }
+        #[cfg(feature = "track-source")]
+        writeln!(f, "  source: {}", self.inner.borrow().source)?;
         Ok(())
     }
 }
 
 
 #[cfg(test)]
+#[allow(clippy::needless_borrows_for_generic_args, clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
     use std::fs::File;
     use std::io::Write;
+    use std::{format, println};
 
     fn write_file(filename: &str, content: &str) {
         let mut file = File::create(filename).unwrap();
@@ -422,6 +1162,14 @@ mod tests {
         println!("{:?}", a);
     }
 
+    #[test]
+    fn test_class_dedup() {
+        let li = Element::new("li", "").attrs(&[("class", "a")]).class_if(true, "a").class_if(true, "b");
+        assert_eq!(li.render(""), r#"<li class="a b"></li>"#);
+        let div = crate::html! { div.a.a {} };
+        assert_eq!(div.render(""), r#"<div class="a"></div>"#);
+    }
+
     #[test]
     fn test_delete() {
         let a = Element::new("div", "");
@@ -442,4 +1190,105 @@ mod tests {
         a.remove_all_children();
         assert_eq!(a.children().len(), 0);
     }
+
+    #[test]
+    fn test_style_sheet() {
+        let style = Element::style_sheet("div > p::after { content: \"<&>\"; }");
+        assert_eq!(style.render(""), "<style>div > p::after { content: \"<&>\"; }</style>");
+
+        // 内容不能提前闭合style元素
+        style.configcnt("a {}</STYLE><script>alert(1)</script>");
+        assert_eq!(style.render(""), "<style>a {}<\\/STYLE><script>alert(1)</script></style>");
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_template() {
+        let title = "<Title>";
+        let items = [1, 2];
+        let page = template!(r#"
+            <div class="page {title}">
+                <h1>{title}</h1>
+                <br>
+                <ul>{items.iter().map(|i| Element::new("li", i.to_string())).collect::<Vec<_>>()}</ul>
+                <style>a > b {{ color: red; }}</style>
+            </div>
+        "#);
+        assert_eq!(
+            page.render(""),
+            "<div class=\"page &lt;Title&gt;\"><h1>&lt;Title&gt;</h1><br><ul><li>1</li><li>2</li></ul><style>a > b { color: red; }</style></div>",
+        );
+
+        let script = template!("<script>if (a < b) {{ go(); }}</script>");
+        assert_eq!(script.render(""), "<script>if (a < b) { go(); }</script>");
+
+        // 模板中的字符实体只转义一次
+        let p = template!(r#"<p title="a &amp; b">Tom &amp; Jerry &lt;3</p>"#);
+        assert_eq!(p.render(""), r#"<p title="a &amp; b">Tom &amp; Jerry &lt;3</p>"#);
+    }
+
+    #[test]
+    fn test_render_size_hint() {
+        let div = Element::new("div", "a&b")
+            .attrs(&[("id", "x"), ("title", "\"q\"")])
+            .add_with(Element::new("br", "").onetag(true))
+            .add_with(Element::new("", "text"))
+            .add_with(Element::ul_from(["1", "2"]))
+            .add_with(Element::new("p", ""));
+        for split_s in ["", "\n", "\r\n"] {
+            let mut buf = "prefix".to_string();
+            div.render_into(split_s, &mut buf);
+            assert_eq!(buf, format!("prefix{}", div.render(split_s)));
+            assert_eq!(div.render_size_hint(split_s), div.render(split_s).len());
+        }
+
+        // head中的作用域样式
+        let body = Element::new("body", "");
+        body.scoped_style(crate::css::Stylesheet::new().rule("&", &[("color", "red")]));
+        let html = Element::new("html", "").add_with(Element::new("head", "")).add_with(body);
+        for split_s in ["", "\n"] {
+            assert_eq!(html.render_size_hint(split_s), html.render(split_s).len());
+        }
+    }
+
+    #[test]
+    fn test_new_static() {
+        let p = Element::new_static("p", "plain");
+        assert!(matches!(p.inner.borrow().content, Cow::Borrowed(_)));
+        let escaped = Element::new_static("my-tag", "a<b");
+        assert_eq!(escaped.render(""), "<my-tag>a&lt;b</my-tag>");
+        escaped.configcnt("plain");
+        assert_eq!(escaped.render(""), "<my-tag>plain</my-tag>");
+    }
+
+    #[test]
+    fn test_child_access() {
+        let ul = Element::ul_from(["a", "b", "c"]);
+        assert_eq!(ul.child_count(), 3);
+        assert_eq!(ul.child(1).unwrap().render(""), "<li>b</li>");
+        assert!(ul.child(3).is_none());
+
+        // 访问时可以修改父元素
+        let mut seen = 0;
+        ul.for_each_child(|li| {
+            seen += 1;
+            if li.render("") == "<li>a</li>" {
+                ul.remove_child(2);
+            }
+        });
+        assert_eq!(seen, 2);
+        assert_eq!(ul.children_ref().len(), 2);
+    }
+
+    #[test]
+    fn test_weak_element() {
+        let body = Element::new("body", "");
+        let registry: HashMap<&str, WeakElement> = HashMap::from([("main", body.downgrade()), ("none", WeakElement::new())]);
+        assert_eq!(registry["main"].upgrade(), Some(body.clone()));
+        assert_eq!(registry["main"], body.downgrade());
+        assert!(registry["none"].upgrade().is_none());
+        assert_eq!(format!("{:?}", registry["main"]), "WeakElement[\"body\"]");
+        drop(body);
+        assert!(registry["main"].upgrade().is_none());
+    }
 }
@@ -0,0 +1,176 @@
+//! 图标与资源提示
+//!
+//! 生成`<head>`中的`<link>`元素，自动补全容易遗漏的`type`、`sizes`、`as`和`crossorigin`
+//!
+//! ```
+//! use htmlbuilder::links::{self, Destination};
+//! use htmlbuilder::tags::head;
+//!
+//! let head = head()
+//!     .add_with(links::icon("/favicon.svg", ""))
+//!     .add_with(links::icon("/favicon-32.png", "32x32"))
+//!     .add_with(links::apple_touch_icon("/apple-touch-icon.png"))
+//!     .add_with(links::preconnect("https://fonts.gstatic.com", true))
+//!     .add_with(links::preload("/fonts/inter.woff2", Destination::Font));
+//! ```
+
+use alloc::string::String;
+
+use crate::Element;
+use crate::tags::link;
+
+/// 根据扩展名推断图标的MIME类型
+fn icon_type(href: &str) -> Option<&'static str> {
+    let path = href.split(['?', '#']).next().unwrap_or(href);
+    let ext = path.rsplit_once('.')?.1.to_ascii_lowercase();
+    Some(match ext.as_str() {
+        "ico" => "image/x-icon",
+        "png" => "image/png",
+        "svg" => "image/svg+xml",
+        "gif" => "image/gif",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        _ => return None,
+    })
+}
+
+/// 网站图标`<link rel="icon">`
+///
+/// `sizes`如`32x32`、`16x16 32x32`或`any`，为空时不设置；`type`由扩展名推断
+pub fn icon(href: impl Into<String>, sizes: &str) -> Element {
+    let href = href.into();
+    let ty = icon_type(&href);
+    let elem = link("icon", href);
+    if let Some(ty) = ty {
+        elem.set_attr("type", ty);
+    }
+    if !sizes.is_empty() {
+        elem.set_attr("sizes", sizes);
+    }
+    elem
+}
+
+/// iOS主屏幕图标`<link rel="apple-touch-icon">`，推荐180x180的png
+pub fn apple_touch_icon(href: impl Into<String>) -> Element {
+    link("apple-touch-icon", href)
+}
+
+/// 预加载资源的类型，即`as`属性
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Destination {
+    /// 音频
+    Audio,
+    /// html文档（iframe等）
+    Document,
+    /// `<embed>`内容
+    Embed,
+    /// `fetch`或XHR请求的数据
+    Fetch,
+    /// 字体
+    Font,
+    /// 图片
+    Image,
+    /// `<object>`内容
+    Object,
+    /// 脚本
+    Script,
+    /// 样式表
+    Style,
+    /// WebVTT字幕
+    Track,
+    /// 视频
+    Video,
+    /// Worker脚本
+    Worker,
+}
+
+impl Destination {
+    /// `as`属性的值
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Destination::Audio => "audio",
+            Destination::Document => "document",
+            Destination::Embed => "embed",
+            Destination::Fetch => "fetch",
+            Destination::Font => "font",
+            Destination::Image => "image",
+            Destination::Object => "object",
+            Destination::Script => "script",
+            Destination::Style => "style",
+            Destination::Track => "track",
+            Destination::Video => "video",
+            Destination::Worker => "worker",
+        }
+    }
+
+    /// 是否总是以CORS方式请求，此时预加载必须带`crossorigin`，否则会重复下载
+    fn requires_cors(self) -> bool {
+        matches!(self, Destination::Font | Destination::Fetch)
+    }
+}
+
+/// 预加载当前页面需要的资源`<link rel="preload" as="...">`
+///
+/// 字体和`fetch`会同时设置`crossorigin="anonymous"`
+///
+/// ```
+/// use htmlbuilder::links::{preload, Destination};
+///
+/// let font = preload("/inter.woff2", Destination::Font);
+/// assert_eq!(font.attr_list().iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>(), ["as", "crossorigin", "href", "rel"]);
+/// ```
+pub fn preload(href: impl Into<String>, dest: Destination) -> Element {
+    let elem = link("preload", href);
+    elem.set_attr("as", dest.as_str());
+    if dest.requires_cors() {
+        elem.set_attr("crossorigin", "anonymous");
+    }
+    elem
+}
+
+/// 预取之后页面可能需要的资源`<link rel="prefetch">`
+pub fn prefetch(href: impl Into<String>) -> Element {
+    link("prefetch", href)
+}
+
+/// 提前建立连接`<link rel="preconnect">`
+///
+/// 连接用于字体等CORS请求时`crossorigin`必须为true，否则浏览器会另建连接
+pub fn preconnect(origin: impl Into<String>, crossorigin: bool) -> Element {
+    let elem = link("preconnect", origin);
+    if crossorigin {
+        elem.set_attr("crossorigin", "");
+    }
+    elem
+}
+
+/// 提前解析域名`<link rel="dns-prefetch">`
+pub fn dns_prefetch(origin: impl Into<String>) -> Element {
+    link("dns-prefetch", origin)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links() {
+        assert_eq!(icon_type("/favicon.ICO?v=2"), Some("image/x-icon"));
+        assert_eq!(icon_type("/icon"), None);
+        crate::assert_html_eq!(
+            icon("/icon.svg", "any").render(""),
+            r#"<link rel="icon" href="/icon.svg" type="image/svg+xml" sizes="any">"#,
+        );
+        crate::assert_html_eq!(icon("/icon", "").render(""), r#"<link rel="icon" href="/icon">"#);
+        crate::assert_html_eq!(
+            preload("/app.css", Destination::Style).render(""),
+            r#"<link rel="preload" href="/app.css" as="style">"#,
+        );
+        crate::assert_html_eq!(
+            preconnect("https://cdn.example.com", true).render(""),
+            r#"<link rel="preconnect" href="https://cdn.example.com" crossorigin="">"#,
+        );
+    }
+}
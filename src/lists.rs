@@ -0,0 +1,120 @@
+//! 列表构建
+
+use alloc::string::ToString;
+
+use crate::{DisplayText, Element};
+
+/// 可作为列表项内容的类型
+///
+/// 文本被转义后放入列表项；`Element`若本身就是目标标签（如`li`）则直接使用，否则放入列表项中
+pub trait IntoListItem {
+    fn into_item(self, item_tag: &'static str) -> Element;
+}
+
+impl IntoListItem for Element {
+    fn into_item(self, item_tag: &'static str) -> Element {
+        let is_item = self.inner.borrow().tag == item_tag;
+        if is_item {
+            self
+        } else {
+            Element::new(item_tag, "").add_with(self)
+        }
+    }
+}
+
+impl<T: DisplayText> IntoListItem for T {
+    fn into_item(self, item_tag: &'static str) -> Element {
+        Element::new(item_tag, self.into_text())
+    }
+}
+
+impl IntoListItem for bool {
+    fn into_item(self, item_tag: &'static str) -> Element {
+        Element::new(item_tag, self.to_string())
+    }
+}
+
+impl Element {
+    fn list_from<I>(tag: &'static str, items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoListItem,
+    {
+        let list = Element::new(tag, "");
+        for item in items {
+            list.add(item.into_item("li"));
+        }
+        list
+    }
+
+    /// 由迭代器创建无序列表`<ul>`，每一项放入`<li>`
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(0..3);
+    /// assert_eq!(ul.render(""), "<ul><li>0</li><li>1</li><li>2</li></ul>");
+    /// ```
+    pub fn ul_from<I>(items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoListItem,
+    {
+        Self::list_from("ul", items)
+    }
+
+    /// 由迭代器创建有序列表`<ol>`，每一项放入`<li>`
+    pub fn ol_from<I>(items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoListItem,
+    {
+        Self::list_from("ol", items)
+    }
+
+    /// 由`(术语, 描述)`迭代器创建描述列表`<dl>`
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let dl = Element::dl_from([("Rust", "a language")]);
+    /// assert_eq!(dl.render(""), "<dl><dt>Rust</dt><dd>a language</dd></dl>");
+    /// ```
+    pub fn dl_from<I, T, D>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (T, D)>,
+        T: IntoListItem,
+        D: IntoListItem,
+    {
+        let list = Element::new("dl", "");
+        for (term, desc) in pairs {
+            list.add(term.into_item("dt"));
+            list.add(desc.into_item("dd"));
+        }
+        list
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_lists() {
+        let items = vec![
+            Element::new("a", "link"),
+            Element::new("li", "ready"),
+        ];
+        assert_eq!(
+            Element::ol_from(items).render(""),
+            "<ol><li><a>link</a></li><li>ready</li></ol>"
+        );
+        assert_eq!(Element::ul_from(["<x>"]).render(""), "<ul><li>&lt;x&gt;</li></ul>");
+        assert_eq!(Element::ul_from(Vec::<String>::new()).render(""), "<ul></ul>");
+        assert_eq!(Element::ul_from([1, 2].iter()).render(""), "<ul><li>1</li><li>2</li></ul>");
+        assert_eq!(Element::ul_from([true]).render(""), "<ul><li>true</li></ul>");
+    }
+}
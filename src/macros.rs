@@ -0,0 +1,216 @@
+//! 声明式构建宏
+
+/// 以声明式语法构建元素树
+/// 
+/// - `tag`：元素，空元素（如`br`、`img`）自动设为单标签
+/// - `#id`、`.class`：设置id与class，可重复使用`.class`
+/// - `[name = value, "data-x" = value, disabled]`：设置属性，连字符属性名用字符串字面量
+/// - `{ ... }`：子元素
+/// - `"text"`：文本
+/// - `(expr)`：嵌入表达式，可以是`Element`、字符串、`Vec<Element>`或`Option<Element>`
+/// 
+/// 宏内容必须只有一个根元素
+/// 
+/// ```
+/// # use htmlbuilder::html;
+/// let items = ["a", "b"];
+/// let page = html! {
+///     div .container {
+///         h1 { "Title" }
+///         br
+///         ul {
+///             (items.iter().map(|i| html! { li { (*i) } }).collect::<Vec<_>>())
+///         }
+///     }
+/// };
+/// assert_eq!(
+///     page.render(""),
+///     r#"<div class="container"><h1>Title</h1><br><ul><li>a</li><li>b</li></ul></div>"#,
+/// );
+/// ```
+#[macro_export]
+macro_rules! html {
+    // 子元素序列
+    (@children $p:ident;) => {};
+    (@children $p:ident; $text:literal $($rest:tt)*) => {
+        $p.add($crate::Element::new("", $text));
+        $crate::html!(@children $p; $($rest)*);
+    };
+    (@children $p:ident; ($e:expr) $($rest:tt)*) => {
+        for __child in $crate::__private::IntoChildren::into_children($e) {
+            $p.add(__child);
+        }
+        $crate::html!(@children $p; $($rest)*);
+    };
+    (@children $p:ident; $tag:ident $($rest:tt)*) => {
+        $crate::html!(@elem $p; ($crate::__private::element(stringify!($tag))); $($rest)*);
+    };
+
+    // 元素修饰：id、class、属性、子元素
+    (@elem $p:ident; ($e:expr); # $id:ident $($rest:tt)*) => {
+        $crate::html!(@elem $p; ({ let __el = $e; __el.set_attr("id", stringify!($id)); __el }); $($rest)*);
+    };
+    (@elem $p:ident; ($e:expr); # $id:literal $($rest:tt)*) => {
+        $crate::html!(@elem $p; ({ let __el = $e; __el.set_attr("id", $id); __el }); $($rest)*);
+    };
+    (@elem $p:ident; ($e:expr); . $class:ident $($rest:tt)*) => {
+        $crate::html!(@elem $p; ($crate::__private::add_class($e, stringify!($class))); $($rest)*);
+    };
+    (@elem $p:ident; ($e:expr); . $class:literal $($rest:tt)*) => {
+        $crate::html!(@elem $p; ($crate::__private::add_class($e, $class)); $($rest)*);
+    };
+    (@elem $p:ident; ($e:expr); [$($attrs:tt)*] $($rest:tt)*) => {
+        $crate::html!(@elem $p; ({ let __el = $e; $crate::html!(@attrs __el; $($attrs)*); __el }); $($rest)*);
+    };
+    (@elem $p:ident; ($e:expr); { $($children:tt)* } $($rest:tt)*) => {
+        $p.add({
+            let __el = $e;
+            $crate::html!(@children __el; $($children)*);
+            __el
+        });
+        $crate::html!(@children $p; $($rest)*);
+    };
+    (@elem $p:ident; ($e:expr); $($rest:tt)*) => {
+        $p.add($e);
+        $crate::html!(@children $p; $($rest)*);
+    };
+
+    // 属性列表
+    (@attrs $el:ident;) => {};
+    (@attrs $el:ident; $k:ident = $v:expr $(, $($rest:tt)*)?) => {
+        $el.set_attr(stringify!($k), $v);
+        $crate::html!(@attrs $el; $($($rest)*)?);
+    };
+    (@attrs $el:ident; $k:literal = $v:expr $(, $($rest:tt)*)?) => {
+        $el.set_attr($k, $v);
+        $crate::html!(@attrs $el; $($($rest)*)?);
+    };
+    (@attrs $el:ident; $k:ident $(, $($rest:tt)*)?) => {
+        $el.set_attr(stringify!($k), "");
+        $crate::html!(@attrs $el; $($($rest)*)?);
+    };
+    (@attrs $el:ident; $k:literal $(, $($rest:tt)*)?) => {
+        $el.set_attr($k, "");
+        $crate::html!(@attrs $el; $($($rest)*)?);
+    };
+
+    ($($body:tt)+) => {{
+        let mut __root = $crate::__private::Root::default();
+        $crate::html!(@children __root; $($body)+);
+        __root.into_element()
+    }};
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use alloc::string::ToString;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::{DisplayText, Element, is_void_tag};
+
+    // 过程宏生成的代码使用，无`std`时同样可用
+    pub use alloc::format;
+
+    pub fn element(tag: &'static str) -> Element {
+        Element::new(tag, "").onetag(is_void_tag(tag))
+    }
+
+    pub fn add_class(elem: Element, class: &str) -> Element {
+        elem.merge_class(class);
+        elem
+    }
+
+    pub trait IntoChildren {
+        fn into_children(self) -> Vec<Element>;
+    }
+
+    impl IntoChildren for Element {
+        fn into_children(self) -> Vec<Element> {
+            vec![self]
+        }
+    }
+
+    impl<T: DisplayText> IntoChildren for T {
+        fn into_children(self) -> Vec<Element> {
+            vec![Element::new("", self.into_text())]
+        }
+    }
+
+    impl IntoChildren for bool {
+        fn into_children(self) -> Vec<Element> {
+            vec![Element::new("", self.to_string())]
+        }
+    }
+
+    impl IntoChildren for Vec<Element> {
+        fn into_children(self) -> Vec<Element> {
+            self
+        }
+    }
+
+    impl IntoChildren for Option<Element> {
+        fn into_children(self) -> Vec<Element> {
+            self.into_iter().collect()
+        }
+    }
+
+    #[derive(Default)]
+    pub struct Root(Vec<Element>);
+
+    impl Root {
+        pub fn add(&mut self, elem: Element) {
+            self.0.push(elem);
+        }
+
+        pub fn into_element(mut self) -> Element {
+            assert!(self.0.len() == 1, "html! 宏必须只有一个根元素，实际为{}个", self.0.len());
+            self.0.pop().unwrap()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use crate::Element;
+
+    #[test]
+    fn test_html_macro() {
+        let name = String::from("<world>");
+        let extra: Option<Element> = None;
+        let page = html! {
+            div #main .container .wide ["data-id" = "7", hidden] {
+                h1 { "Hello " (name.clone()) }
+                img [src = "a.png", alt = "pic"]
+                ul {
+                    ((0..3).map(|i| html! { li { (i.to_string()) } }).collect::<Vec<_>>())
+                }
+                (extra)
+            }
+        };
+
+        let children = page.children();
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[0].render(""), "<h1>Hello &lt;world&gt;</h1>");
+        assert_eq!(children[1].children().len(), 0);
+        assert!(children[1].render("").starts_with("<img "));
+        assert!(!children[1].render("").contains("</img>"));
+        assert_eq!(children[2].render(""), "<ul><li>0</li><li>1</li><li>2</li></ul>");
+
+        let text = page.render("");
+        assert!(text.contains(r#"id="main""#));
+        assert!(text.contains(r#"class="container wide""#));
+        assert!(text.contains(r#"data-id="7""#));
+        assert!(text.contains(r#"hidden="""#));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_html_macro_single_root() {
+        html! { p p };
+    }
+}
@@ -0,0 +1,220 @@
+//! Markdown转换
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+use crate::Element;
+
+fn raw_html(html: &str) -> Element {
+    let elem = Element::new("", "").pre(true);
+    elem.configcnt(html);
+    elem
+}
+
+impl Element {
+    /// 将Markdown解析为元素树，全部块元素放在一个`<div>`中
+    ///
+    /// 支持CommonMark以及表格、删除线和任务列表，Markdown中的html原样保留。需要`markdown`特性
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let doc = Element::from_markdown("# Title\n\nSome *text* and [a link](/x).");
+    /// assert_eq!(
+    ///     doc.render(""),
+    ///     r#"<div><h1>Title</h1><p>Some <em>text</em> and <a href="/x">a link</a>.</p></div>"#,
+    /// );
+    /// ```
+    pub fn from_markdown(md: &str) -> Self {
+        let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+        let root = Element::new("div", "");
+        let mut stack = vec![root.clone()];
+        let mut in_table_head = false;
+        let mut alt = String::new();
+
+        for event in Parser::new_ext(md, options) {
+            let top = stack.last().unwrap().clone();
+            let in_image = top.inner.borrow().tag == "img";
+            match event {
+                Event::Start(tag) => {
+                    let elem = match tag {
+                        Tag::Paragraph => Element::new("p", ""),
+                        Tag::Heading { level, id, classes, .. } => {
+                            let elem = Element::new(level.to_string(), "");
+                            if let Some(id) = id {
+                                elem.set_attr("id", id.to_string());
+                            }
+                            if !classes.is_empty() {
+                                elem.set_attr("class", classes.join(" "));
+                            }
+                            elem
+                        }
+                        Tag::BlockQuote(_) => Element::new("blockquote", ""),
+                        Tag::CodeBlock(kind) => {
+                            let code = Element::new("code", "");
+                            if let CodeBlockKind::Fenced(lang) = kind
+                                && let Some(lang) = lang.split_whitespace().next()
+                            {
+                                code.set_attr("class", format!("language-{}", lang));
+                            }
+                            top.add(Element::new("pre", "").add_with(code.clone()));
+                            stack.push(code);
+                            continue;
+                        }
+                        Tag::List(Some(start)) => {
+                            let elem = Element::new("ol", "");
+                            if start != 1 {
+                                elem.set_attr("start", start.to_string());
+                            }
+                            elem
+                        }
+                        Tag::List(None) => Element::new("ul", ""),
+                        Tag::Item => Element::new("li", ""),
+                        Tag::Emphasis => Element::new("em", ""),
+                        Tag::Strong => Element::new("strong", ""),
+                        Tag::Strikethrough => Element::new("del", ""),
+                        Tag::Link { dest_url, title, .. } => {
+                            let elem = Element::new("a", "");
+                            elem.set_attr("href", dest_url.to_string());
+                            if !title.is_empty() {
+                                elem.set_attr("title", title.to_string());
+                            }
+                            elem
+                        }
+                        Tag::Image { dest_url, title, .. } => {
+                            let elem = Element::new("img", "").onetag(true);
+                            elem.set_attr("src", dest_url.to_string());
+                            if !title.is_empty() {
+                                elem.set_attr("title", title.to_string());
+                            }
+                            alt.clear();
+                            elem
+                        }
+                        Tag::Table(_) => Element::new("table", ""),
+                        Tag::TableHead => {
+                            in_table_head = true;
+                            let row = Element::new("tr", "");
+                            top.add(Element::new("thead", "").add_with(row.clone()));
+                            stack.push(row);
+                            continue;
+                        }
+                        Tag::TableRow => {
+                            // 表体行放入tbody
+                            let last_is_tbody = top.children().last()
+                                .is_some_and(|c| c.inner.borrow().tag == "tbody");
+                            if !last_is_tbody {
+                                top.add(Element::new("tbody", ""));
+                            }
+                            let row = Element::new("tr", "");
+                            top.children().last().unwrap().add(row.clone());
+                            stack.push(row);
+                            continue;
+                        }
+                        Tag::TableCell => Element::new(if in_table_head { "th" } else { "td" }, ""),
+                        Tag::FootnoteDefinition(label) => {
+                            let elem = Element::new("div", "");
+                            elem.set_attr("class", "footnote-definition");
+                            elem.set_attr("id", label.to_string());
+                            elem
+                        }
+                        Tag::HtmlBlock => {
+                            // 内容由Html事件提供
+                            continue;
+                        }
+                        _ => Element::new("div", ""),
+                    };
+                    top.add(elem.clone());
+                    stack.push(elem);
+                }
+                Event::End(tag) => {
+                    match tag {
+                        TagEnd::HtmlBlock => continue,
+                        TagEnd::TableHead => in_table_head = false,
+                        TagEnd::Image => top.set_attr("alt", alt.as_str()),
+                        _ => {}
+                    }
+                    stack.pop();
+                }
+                Event::Text(text) if in_image => alt.push_str(&text),
+                Event::Code(text) if in_image => alt.push_str(&text),
+                Event::Text(text) | Event::InlineMath(text) | Event::DisplayMath(text) => {
+                    top.add(Element::new("", text.to_string()));
+                }
+                Event::Code(text) => {
+                    top.add(Element::new("code", text.to_string()));
+                }
+                Event::Html(html) | Event::InlineHtml(html) => {
+                    top.add(raw_html(&html));
+                }
+                Event::FootnoteReference(label) => {
+                    let link = Element::new("a", label.to_string());
+                    link.set_attr("href", format!("#{}", label));
+                    top.add(Element::new("sup", "").add_with(link));
+                }
+                Event::SoftBreak => {
+                    top.add(Element::new("", "\n"));
+                }
+                Event::HardBreak => {
+                    top.add(Element::new("br", "").onetag(true));
+                }
+                Event::Rule => {
+                    top.add(Element::new("hr", "").onetag(true));
+                }
+                Event::TaskListMarker(checked) => {
+                    let checkbox = Element::new("input", "").onetag(true);
+                    checkbox.set_attr("type", "checkbox");
+                    checkbox.set_attr("disabled", "");
+                    if checked {
+                        checkbox.set_attr("checked", "");
+                    }
+                    top.add(checkbox);
+                }
+            }
+        }
+
+        root
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_markdown() {
+        let md = "\
+- a
+- **b** ~~c~~
+
+```rust
+let x = 1 < 2;
+```
+
+| h |
+|---|
+| v |
+
+<div>raw</div>
+
+![alt `x`](p.png)";
+        let doc = Element::from_markdown(md);
+        let children = doc.children();
+        assert_eq!(children[0].render(""), "<ul><li>a</li><li><strong>b</strong> <del>c</del></li></ul>");
+        assert_eq!(
+            children[1].render(""),
+            "<pre><code class=\"language-rust\">let x = 1 &lt; 2;\n</code></pre>"
+        );
+        assert_eq!(
+            children[2].render(""),
+            "<table><thead><tr><th>h</th></tr></thead><tbody><tr><td>v</td></tr></tbody></table>"
+        );
+        assert_eq!(children[3].render(""), "<div>raw</div>\n");
+
+        let img = &children[4].children()[0];
+        assert_eq!(img.inner.borrow().kws.get("alt").map(String::as_str), Some("alt x"));
+    }
+}
@@ -0,0 +1,220 @@
+//! 记忆化构建
+
+use core::cell::RefCell;
+use core::hash::{BuildHasher, Hash};
+use core::panic::Location;
+use std::collections::HashMap;
+use std::hash::RandomState;
+
+use crate::Element;
+
+/// 调用位置和key的哈希
+type MemoKey = (&'static Location<'static>, u64);
+
+/// 记忆化构建的缓存，按调用位置和`key`的哈希保存构建结果，需要`std`特性
+///
+/// 同一调用位置可以保存多个`key`的结果（循环或可复用组件中的`memo`），超过容量时淘汰最久未使用的结果。
+/// `Element::memo`使用每个线程自己的缓存（见`MemoCache::local`），也可以创建单独的缓存按页面或请求管理。
+///
+/// 注意：只比较`key`的64位哈希，不保存`key`本身。哈希使用随机的种子，
+/// 不同的`key`哈希相同的概率极低（约2<sup>-64</sup>），但发生时会返回另一个`key`的结果
+///
+/// ```
+/// # use htmlbuilder::{Element, MemoCache};
+/// let mut cache = MemoCache::new(16);
+/// for i in [1u32, 2, 1, 2] {
+///     let item = cache.memo(i, || Element::new("li", i.to_string()));
+///     assert_eq!(item.render(""), format!("<li>{}</li>", i));
+/// }
+/// assert_eq!(cache.len(), 2);
+/// cache.clear();
+/// assert!(cache.is_empty());
+/// ```
+pub struct MemoCache {
+    entries: HashMap<MemoKey, (u64, Element)>, // 最近使用的序号和结果
+    capacity: usize,
+    tick: u64,
+    state: RandomState,
+}
+
+impl MemoCache {
+    /// 线程缓存默认的容量
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// 最多保存`capacity`个结果的缓存
+    pub fn new(capacity: usize) -> Self {
+        Self { entries: HashMap::new(), capacity, tick: 0, state: RandomState::new() }
+    }
+
+    /// 同一调用位置、相同`key`已有结果时返回其深拷贝，否则调用`build`构建并保存
+    #[track_caller]
+    pub fn memo(&mut self, key: impl Hash, build: impl FnOnce() -> Element) -> Element {
+        let key = (Location::caller(), self.state.hash_one(key));
+        if let Some(elem) = self.get(key) {
+            return elem;
+        }
+        let elem = build();
+        self.insert(key, &elem);
+        elem
+    }
+
+    fn get(&mut self, key: MemoKey) -> Option<Element> {
+        self.tick += 1;
+        let (used, elem) = self.entries.get_mut(&key)?;
+        *used = self.tick;
+        Some(elem.deep_clone())
+    }
+
+    fn insert(&mut self, key: MemoKey, elem: &Element) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            self.evict_oldest();
+        }
+        self.tick += 1;
+        self.entries.insert(key, (self.tick, elem.deep_clone()));
+    }
+
+    /// 删除全部结果
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// 保存的结果数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 是否没有保存任何结果
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 修改容量，超出的结果按最久未使用的顺序删除
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            self.evict_oldest();
+        }
+    }
+
+    /// 删除最久未使用的结果
+    fn evict_oldest(&mut self) {
+        let oldest = self.entries.iter().min_by_key(|(_, (used, _))| *used).map(|(key, _)| *key);
+        if let Some(oldest) = oldest {
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// 访问当前线程`Element::memo`使用的缓存，如清空或修改容量
+    ///
+    /// `f`中不能调用`Element::memo`
+    ///
+    /// ```
+    /// # use htmlbuilder::{Element, MemoCache};
+    /// Element::memo("page", || Element::new("p", ""));
+    /// MemoCache::local(|cache| cache.clear());
+    /// assert!(MemoCache::local(|cache| cache.is_empty()));
+    /// ```
+    pub fn local<R>(f: impl FnOnce(&mut MemoCache) -> R) -> R {
+        MEMO.with_borrow_mut(f)
+    }
+}
+
+impl Default for MemoCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+std::thread_local! {
+    static MEMO: RefCell<MemoCache> = RefCell::default();
+}
+
+impl Element {
+    /// 记忆化构建子树，需要`std`特性
+    ///
+    /// 同一调用位置的`key`已经构建过时复用当时的结果，否则调用`build`构建。
+    /// 结果保存在当前线程的`MemoCache`中，返回的是缓存的深拷贝，可以放入新的元素树
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let mut builds = 0;
+    /// for version in [1, 1, 2] {
+    ///     let body = Element::memo(version, || {
+    ///         builds += 1;
+    ///         Element::new("article", format!("v{}", version))
+    ///     });
+    ///     assert_eq!(body.render(""), format!("<article>v{}</article>", version));
+    /// }
+    /// assert_eq!(builds, 2);
+    /// ```
+    #[track_caller]
+    pub fn memo(key: impl Hash, build: impl FnOnce() -> Element) -> Element {
+        let key = MEMO.with_borrow(|memo| (Location::caller(), memo.state.hash_one(key)));
+        if let Some(elem) = MEMO.with_borrow_mut(|memo| memo.get(key)) {
+            return elem;
+        }
+        // build中可能再次调用memo，构建期间不持有缓存
+        let elem = build();
+        MEMO.with_borrow_mut(|memo| memo.insert(key, &elem));
+        elem
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(title: &str, builds: &mut usize) -> Element {
+        let body = Element::memo(title, || {
+            *builds += 1;
+            Element::new("h1", title).add_with(Element::memo(title.len(), || Element::new("p", "")))
+        });
+        Element::new("body", "").add_with(body)
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut builds = 0;
+        let first = page("a", &mut builds);
+        let second = page("a", &mut builds);
+        assert_eq!(builds, 1);
+        assert_eq!(first.render(""), second.render(""));
+        // 结果相互独立
+        first.children()[0].configcnt("changed");
+        assert_eq!(second.render(""), "<body><h1>a<p></p></h1></body>");
+
+        // 同一调用位置保存多个key的结果
+        page("b", &mut builds);
+        page("a", &mut builds);
+        assert_eq!(builds, 2);
+
+        MemoCache::local(|cache| cache.clear());
+        page("a", &mut builds);
+        assert_eq!(builds, 3);
+    }
+
+    #[test]
+    fn test_memo_cache_capacity() {
+        let mut cache = MemoCache::new(2);
+        let mut builds = 0;
+        for i in [1u32, 2, 1, 3, 1, 2] {
+            cache.memo(i, || {
+                builds += 1;
+                Element::new("li", "")
+            });
+        }
+        // 3淘汰了最久未使用的2，1一直保留
+        assert_eq!(builds, 4);
+        assert_eq!(cache.len(), 2);
+
+        cache.set_capacity(1);
+        assert_eq!(cache.len(), 1);
+        let mut none = MemoCache::new(0);
+        none.memo(1, || Element::new("p", ""));
+        assert!(none.is_empty());
+    }
+}
@@ -0,0 +1,288 @@
+//! SEO元数据
+//!
+//! 统一生成`<title>`、`description`、Open Graph与Twitter卡片等`<meta>`/`<link>`元素
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::meta::Meta;
+//!
+//! let head = Element::new("head", "");
+//! Meta::new()
+//!     .title("My Page")
+//!     .description("A page built with htmlbuilder")
+//!     .og_image("https://example.com/cover.png")
+//!     .twitter_card("summary_large_image")
+//!     .canonical("https://example.com/page")
+//!     .apply_to(&head);
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Element;
+use crate::tags::{link, meta, title};
+
+/// 元数据错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetaError {
+    /// `hreflang`不是有效的语言代码
+    InvalidHreflang(String),
+}
+
+impl fmt::Display for MetaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaError::InvalidHreflang(lang) => write!(f, "`{}` is not a valid hreflang code", lang),
+        }
+    }
+}
+
+impl core::error::Error for MetaError {}
+
+/// 规范链接`<link rel="canonical" href="...">`
+pub fn canonical(url: impl Into<String>) -> Element {
+    link("canonical", url)
+}
+
+/// 是否为有效的`hreflang`：`x-default`，或语言代码后接可选的文字（4个字母）和地区（2个字母或3个数字），
+/// 如`en`、`zh-Hant`、`pt-BR`、`es-419`
+fn valid_hreflang(lang: &str) -> bool {
+    if lang.eq_ignore_ascii_case("x-default") {
+        return true;
+    }
+    let mut parts = lang.split('-').peekable();
+    let Some(language) = parts.next() else { return false };
+    if !(2..=3).contains(&language.len()) || !language.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return false;
+    }
+    if let Some(script) = parts.peek()
+        && script.len() == 4
+        && script.bytes().all(|b| b.is_ascii_alphabetic())
+    {
+        parts.next();
+    }
+    match parts.next() {
+        None => true,
+        Some(region) => {
+            let region_ok = (region.len() == 2 && region.bytes().all(|b| b.is_ascii_alphabetic()))
+                || (region.len() == 3 && region.bytes().all(|b| b.is_ascii_digit()));
+            region_ok && parts.next().is_none()
+        }
+    }
+}
+
+/// 其他语言版本的链接`<link rel="alternate" hreflang="..." href="...">`
+///
+/// ```
+/// use htmlbuilder::meta::{alternate_lang, MetaError};
+///
+/// let link = alternate_lang("pt-BR", "https://example.com/pt-br/").unwrap();
+/// assert_eq!(link.attr_list()[1], ("hreflang".to_string(), "pt-BR".to_string()));
+/// assert_eq!(alternate_lang("en_US", "/"), Err(MetaError::InvalidHreflang("en_US".into())));
+/// ```
+pub fn alternate_lang(lang: &str, url: impl Into<String>) -> Result<Element, MetaError> {
+    if !valid_hreflang(lang) {
+        return Err(MetaError::InvalidHreflang(lang.into()));
+    }
+    let elem = link("alternate", url);
+    elem.set_attr("hreflang", lang);
+    Ok(elem)
+}
+
+/// 页面元数据构建器
+#[derive(Default, Clone)]
+pub struct Meta {
+    title: Option<String>,
+    description: Option<String>,
+    keywords: Vec<String>,
+    canonical: Option<String>,
+    og_type: Option<String>,
+    og_image: Option<String>,
+    site_name: Option<String>,
+    twitter_card: Option<String>,
+    twitter_site: Option<String>,
+}
+
+fn named(name: &str, content: &str) -> Element {
+    let elem = meta();
+    elem.set_attr("name", name);
+    elem.set_attr("content", content);
+    elem
+}
+
+fn property(prop: &str, content: &str) -> Element {
+    let elem = meta();
+    elem.set_attr("property", prop);
+    elem.set_attr("content", content);
+    elem
+}
+
+impl Meta {
+    /// 创建元数据构建器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 页面标题，同时用于`og:title`与`twitter:title`
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// 页面描述，同时用于`og:description`与`twitter:description`
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// 关键词
+    pub fn keywords<I, S>(mut self, keywords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.keywords.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+
+    /// 规范链接`<link rel="canonical">`，同时用于`og:url`
+    pub fn canonical(mut self, url: impl Into<String>) -> Self {
+        self.canonical = Some(url.into());
+        self
+    }
+
+    /// `og:type`，默认`website`
+    pub fn og_type(mut self, og_type: impl Into<String>) -> Self {
+        self.og_type = Some(og_type.into());
+        self
+    }
+
+    /// 分享图片，同时用于`og:image`与`twitter:image`
+    pub fn og_image(mut self, url: impl Into<String>) -> Self {
+        self.og_image = Some(url.into());
+        self
+    }
+
+    /// `og:site_name`
+    pub fn site_name(mut self, name: impl Into<String>) -> Self {
+        self.site_name = Some(name.into());
+        self
+    }
+
+    /// Twitter卡片类型，如`summary`、`summary_large_image`
+    pub fn twitter_card(mut self, card: impl Into<String>) -> Self {
+        self.twitter_card = Some(card.into());
+        self
+    }
+
+    /// `twitter:site`账号，如`@rustlang`
+    pub fn twitter_site(mut self, site: impl Into<String>) -> Self {
+        self.twitter_site = Some(site.into());
+        self
+    }
+
+    /// 生成全部元素
+    pub fn elements(&self) -> Vec<Element> {
+        let mut elems = Vec::new();
+
+        if let Some(t) = &self.title {
+            elems.push(title().add_with(Element::new("", t.as_str())));
+        }
+        if let Some(d) = &self.description {
+            elems.push(named("description", d));
+        }
+        if !self.keywords.is_empty() {
+            elems.push(named("keywords", &self.keywords.join(", ")));
+        }
+        if let Some(url) = &self.canonical {
+            elems.push(canonical(url.as_str()));
+        }
+
+        // Open Graph
+        elems.push(property("og:type", self.og_type.as_deref().unwrap_or("website")));
+        if let Some(t) = &self.title {
+            elems.push(property("og:title", t));
+        }
+        if let Some(d) = &self.description {
+            elems.push(property("og:description", d));
+        }
+        if let Some(url) = &self.canonical {
+            elems.push(property("og:url", url));
+        }
+        if let Some(img) = &self.og_image {
+            elems.push(property("og:image", img));
+        }
+        if let Some(name) = &self.site_name {
+            elems.push(property("og:site_name", name));
+        }
+
+        // Twitter
+        if let Some(card) = &self.twitter_card {
+            elems.push(named("twitter:card", card));
+            if let Some(site) = &self.twitter_site {
+                elems.push(named("twitter:site", site));
+            }
+            if let Some(t) = &self.title {
+                elems.push(named("twitter:title", t));
+            }
+            if let Some(d) = &self.description {
+                elems.push(named("twitter:description", d));
+            }
+            if let Some(img) = &self.og_image {
+                elems.push(named("twitter:image", img));
+            }
+        }
+
+        elems
+    }
+
+    /// 将全部元素添加到`head`中
+    pub fn apply_to(&self, head: &Element) {
+        for elem in self.elements() {
+            head.add(elem);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attr(elem: &Element, name: &str) -> Option<String> {
+        elem.inner.borrow().kws.get(name).cloned()
+    }
+
+    #[test]
+    fn test_meta() {
+        let elems = Meta::new()
+            .title("A & B")
+            .description("desc")
+            .og_image("img.png")
+            .twitter_card("summary")
+            .elements();
+
+        assert_eq!(elems[0].render(""), "<title>A &amp; B</title>");
+        let find = |key: &str, value: &str| {
+            elems.iter()
+                .find(|e| attr(e, key).as_deref() == Some(value))
+                .and_then(|e| attr(e, "content"))
+        };
+        assert_eq!(find("name", "description").as_deref(), Some("desc"));
+        assert_eq!(find("property", "og:type").as_deref(), Some("website"));
+        assert_eq!(find("property", "og:title").as_deref(), Some("A &amp; B"));
+        assert_eq!(find("name", "twitter:image").as_deref(), Some("img.png"));
+        assert_eq!(find("property", "og:url"), None);
+    }
+
+    #[test]
+    fn test_hreflang() {
+        for lang in ["en", "EN-us", "zh-Hant", "zh-Hant-TW", "es-419", "x-default", "fil"] {
+            assert!(valid_hreflang(lang), "{}", lang);
+        }
+        for lang in ["", "e", "english", "en_US", "en-", "en-USA", "zh-Hant-TW-x", "12"] {
+            assert!(!valid_hreflang(lang), "{}", lang);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! 内联样式和脚本的压缩
+//!
+//! 只做保守的处理：删除注释和多余的空白，字符串、模板字符串和正则表达式保持原样。
+//! 脚本保留换行，不受自动分号插入的影响
+
+use alloc::string::String;
+
+use crate::ElementInner;
+
+/// 按元素类型压缩原始文本内容，不是css或js时返回`None`
+pub(crate) fn minify_rawtext(inner: &ElementInner) -> Option<String> {
+    let ty = inner.kws.get("type").map(|t| t.trim().to_ascii_lowercase());
+    match &*inner.tag {
+        "style" if ty.as_deref().is_none_or(|t| t.is_empty() || t == "text/css") => Some(minify_css(&inner.content)),
+        "script"
+            if ty.as_deref().is_none_or(|t| {
+                matches!(t, "" | "module" | "text/javascript" | "application/javascript")
+            }) =>
+        {
+            Some(minify_js(&inner.content))
+        }
+        _ => None,
+    }
+}
+
+/// 复制从`quote`开始的字符串字面量，返回结束后的位置
+fn copy_string(src: &str, start: usize, out: &mut String) -> usize {
+    let bytes = src.as_bytes();
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote => {
+                i += 1;
+                break;
+            }
+            _ => i += 1,
+        }
+    }
+    let end = i.min(bytes.len());
+    out.push_str(&src[start..end]);
+    end
+}
+
+/// 跳过`/* */`注释，返回结束后的位置
+fn skip_block_comment(src: &str, start: usize) -> usize {
+    src[start + 2..].find("*/").map_or(src.len(), |i| start + 2 + i + 2)
+}
+
+/// 删除空白后不会改变含义的css符号
+fn css_punct(c: char) -> bool {
+    matches!(c, '{' | '}' | ';' | ',' | '>')
+}
+
+/// 压缩css：删除注释，合并空白，删除符号两侧和`}`前最后一个`;`
+///
+/// `:`前的空白保留，`a :hover`与`a:hover`含义不同
+pub(crate) fn minify_css(css: &str) -> String {
+    let bytes = css.as_bytes();
+    let mut out = String::with_capacity(css.len());
+    let mut space = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = css[i..].chars().next().unwrap();
+        if c == '/' && bytes.get(i + 1) == Some(&b'*') {
+            i = skip_block_comment(css, i);
+            space = true;
+            continue;
+        }
+        if c.is_whitespace() {
+            space = true;
+            i += c.len_utf8();
+            continue;
+        }
+        if c == '}' && out.ends_with(';') {
+            out.pop();
+        }
+        if space && !out.is_empty() && !css_punct(c) && !out.ends_with(|p: char| css_punct(p) || p == ':') {
+            out.push(' ');
+        }
+        space = false;
+        if c == '"' || c == '\'' {
+            i = copy_string(css, i, &mut out);
+        } else {
+            out.push(c);
+            i += c.len_utf8();
+        }
+    }
+    out
+}
+
+/// 删除空白后不会改变含义的js符号，`+`和`-`不在其中（`a + +b`）
+fn js_punct(c: char) -> bool {
+    matches!(c, '{' | '}' | '(' | ')' | '[' | ']' | ';' | ',' | ':' | '=' | '<' | '>' | '?' | '!' | '&' | '|' | '*' | '%')
+}
+
+/// 之后可以是表达式的关键字，`return /a/`中的`/`开始正则表达式
+const REGEX_KEYWORDS: &[&str] = &[
+    "return", "typeof", "instanceof", "in", "of", "new", "delete", "void", "throw", "case", "do",
+    "else", "yield", "await",
+];
+
+/// `/`之前为这些符号、上述关键字或没有内容时，`/`开始正则表达式而不是除号
+fn regex_allowed(before: &str) -> bool {
+    let before = before.trim_end();
+    let word = before.trim_end_matches(|c: char| c.is_alphanumeric() || c == '_' || c == '$');
+    if word.len() < before.len() {
+        // 属性访问（`a.in / 2`）不是关键字
+        return !word.ends_with('.') && REGEX_KEYWORDS.contains(&&before[word.len()..]);
+    }
+    before.chars().next_back().is_none_or(|p| "(,=:[!&|?{};+-*%<>~^".contains(p))
+}
+
+/// 压缩js：删除注释，删除行首尾空白和空行，合并行内空白，删除符号两侧的空白
+pub(crate) fn minify_js(js: &str) -> String {
+    let bytes = js.as_bytes();
+    let mut out = String::with_capacity(js.len());
+    let mut space = false;
+    let mut newline = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = js[i..].chars().next().unwrap();
+        if c == '/' && bytes.get(i + 1) == Some(&b'/') {
+            i = js[i..].find('\n').map_or(js.len(), |n| i + n);
+            continue;
+        }
+        if c == '/' && bytes.get(i + 1) == Some(&b'*') {
+            let end = skip_block_comment(js, i);
+            if js[i..end].contains('\n') {
+                newline = true;
+            } else {
+                space = true;
+            }
+            i = end;
+            continue;
+        }
+        if c == '\n' {
+            newline = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            space = true;
+            i += c.len_utf8();
+            continue;
+        }
+
+        let prev = out.chars().next_back();
+        if newline && !out.is_empty() {
+            out.push('\n');
+        } else if space && prev.is_some_and(|p| !js_punct(p)) && !js_punct(c) {
+            out.push(' ');
+        }
+        space = false;
+        newline = false;
+
+        match c {
+            '"' | '\'' | '`' => i = copy_string(js, i, &mut out),
+            '/' if regex_allowed(&out) => {
+                // 正则表达式，`[...]`中的`/`不结束表达式
+                let start = i;
+                let mut class = false;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    match bytes[i] {
+                        b'\\' => i += 1,
+                        b'[' => class = true,
+                        b']' => class = false,
+                        b'/' if !class => {
+                            i += 1;
+                            break;
+                        }
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                let end = i.min(bytes.len());
+                out.push_str(&js[start..end]);
+                i = end;
+            }
+            _ => {
+                out.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+    out
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Element, RenderOptions};
+    use crate::script::Script;
+
+    #[test]
+    fn test_minify_css() {
+        let css = "/* 标题 */\nh1 , h2 > a:hover {\n  color: red;\n  font: 12px \"Open  Sans\";\n}\n\np :first-child { margin: calc(1px + 2px) }\n";
+        assert_eq!(minify_css(css), "h1,h2>a:hover{color:red;font:12px \"Open  Sans\"}p :first-child{margin:calc(1px + 2px)}");
+    }
+
+    #[test]
+    fn test_minify_js() {
+        let js = "// init\nconst url = \"http://x\"; /* a */ let re = /[/]\\/+/g;\n\n  if (a / b > 1) {\n    x = a + +b; // sum\n  }\nlet s = `a  // b`;\n";
+        assert_eq!(
+            minify_js(js),
+            "const url=\"http://x\";let re=/[/]\\/+/g;\nif(a / b>1){\nx=a + +b;\n}\nlet s=`a  // b`;"
+        );
+    }
+
+    #[test]
+    fn test_minify_js_regex_after_keyword() {
+        let js = "function f(s) { return /\\/\\//.test(s) ? 1 : 2; }";
+        assert_eq!(minify_js(js), "function f(s){return /\\/\\//.test(s)?1:2;}");
+        assert_eq!(minify_js("x = typeof /a/; y = a.in / 2 // half"), "x=typeof /a/;y=a.in / 2");
+    }
+
+    #[test]
+    fn test_render_minified() {
+        let page = Element::new("div", "")
+            .add_with(Element::style_sheet("p {\n  color: red;\n}"))
+            .add_with(Script::inline("let a = 1; // one\n</script>"))
+            .add_with(Element::new("script", "{ \"a\": 1 }").attrs(&[("type", "application/json")]).rawtext(true))
+            .add_with(Element::new("pre", "a  /* b */"));
+        let opts = RenderOptions::new().minify_assets(true);
+        assert_eq!(
+            page.render_with("", &opts),
+            "<div><style>p{color:red}</style><script>let a=1;\n<\\/script></script><script type=\"application/json\">{ \"a\": 1 }</script><pre>a  /* b */</pre></div>"
+        );
+        assert_eq!(page.render_with("", &RenderOptions::new()), page.render(""));
+    }
+}
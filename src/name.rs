@@ -0,0 +1,130 @@
+//! 标签名与属性名
+//!
+//! 大型文档中相同的标签名和属性名重复出现，常见的名称直接指向静态字符串，不单独分配内存
+
+use alloc::borrow::Cow;
+use alloc::rc::Rc;
+use alloc::string::String;
+use core::{fmt, ops::Deref, ptr};
+
+/// 常见html标签，按字典序排列；空字符串用于文本节点
+const KNOWN_TAGS: &[&str] = &[
+    "", "a", "abbr", "address", "area", "article", "aside", "audio", "b", "base", "bdi", "bdo",
+    "blockquote", "body", "br", "button", "canvas", "caption", "cite", "code", "col",
+    "colgroup", "data", "datalist", "dd", "del", "details", "dfn", "dialog", "div", "dl", "dt",
+    "em", "embed", "fieldset", "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4",
+    "h5", "h6", "head", "header", "hgroup", "hr", "html", "i", "iframe", "img", "input", "ins",
+    "kbd", "label", "legend", "li", "link", "main", "map", "mark", "math", "menu", "meta",
+    "meter", "nav", "noscript", "object", "ol", "optgroup", "option", "output", "p", "param",
+    "picture", "pre", "progress", "q", "rp", "rt", "ruby", "s", "samp", "script", "search",
+    "section", "select", "slot", "small", "source", "span", "strong", "style", "sub", "summary",
+    "sup", "svg", "table", "tbody", "td", "template", "textarea", "tfoot", "th", "thead",
+    "time", "title", "tr", "track", "u", "ul", "var", "video", "wbr",
+];
+
+/// 常见html属性，按字典序排列
+const KNOWN_ATTRS: &[&str] = &[
+    "accept", "action", "alt", "aria-describedby", "aria-hidden", "aria-label",
+    "aria-labelledby", "async", "autocomplete", "charset", "checked", "class", "colspan",
+    "content", "crossorigin", "data", "datetime", "defer", "dir", "disabled", "download",
+    "for", "form", "height", "hidden", "href", "hreflang", "id", "integrity", "lang", "loading",
+    "max", "media", "method", "min", "multiple", "name", "nonce", "pattern", "placeholder",
+    "property", "readonly", "rel", "required", "role", "rowspan", "scope", "selected", "sizes",
+    "span", "src", "srcset", "step", "style", "tabindex", "target", "title", "type", "value",
+    "width",
+];
+
+fn known(table: &[&'static str], name: &str) -> Option<&'static str> {
+    table.binary_search(&name).ok().map(|i| table[i])
+}
+
+/// 标签名，常见标签指向静态字符串，其余标签使用`Rc<str>`，拷贝时共享
+#[derive(Clone)]
+pub(crate) enum TagName {
+    Static(&'static str),
+    Shared(Rc<str>),
+}
+
+impl TagName {
+    pub(crate) fn new(name: String) -> Self {
+        match known(KNOWN_TAGS, &name) {
+            Some(name) => Self::Static(name),
+            None => Self::Shared(name.into()),
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            Self::Static(name) => name,
+            Self::Shared(name) => name,
+        }
+    }
+}
+
+impl Deref for TagName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for TagName {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            // 常见标签只比较指针
+            (Self::Static(a), Self::Static(b)) => ptr::eq(*a, *b) || a == b,
+            (Self::Shared(a), Self::Shared(b)) => Rc::ptr_eq(a, b) || a == b,
+            _ => self.as_str() == other.as_str(),
+        }
+    }
+}
+
+impl<T: AsRef<str> + ?Sized> PartialEq<T> for TagName {
+    fn eq(&self, other: &T) -> bool {
+        self.as_str() == other.as_ref()
+    }
+}
+
+impl fmt::Display for TagName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for TagName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// 属性名，常见属性指向静态字符串，其余属性名由元素自己持有，随元素释放
+pub(crate) fn attr_name(name: impl Into<Cow<'static, str>>) -> Cow<'static, str> {
+    let name = name.into();
+    match known(KNOWN_ATTRS, &name) {
+        Some(known) => Cow::Borrowed(known),
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_sorted() {
+        assert!(KNOWN_TAGS.windows(2).all(|w| w[0] < w[1]));
+        assert!(KNOWN_ATTRS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_tag_name() {
+        let div = TagName::new("div".into());
+        assert!(matches!(div, TagName::Static(_)));
+        assert!(matches!(TagName::new("my-widget".into()), TagName::Shared(_)));
+        assert!(div == TagName::new("div".into()));
+        assert!(div == "div" && div != "span");
+        assert!(matches!(attr_name(String::from("class")), Cow::Borrowed("class")));
+        assert!(matches!(attr_name(String::from("x-custom")), Cow::Owned(_)));
+    }
+}
@@ -0,0 +1,413 @@
+//! 导航组件
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::tags::{a, li, nav, ol, span, ul};
+use crate::{Component, Element};
+
+/// 分页导航，页码从1开始
+///
+/// 输出`<nav aria-label="pagination">`，包含上一页、下一页、首末页和当前页附近的页码，
+/// 省略的页码显示为`…`，当前页的链接带`aria-current="page"`
+///
+/// ```
+/// use htmlbuilder::Element;
+/// use htmlbuilder::nav::Pagination;
+///
+/// let pager = Element::from(Pagination::new(5, 20, |page| format!("/posts?page={}", page)).window(1));
+/// htmlbuilder::assert_html_eq!(pager, concat!(
+///     r#"<nav aria-label="pagination"><ul class="pagination">"#,
+///     r#"<li><a href="/posts?page=4" rel="prev">Previous</a></li>"#,
+///     r#"<li><a href="/posts?page=1">1</a></li>"#,
+///     r#"<li><span>…</span></li>"#,
+///     r#"<li><a href="/posts?page=4">4</a></li>"#,
+///     r#"<li class="active"><a href="/posts?page=5" aria-current="page">5</a></li>"#,
+///     r#"<li><a href="/posts?page=6">6</a></li>"#,
+///     r#"<li><span>…</span></li>"#,
+///     r#"<li><a href="/posts?page=20">20</a></li>"#,
+///     r#"<li><a href="/posts?page=6" rel="next">Next</a></li>"#,
+///     "</ul></nav>",
+/// ));
+/// ```
+pub struct Pagination<F: Fn(usize) -> String> {
+    current: usize,
+    total_pages: usize,
+    url: F,
+    window: usize,
+    prev_label: String,
+    next_label: String,
+    list_class: String,
+    item_class: String,
+    active_class: String,
+    disabled_class: String,
+}
+
+impl<F: Fn(usize) -> String> Pagination<F> {
+    /// 创建分页导航
+    ///
+    /// current: 当前页，超出范围时取最近的有效页
+    ///
+    /// url_fn: 由页码生成链接地址
+    pub fn new(current: usize, total_pages: usize, url_fn: F) -> Self {
+        let total_pages = total_pages.max(1);
+        Self {
+            current: current.clamp(1, total_pages),
+            total_pages,
+            url: url_fn,
+            window: 2,
+            prev_label: "Previous".into(),
+            next_label: "Next".into(),
+            list_class: "pagination".into(),
+            item_class: String::new(),
+            active_class: "active".into(),
+            disabled_class: "disabled".into(),
+        }
+    }
+
+    /// 当前页两侧显示的页码数，默认为2
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// 上一页和下一页的文本
+    pub fn labels(mut self, prev: impl Into<String>, next: impl Into<String>) -> Self {
+        self.prev_label = prev.into();
+        self.next_label = next.into();
+        self
+    }
+
+    /// `<ul>`的类名，默认为`pagination`
+    pub fn list_class(mut self, class: impl Into<String>) -> Self {
+        self.list_class = class.into();
+        self
+    }
+
+    /// 每个`<li>`的类名，默认为空
+    pub fn item_class(mut self, class: impl Into<String>) -> Self {
+        self.item_class = class.into();
+        self
+    }
+
+    /// 当前页`<li>`的类名，默认为`active`
+    pub fn active_class(mut self, class: impl Into<String>) -> Self {
+        self.active_class = class.into();
+        self
+    }
+
+    /// 不可用的上一页/下一页`<li>`的类名，默认为`disabled`
+    pub fn disabled_class(mut self, class: impl Into<String>) -> Self {
+        self.disabled_class = class.into();
+        self
+    }
+
+    /// 显示的页码，None表示省略
+    fn pages(&self) -> Vec<Option<usize>> {
+        let mut start = self.current.saturating_sub(self.window).max(1);
+        let mut end = self.current.saturating_add(self.window).min(self.total_pages);
+        // 只省略一页时直接显示该页
+        if start <= 3 {
+            start = 1;
+        }
+        if end.saturating_add(2) >= self.total_pages {
+            end = self.total_pages;
+        }
+        let mut pages = Vec::new();
+        if start > 1 {
+            pages.extend([Some(1), None]);
+        }
+        pages.extend((start..=end).map(Some));
+        if end < self.total_pages {
+            pages.extend([None, Some(self.total_pages)]);
+        }
+        pages
+    }
+
+    fn item(&self, content: Element) -> Element {
+        li().class_if(!self.item_class.is_empty(), &self.item_class).add_with(content)
+    }
+
+    /// 上一页/下一页，没有该页时输出不可用的`<span>`
+    fn step(&self, page: Option<usize>, label: &str, rel: &'static str) -> Element {
+        match page {
+            Some(page) => {
+                let link = a((self.url)(page)).add_with(Element::new("", label));
+                link.set_attr("rel", rel);
+                self.item(link)
+            }
+            None => {
+                let text = span().add_with(Element::new("", label));
+                text.set_attr("aria-disabled", "true");
+                self.item(text).class_if(!self.disabled_class.is_empty(), &self.disabled_class)
+            }
+        }
+    }
+}
+
+impl<F: Fn(usize) -> String> Component for Pagination<F> {
+    fn view(&self) -> Element {
+        let list = ul().class_if(!self.list_class.is_empty(), &self.list_class);
+        list.add(self.step((self.current > 1).then(|| self.current - 1), &self.prev_label, "prev"));
+        for page in self.pages() {
+            let item = match page {
+                Some(page) => {
+                    let link = a((self.url)(page)).add_with(Element::new("", page.to_string()));
+                    if page == self.current {
+                        link.set_attr("aria-current", "page");
+                        self.item(link).class_if(!self.active_class.is_empty(), &self.active_class)
+                    } else {
+                        self.item(link)
+                    }
+                }
+                None => self.item(span().add_with(Element::new("", "…"))),
+            };
+            list.add(item);
+        }
+        let next = (self.current < self.total_pages).then(|| self.current + 1);
+        list.add(self.step(next, &self.next_label, "next"));
+
+        let nav = nav().add_with(list);
+        nav.set_attr("aria-label", "pagination");
+        nav
+    }
+}
+
+
+/// 面包屑导航，最后一项为当前页，不输出链接
+///
+/// ```
+/// use htmlbuilder::Element;
+/// use htmlbuilder::nav::Breadcrumbs;
+///
+/// let crumbs = Element::from(Breadcrumbs::from(&[("Home", "/"), ("Docs", "/docs"), ("Install", "/docs/install")]));
+/// htmlbuilder::assert_html_eq!(crumbs, concat!(
+///     r#"<nav aria-label="breadcrumb"><ol class="breadcrumb">"#,
+///     r#"<li><a href="/">Home</a></li>"#,
+///     r#"<li><a href="/docs">Docs</a></li>"#,
+///     r#"<li aria-current="page">Install</li>"#,
+///     "</ol></nav>",
+/// ));
+/// ```
+pub struct Breadcrumbs {
+    items: Vec<(String, String)>,
+    microdata: bool,
+}
+
+impl Breadcrumbs {
+    /// 是否输出schema.org `BreadcrumbList`微数据，默认不输出
+    pub fn microdata(mut self, microdata: bool) -> Self {
+        self.microdata = microdata;
+        self
+    }
+}
+
+impl<L: AsRef<str>, H: AsRef<str>> From<&[(L, H)]> for Breadcrumbs {
+    /// 由`(文本, 链接)`列表创建
+    fn from(items: &[(L, H)]) -> Self {
+        Self {
+            items: items.iter().map(|(label, href)| (label.as_ref().into(), href.as_ref().into())).collect(),
+            microdata: false,
+        }
+    }
+}
+
+impl<L: AsRef<str>, H: AsRef<str>, const N: usize> From<&[(L, H); N]> for Breadcrumbs {
+    fn from(items: &[(L, H); N]) -> Self {
+        Self::from(&items[..])
+    }
+}
+
+impl Component for Breadcrumbs {
+    fn view(&self) -> Element {
+        let list = ol().attrs(&[("class", "breadcrumb")]);
+        if self.microdata {
+            list.set_attr("itemscope", "");
+            list.set_attr("itemtype", "https://schema.org/BreadcrumbList");
+        }
+        for (index, (label, href)) in self.items.iter().enumerate() {
+            let last = index + 1 == self.items.len();
+            let item = li();
+            let text = Element::new("", label.as_str());
+            if self.microdata {
+                item.set_attr("itemprop", "itemListElement");
+                item.set_attr("itemscope", "");
+                item.set_attr("itemtype", "https://schema.org/ListItem");
+                let name = span().attrs(&[("itemprop", "name")]).add_with(text);
+                if last {
+                    item.add(name);
+                } else {
+                    let link = a(href.as_str()).add_with(name);
+                    link.set_attr("itemprop", "item");
+                    item.add(link);
+                }
+                let position = Element::new("meta", "").onetag(true);
+                position.set_attr("itemprop", "position");
+                position.set_attr("content", (index + 1).to_string());
+                item.add(position);
+            } else if last {
+                item.add(text);
+            } else {
+                item.add(a(href.as_str()).add_with(text));
+            }
+            if last {
+                item.set_attr("aria-current", "page");
+            }
+            list.add(item);
+        }
+        let nav = nav().add_with(list);
+        nav.set_attr("aria-label", "breadcrumb");
+        nav
+    }
+}
+
+/// 导航菜单，生成嵌套的`<nav><ul><li>`结构
+///
+/// 链接等于`active`的项标记为当前页：`<li>`添加`active`类，链接带`aria-current="page"`
+///
+/// ```
+/// use htmlbuilder::Element;
+/// use htmlbuilder::nav::NavMenu;
+///
+/// let menu = NavMenu::new()
+///     .item("Home", "/")
+///     .submenu("Docs", "/docs", NavMenu::new().item("Install", "/docs/install"))
+///     .active("/docs/install");
+/// htmlbuilder::assert_html_eq!(Element::from(menu), concat!(
+///     r#"<nav><ul><li><a href="/">Home</a></li>"#,
+///     r#"<li><a href="/docs">Docs</a><ul>"#,
+///     r#"<li class="active"><a href="/docs/install" aria-current="page">Install</a></li>"#,
+///     "</ul></li></ul></nav>",
+/// ));
+/// ```
+#[derive(Default)]
+pub struct NavMenu {
+    items: Vec<MenuItem>,
+    active: Option<String>,
+    aria_label: Option<String>,
+    active_class: Option<String>,
+}
+
+struct MenuItem {
+    label: String,
+    href: String,
+    submenu: Option<NavMenu>,
+}
+
+impl NavMenu {
+    /// 创建空菜单
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一项
+    pub fn item(mut self, label: impl Into<String>, href: impl Into<String>) -> Self {
+        self.items.push(MenuItem { label: label.into(), href: href.into(), submenu: None });
+        self
+    }
+
+    /// 添加带子菜单的一项，子菜单的`active`等设置由外层菜单决定
+    pub fn submenu(mut self, label: impl Into<String>, href: impl Into<String>, menu: NavMenu) -> Self {
+        self.items.push(MenuItem { label: label.into(), href: href.into(), submenu: Some(menu) });
+        self
+    }
+
+    /// 设置当前页的链接
+    pub fn active(mut self, href: impl Into<String>) -> Self {
+        self.active = Some(href.into());
+        self
+    }
+
+    /// 设置`<nav>`的`aria-label`，页面有多个导航时用于区分
+    pub fn aria_label(mut self, label: impl Into<String>) -> Self {
+        self.aria_label = Some(label.into());
+        self
+    }
+
+    /// 当前项`<li>`的类名，默认为`active`
+    pub fn active_class(mut self, class: impl Into<String>) -> Self {
+        self.active_class = Some(class.into());
+        self
+    }
+
+    fn list(&self, active: Option<&str>, active_class: &str) -> Element {
+        let list = ul();
+        for item in &self.items {
+            let link = a(item.href.as_str()).add_with(Element::new("", item.label.as_str()));
+            let current = active == Some(item.href.as_str());
+            if current {
+                link.set_attr("aria-current", "page");
+            }
+            let elem = li().class_if(current && !active_class.is_empty(), active_class).add_with(link);
+            if let Some(menu) = &item.submenu {
+                elem.add(menu.list(active, active_class));
+            }
+            list.add(elem);
+        }
+        list
+    }
+}
+
+impl Component for NavMenu {
+    fn view(&self) -> Element {
+        let nav = nav().add_with(self.list(self.active.as_deref(), self.active_class.as_deref().unwrap_or("active")));
+        if let Some(label) = &self.aria_label {
+            nav.set_attr("aria-label", label.as_str());
+        }
+        nav
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+
+    fn pages(current: usize, total: usize) -> Vec<Option<usize>> {
+        Pagination::new(current, total, |p| format!("?p={}", p)).pages()
+    }
+
+    #[test]
+    fn test_pagination() {
+        assert_eq!(pages(1, 1), [Some(1)]);
+        assert_eq!(pages(0, 0), [Some(1)]);
+        assert_eq!(pages(4, 7), (1..=7).map(Some).collect::<Vec<_>>());
+        assert_eq!(pages(1, 10), [Some(1), Some(2), Some(3), None, Some(10)]);
+        assert_eq!(pages(10, 10), [Some(1), None, Some(8), Some(9), Some(10)]);
+        assert_eq!(pages(50, 10), pages(10, 10));
+        let wide = Pagination::new(3, 5, |p| format!("?p={}", p)).window(usize::MAX).pages();
+        assert_eq!(wide, (1..=5).map(Some).collect::<Vec<_>>());
+
+        let first = Element::from(
+            Pagination::new(1, 2, |p| format!("?p={}", p))
+                .labels("«", "»")
+                .list_class("pager")
+                .item_class("page-item")
+                .active_class("is-current")
+                .disabled_class(""),
+        );
+        crate::assert_html_eq!(first, concat!(
+            r#"<nav aria-label="pagination"><ul class="pager">"#,
+            r#"<li class="page-item"><span aria-disabled="true">«</span></li>"#,
+            r#"<li class="page-item is-current"><a href="?p=1" aria-current="page">1</a></li>"#,
+            r#"<li class="page-item"><a href="?p=2">2</a></li>"#,
+            r#"<li class="page-item"><a href="?p=2" rel="next">»</a></li>"#,
+            "</ul></nav>",
+        ));
+    }
+
+    #[test]
+    fn test_breadcrumbs_microdata() {
+        let crumbs = Element::from(Breadcrumbs::from(&[("Home", "/"), ("<Post>", "/p/1")][..]).microdata(true));
+        crate::assert_html_eq!(crumbs, concat!(
+            r#"<nav aria-label="breadcrumb"><ol class="breadcrumb" itemscope="" itemtype="https://schema.org/BreadcrumbList">"#,
+            r#"<li itemprop="itemListElement" itemscope="" itemtype="https://schema.org/ListItem">"#,
+            r#"<a href="/" itemprop="item"><span itemprop="name">Home</span></a><meta itemprop="position" content="1"></li>"#,
+            r#"<li itemprop="itemListElement" itemscope="" itemtype="https://schema.org/ListItem" aria-current="page">"#,
+            r#"<span itemprop="name">&lt;Post&gt;</span><meta itemprop="position" content="2"></li>"#,
+            "</ol></nav>",
+        ));
+    }
+}
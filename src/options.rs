@@ -0,0 +1,125 @@
+//! 渲染选项
+
+use alloc::string::String;
+
+use crate::Element;
+use crate::escape::{Escaper, EscaperRef};
+
+/// 渲染选项，用于`Element::render_with`
+///
+/// ```
+/// # use htmlbuilder::{Element, RenderOptions};
+/// let a = Element::new("a", "").attrs(&[("title", "t"), ("href", "/"), ("class", "c")]);
+/// let opts = RenderOptions::new().sort_attrs(true);
+/// assert_eq!(a.render_with("", &opts), r#"<a class="c" href="/" title="t"></a>"#);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub(crate) sort_attrs: bool,
+    pub(crate) max_line_width: Option<usize>,
+    pub(crate) xml: bool,
+    pub(crate) escaper: Option<EscaperRef>,
+    #[cfg(feature = "minify")]
+    pub(crate) minify_assets: bool,
+}
+
+impl RenderOptions {
+    /// 默认选项，与`render`输出相同
+    pub const fn new() -> Self {
+        Self {
+            sort_attrs: false,
+            max_line_width: None,
+            xml: false,
+            escaper: None,
+            #[cfg(feature = "minify")]
+            minify_assets: false,
+        }
+    }
+
+    /// 按属性名排序输出属性，便于比较和校验
+    pub const fn sort_attrs(mut self, sort: bool) -> Self {
+        self.sort_attrs = sort;
+        self
+    }
+
+    /// 开始标签所在行超过`width`个字符时，将属性分行对齐输出
+    ///
+    /// ```
+    /// # use htmlbuilder::{Element, RenderOptions};
+    /// let label = Element::new("label", "Search: ")
+    ///     .add_with(Element::new("input", "").attrs(&[("type", "search"), ("name", "q")]).onetag(true));
+    /// let opts = RenderOptions::new().sort_attrs(true).max_line_width(30);
+    /// assert_eq!(label.render_with("", &opts), r#"<label>Search: <input name="q"
+    ///                       type="search"></label>"#);
+    /// ```
+    pub const fn max_line_width(mut self, width: usize) -> Self {
+        self.max_line_width = Some(width);
+        self
+    }
+
+    /// XML输出：没有内容和子元素的元素以及单标签输出为`<tag/>`
+    ///
+    /// ```
+    /// # use htmlbuilder::{Element, RenderOptions};
+    /// let svg = Element::new("svg", "").add_with(Element::new("circle", "").attrs(&[("r", "4")]));
+    /// assert_eq!(svg.render_with("", &RenderOptions::new().xml(true)), r#"<svg><circle r="4"/></svg>"#);
+    /// ```
+    pub const fn xml(mut self, xml: bool) -> Self {
+        self.xml = xml;
+        self
+    }
+
+    /// 使用其他转义规则输出内容和属性值，见`escape`模块
+    pub const fn escaper(mut self, escaper: &'static dyn Escaper) -> Self {
+        self.escaper = Some(EscaperRef(escaper));
+        self
+    }
+
+    /// 压缩`<style>`和内联`<script>`的内容：删除注释和多余的空白，需要`minify`特性
+    ///
+    /// ```
+    /// # use htmlbuilder::{Element, RenderOptions};
+    /// let style = Element::style_sheet("/* 正文 */\np {\n  color: red;\n}\n");
+    /// assert_eq!(style.render_with("", &RenderOptions::new().minify_assets(true)), "<style>p{color:red}</style>");
+    /// ```
+    #[cfg(feature = "minify")]
+    pub const fn minify_assets(mut self, minify: bool) -> Self {
+        self.minify_assets = minify;
+        self
+    }
+
+    /// 是否与`render`输出相同，此时可以使用渲染缓存
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::new()
+    }
+}
+
+impl Element {
+    /// 按选项渲染
+    pub fn render_with(&self, split_s: &str, opts: &RenderOptions) -> String {
+        let mut htmltext = String::new();
+        self.render_pass(split_s, opts, &mut htmltext);
+        htmltext
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_attrs() {
+        let div = Element::new("div", "")
+            .attrs(&[("id", "d"), ("data-x", "1"), ("aria-label", "a & b")])
+            .add_with(Element::new("input", "").attrs(&[("type", "text"), ("name", "q")]).onetag(true));
+        div.cache_render(true);
+        let opts = RenderOptions::new().sort_attrs(true);
+        let html = r#"<div aria-label="a &amp; b" data-x="1" id="d"><input name="q" type="text"></div>"#;
+        assert_eq!(div.render_with("", &opts), html);
+        // 默认选项使用缓存，与render相同
+        div.render("");
+        assert_eq!(div.render_with("", &opts), html);
+        assert_eq!(div.render_with("", &RenderOptions::new()), div.render(""));
+    }
+}
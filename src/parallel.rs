@@ -0,0 +1,160 @@
+//! 并行渲染
+//!
+//! `Element`基于`Rc`，不能跨线程使用。先在当前线程把元素树复制为快照，只保存标签名、已转义的属性和内容，
+//! 常见标签名、属性名和`new_static`的静态内容不复制；开始标签、结束标签的拼接和输出在rayon的并行阶段完成。
+//!
+//! 注意：内容和属性在设置时已经转义，渲染本身只是遍历和复制字符串，而复制快照同样要遍历整棵树、复制全部字符串，
+//! 且只能串行进行，开销与`render`相当。因此`render_par`通常**不比**`render`快：
+//! 单核时约慢一倍，多核时也只能接近而不能超过`render`。
+//! 可以用`cargo bench --features rayon --bench render_par`在目标机器上比较
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use rayon::prelude::*;
+
+use crate::Element;
+use crate::name::TagName;
+use crate::scoped::ScopedStyle;
+
+/// 可以跨线程的元素快照，属性值和内容已转义
+enum Node {
+    /// 已渲染的html：文本节点、渲染缓存和作用域样式
+    Html(Cow<'static, str>),
+    Element {
+        tag: Cow<'static, str>,
+        attrs: Vec<(Cow<'static, str>, String)>,
+        content: Cow<'static, str>,
+        onetag: bool,
+        children: Vec<Node>,
+    },
+}
+
+fn tag_cow(tag: &TagName) -> Cow<'static, str> {
+    match tag {
+        TagName::Static(name) => Cow::Borrowed(name),
+        TagName::Shared(name) => Cow::Owned(String::from(&**name)),
+    }
+}
+
+impl Node {
+    /// 与`Element::render`的结构一致
+    fn snapshot(elem: &Element, split_s: &str, scoped: &ScopedStyle) -> Self {
+        let inner = elem.inner.borrow();
+        if let Some(html) = inner.cached(split_s) {
+            return Self::Html(Cow::Owned(String::from(&*html)));
+        }
+        if inner.tag.is_empty() {
+            return Self::Html(inner.content.clone());
+        }
+
+        let mut children: Vec<Node> = inner.children.iter().map(|child| Self::snapshot(child, split_s, scoped)).collect();
+        if let Some(style) = scoped.for_tag(&inner.tag) {
+            children.push(Self::Html(Cow::Owned(style.render(split_s))));
+        }
+        Self::Element {
+            tag: tag_cow(&inner.tag),
+            // 保持与`render`相同的属性顺序
+            attrs: inner.kws.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            content: inner.content.clone(),
+            onetag: inner.onetag,
+            children,
+        }
+    }
+
+    fn render_into(&self, split_s: &str, out: &mut String) {
+        let (tag, attrs, content, onetag, children) = match self {
+            Self::Html(html) => return out.push_str(html),
+            Self::Element { tag, attrs, content, onetag, children } => (tag, attrs, content, onetag, children),
+        };
+        out.push('<');
+        out.push_str(tag);
+        for (k, v) in attrs {
+            out.push(' ');
+            out.push_str(k);
+            out.push_str("=\"");
+            out.push_str(v);
+            out.push('"');
+        }
+        out.push('>');
+        out.push_str(content);
+        for child in children {
+            out.push_str(split_s);
+            child.render_into(split_s, out);
+        }
+        if *onetag {
+            out.push_str(split_s);
+        } else {
+            if !children.is_empty() {
+                out.push_str(split_s);
+            }
+            out.push_str("</");
+            out.push_str(tag);
+            out.push('>');
+        }
+    }
+}
+
+impl Element {
+    /// 并行渲染各个顶层子元素并按顺序拼接，结果与`render(split_s)`相同。需要`rayon`特性
+    ///
+    /// 复制快照是串行的，通常不比`render`快，见模块说明
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let body = Element::new("body", "")
+    ///     .add_with(Element::ul_from(0..1000))
+    ///     .add_with(Element::ol_from(0..1000));
+    /// assert_eq!(body.render_par("\n"), body.render("\n"));
+    /// ```
+    pub fn render_par(&self, split_s: &str) -> String {
+        let root = Node::snapshot(self, split_s, &ScopedStyle::new(self));
+        let Node::Element { children, .. } = &root else {
+            let mut out = String::new();
+            root.render_into(split_s, &mut out);
+            return out;
+        };
+        let parts: Vec<String> = children
+            .par_iter()
+            .map(|child| {
+                let mut out = String::new();
+                child.render_into(split_s, &mut out);
+                out
+            })
+            .collect();
+
+        // 根元素本身按相同规则输出，子树替换为并行渲染的结果
+        let Node::Element { tag, attrs, content, onetag, .. } = root else { unreachable!() };
+        let children = parts.into_iter().map(|part| Node::Html(Cow::Owned(part))).collect();
+        let mut out = String::new();
+        Node::Element { tag, attrs, content, onetag, children }.render_into(split_s, &mut out);
+        out
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::Stylesheet;
+
+    #[test]
+    fn test_render_par() {
+        let head = Element::new("head", "").add_with(Element::new("title", "t"));
+        let body = Element::new("body", "x")
+            .add_with(Element::new("br", "").onetag(true))
+            .add_with(Element::new("", "text"))
+            .add_with(Element::ul_from(["a", "b"]));
+        body.scoped_style(Stylesheet::new().rule("&", &[("color", "red")]));
+        let html = Element::new("html", "").add_with(head).add_with(body);
+        for split_s in ["", "\n"] {
+            assert_eq!(html.render_par(split_s), html.render(split_s));
+        }
+        assert_eq!(Element::new("p", "").render_par(""), "<p></p>");
+
+        html.cache_render(true);
+        html.render("");
+        assert_eq!(html.render_par(""), html.render(""));
+    }
+}
@@ -0,0 +1,347 @@
+//! html解析
+//!
+//! 宽松的html片段解析器，将html文本转为元素树。
+//! 文本转为空标签名的文本节点，注释和`<!DOCTYPE>`保留为原文本节点
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::{Element, is_void_tag};
+
+/// 内容为原始文本的元素
+const RAW_TEXT_TAGS: &[&str] = &["script", "style"];
+
+/// 内容只解码字符引用、不含子元素的元素
+const RCDATA_TAGS: &[&str] = &["textarea", "title"];
+
+/// 开始时会自动闭合`<p>`的元素
+const CLOSES_P: &[&str] = &[
+    "address", "article", "aside", "blockquote", "details", "div", "dl", "fieldset", "figcaption",
+    "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6", "header", "hgroup", "hr",
+    "main", "menu", "nav", "ol", "p", "pre", "section", "table", "ul",
+];
+
+/// 解码字符引用
+pub(crate) fn decode_entities(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(pos) = rest.find('&') {
+        result.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let decoded = rest[1..].find(';').filter(|&end| end <= 32).and_then(|end| {
+            let name = &rest[1..end + 1];
+            let c = match name {
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "nbsp" => Some('\u{a0}'),
+                _ => {
+                    let code = if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else if let Some(dec) = name.strip_prefix('#') {
+                        dec.parse().ok()
+                    } else {
+                        None
+                    };
+                    code.and_then(char::from_u32)
+                }
+            };
+            c.map(|c| (c, end + 2))
+        });
+        match decoded {
+            Some((c, len)) => {
+                result.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn raw_node(text: &str) -> Element {
+    let elem = Element::new("", "").pre(true);
+    elem.configcnt(text);
+    elem
+}
+
+/// 属性过滤，参数为`(标签名, 属性名)`
+type AttrFilter<'a> = &'a dyn Fn(&str, &str) -> bool;
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    roots: Vec<Element>,
+    stack: Vec<Element>,
+    /// 按`(标签名, 属性名)`过滤属性，返回`false`的属性不会被保存
+    attr_filter: Option<AttrFilter<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn push_node(&mut self, elem: Element) {
+        match self.stack.last() {
+            Some(parent) => {
+                parent.add(elem);
+            }
+            None => self.roots.push(elem),
+        }
+    }
+
+    fn top_tag(&self) -> Option<String> {
+        self.stack.last().map(|e| e.inner.borrow().tag.to_string())
+    }
+
+    /// 新元素开始前，自动闭合不能包含它的元素
+    fn close_implied(&mut self, tag: &str) {
+        while let Some(top) = self.top_tag() {
+            let close = match tag {
+                "li" => top == "li",
+                "dt" | "dd" => top == "dt" || top == "dd",
+                "tr" => matches!(top.as_str(), "tr" | "td" | "th"),
+                "td" | "th" => top == "td" || top == "th",
+                "option" => top == "option",
+                _ => top == "p" && CLOSES_P.contains(&tag),
+            };
+            if !close {
+                break;
+            }
+            self.stack.pop();
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.src.len() - trimmed.len();
+    }
+
+    fn name(&mut self) -> String {
+        let len = self.rest()
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '=' | '"' | '\'' | '<'))
+            .unwrap_or(self.rest().len());
+        let name = self.rest()[..len].to_ascii_lowercase();
+        self.pos += len;
+        name
+    }
+
+    fn attr_value(&mut self) -> String {
+        let rest = self.rest();
+        let value = match rest.chars().next() {
+            Some(q @ ('"' | '\'')) => {
+                let end = rest[1..].find(q).map_or(rest.len(), |i| i + 1);
+                self.pos += (end + 1).min(rest.len());
+                &rest[1..end]
+            }
+            _ => {
+                let end = rest.find(|c: char| c.is_whitespace() || c == '>').unwrap_or(rest.len());
+                self.pos += end;
+                &rest[..end]
+            }
+        };
+        decode_entities(value)
+    }
+
+    /// 解析开始标签，`<`已被消耗
+    fn start_tag(&mut self) {
+        let tag = self.name();
+        let elem = Element::new(tag.clone(), "");
+        let mut self_closing = false;
+        loop {
+            self.skip_ws();
+            match self.rest().chars().next() {
+                None => break,
+                Some('>') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('/') => {
+                    self.pos += 1;
+                    self_closing = true;
+                }
+                Some(_) => {
+                    let name = self.name();
+                    if name.is_empty() {
+                        // 无法识别的字符
+                        self.pos += self.rest().chars().next().map_or(0, char::len_utf8);
+                        continue;
+                    }
+                    self.skip_ws();
+                    let value = if self.rest().starts_with('=') {
+                        self.pos += 1;
+                        self.skip_ws();
+                        self.attr_value()
+                    } else {
+                        String::new()
+                    };
+                    let has_attr = elem.inner.borrow().kws.contains_key(name.as_str());
+                    let allowed = self.attr_filter.is_none_or(|filter| filter(&tag, &name));
+                    if !has_attr && allowed {
+                        elem.set_attr(name, value);
+                    }
+                }
+            }
+        }
+
+        self.close_implied(&tag);
+        if is_void_tag(&tag) {
+            self.push_node(elem.onetag(true));
+            return;
+        }
+        self.push_node(elem.clone());
+        if self_closing {
+            return;
+        }
+
+        if RAW_TEXT_TAGS.contains(&tag.as_str()) || RCDATA_TAGS.contains(&tag.as_str()) {
+            let close = format!("</{}", tag);
+            let end = self.rest().to_ascii_lowercase().find(&close).unwrap_or(self.rest().len());
+            let text = &self.rest()[..end];
+            if RAW_TEXT_TAGS.contains(&tag.as_str()) {
+                elem.inner.borrow_mut().rawtext = true;
+                elem.configcnt(text);
+            } else {
+                elem.configcnt(decode_entities(text));
+            }
+            self.pos += end;
+            // 跳过闭合标签
+            if let Some(gt) = self.rest().find('>') {
+                self.pos += gt + 1;
+            }
+            return;
+        }
+        self.stack.push(elem);
+    }
+
+    /// 解析闭合标签，`</`已被消耗
+    fn end_tag(&mut self) {
+        let tag = self.name();
+        if let Some(gt) = self.rest().find('>') {
+            self.pos += gt + 1;
+        } else {
+            self.pos = self.src.len();
+        }
+        let open = self.stack.iter().rposition(|e| e.inner.borrow().tag == tag);
+        if let Some(index) = open {
+            self.stack.truncate(index);
+        }
+    }
+
+    fn parse(mut self) -> Vec<Element> {
+        while self.pos < self.src.len() {
+            let rest = self.rest();
+            let Some(lt) = rest.find('<') else {
+                let text = decode_entities(rest);
+                self.push_node(Element::new("", text));
+                break;
+            };
+            if lt > 0 {
+                let text = decode_entities(&rest[..lt]);
+                self.push_node(Element::new("", text));
+                self.pos += lt;
+                continue;
+            }
+
+            let rest = self.rest();
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                let end = comment.find("-->").map_or(rest.len(), |i| i + 7);
+                self.push_node(raw_node(&rest[..end]));
+                self.pos += end;
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+                self.push_node(raw_node(&rest[..end]));
+                self.pos += end;
+            } else if rest.starts_with("</") && rest[2..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+                self.pos += 2;
+                self.end_tag();
+            } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+                self.pos += 1;
+                self.start_tag();
+            } else {
+                // 不是标签的`<`
+                self.push_node(Element::new("", "<"));
+                self.pos += 1;
+            }
+        }
+        self.roots
+    }
+}
+
+/// 解析html片段，返回顶层节点
+pub(crate) fn parse_fragment(html: &str) -> Vec<Element> {
+    Parser { src: html, pos: 0, roots: Vec::new(), stack: Vec::new(), attr_filter: None }.parse()
+}
+
+/// 解析html片段，只保存`filter(标签名, 属性名)`为真的属性，用于不可信的输入
+pub(crate) fn parse_fragment_filtered(html: &str, filter: AttrFilter<'_>) -> Vec<Element> {
+    Parser { src: html, pos: 0, roots: Vec::new(), stack: Vec::new(), attr_filter: Some(filter) }.parse()
+}
+
+impl Element {
+    /// 解析html片段，返回顶层节点
+    ///
+    /// 解析是宽松的：未闭合的元素在父元素结束时闭合，多余的结束标签被忽略
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let nodes = Element::parse_html("<!DOCTYPE html><p class=a>x<br>y");
+    /// assert_eq!(nodes.len(), 2);
+    /// assert_eq!(nodes[1].render(""), "<p class=\"a\">x<br>y</p>");
+    /// ```
+    pub fn parse_html(html: &str) -> Vec<Element> {
+        parse_fragment(html)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::Cow;
+
+    use super::*;
+
+    fn render(nodes: &[Element]) -> String {
+        nodes.iter().map(|n| n.render("")).collect()
+    }
+
+    #[test]
+    fn test_parse_fragment() {
+        let nodes = parse_fragment("<!-- c --><p class='a' hidden>x &amp; y&#33;<br/>z</p>tail");
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[0].render(""), "<!-- c -->");
+        let p = &nodes[1];
+        assert_eq!(p.inner.borrow().kws.get("class").map(String::as_str), Some("a"));
+        assert_eq!(p.inner.borrow().kws.get("hidden").map(String::as_str), Some(""));
+        assert_eq!(p.children().len(), 3);
+        assert_eq!(p.children()[0].render(""), "x &amp; y!");
+
+        // 隐式闭合
+        let nodes = parse_fragment("<ul><li>a<li>b</ul><p>1<div>2</div>");
+        assert_eq!(render(&nodes), "<ul><li>a</li><li>b</li></ul><p>1</p><div>2</div>");
+
+        let nodes = parse_fragment("<script>if (a < b) {}</script><title>a &lt; b</title>");
+        assert_eq!(render(&nodes), "<script>if (a < b) {}</script><title>a &lt; b</title>");
+
+        assert_eq!(decode_entities("&#x41;&unknown;&"), "A&unknown;&");
+    }
+
+    #[test]
+    fn test_parse_attr_names() {
+        // 常见属性名指向静态字符串，其余属性名由元素持有，随元素释放
+        let nodes = parse_fragment("<a href='/' x-unknown-1=1 X-Unknown-1=2>a</a>");
+        let inner = nodes[0].inner.borrow();
+        let names: Vec<_> = inner.kws.keys().map(|k| (k.as_ref(), matches!(k, Cow::Borrowed(_)))).collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&("href", true)));
+        assert!(names.contains(&("x-unknown-1", false)));
+    }
+}
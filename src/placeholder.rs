@@ -0,0 +1,125 @@
+//! 占位符
+//!
+//! 元素树只构建一次，每次请求用不同的数据填充占位符生成新的树
+
+use alloc::rc::Rc;
+use alloc::string::String;
+
+use crate::{Element, un_escape_ascii};
+
+/// 占位符标记使用的属性，占位符本身是不输出内容的文本节点
+const PLACEHOLDER_ATTR: &str = "data-placeholder";
+
+/// 填充占位符的值
+pub enum FillValue {
+    /// 文本，会被转义
+    Text(String),
+    /// 元素，填充时使用拷贝
+    Element(Element),
+}
+
+impl From<&str> for FillValue {
+    fn from(text: &str) -> Self {
+        FillValue::Text(text.into())
+    }
+}
+
+impl From<String> for FillValue {
+    fn from(text: String) -> Self {
+        FillValue::Text(text)
+    }
+}
+
+impl From<Element> for FillValue {
+    fn from(elem: Element) -> Self {
+        FillValue::Element(elem)
+    }
+}
+
+fn placeholder_of(elem: &Element) -> Option<String> {
+    let inner = elem.inner.borrow();
+    if !inner.tag.is_empty() {
+        return None;
+    }
+    inner.kws.get(PLACEHOLDER_ATTR).map(|name| un_escape_ascii(name))
+}
+
+fn fill_node(elem: &Element, values: &[(&str, FillValue)]) -> Option<Element> {
+    let name = placeholder_of(elem)?;
+    let (_, value) = values.iter().find(|(n, _)| *n == name)?;
+    Some(match value {
+        FillValue::Text(text) => Element::new("", text.as_str()),
+        FillValue::Element(e) => e.deep_clone(),
+    })
+}
+
+/// 替换子树中的占位符，只用于新拷贝的树，不需要清除渲染缓存
+fn fill_children(elem: &Element, values: &[(&str, FillValue)]) {
+    let mut inner = elem.inner.borrow_mut();
+    for child in inner.children.iter_mut() {
+        match fill_node(child, values) {
+            Some(node) => {
+                node.inner.borrow_mut().parent = Some(Rc::downgrade(&elem.inner));
+                child.inner.borrow_mut().parent = None;
+                *child = node;
+            }
+            None => fill_children(child, values),
+        }
+    }
+}
+
+impl Element {
+    /// 创建占位符，`fill`时替换为对应的值，未填充时不输出内容
+    pub fn placeholder(name: &str) -> Element {
+        let elem = Element::new("", "");
+        elem.set_attr(PLACEHOLDER_ATTR, name);
+        elem
+    }
+
+    /// 用给定的值填充占位符，返回新的元素树，原树不变
+    ///
+    /// 文本值会被转义，元素值使用拷贝，没有对应值的占位符保持不变
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let greeting = Element::new("p", "Hello, ").add_with(Element::placeholder("user"));
+    /// let page = greeting.fill(&[("user", "<Bob>".into())]);
+    /// assert_eq!(page.render(""), "<p>Hello, &lt;Bob&gt;</p>");
+    ///
+    /// let page = greeting.fill(&[("user", Element::new("b", "Alice").into())]);
+    /// assert_eq!(page.render(""), "<p>Hello, <b>Alice</b></p>");
+    /// assert_eq!(greeting.render(""), "<p>Hello, </p>");
+    /// ```
+    pub fn fill(&self, values: &[(&str, FillValue)]) -> Element {
+        if let Some(node) = fill_node(self, values) {
+            return node;
+        }
+        let copy = self.deep_clone();
+        fill_children(&copy, values);
+        copy
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill() {
+        let card = Element::new("div", "")
+            .add_with(Element::new("h2", "").add_with(Element::placeholder("title")))
+            .add_with(Element::placeholder("body"))
+            .add_with(Element::placeholder("missing"));
+        let page = card.fill(&[
+            ("title", String::from("a & b").into()),
+            ("body", Element::new("p", "text").into()),
+        ]);
+        assert_eq!(page.render(""), "<div><h2>a &amp; b</h2><p>text</p></div>");
+        assert_eq!(page.children()[1].parent(), Some(page.clone()));
+        assert_eq!(placeholder_of(&page.children()[2]), Some("missing".into()));
+
+        let root = Element::placeholder("root");
+        assert_eq!(root.fill(&[("root", "x".into())]).render(""), "x");
+    }
+}
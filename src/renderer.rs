@@ -0,0 +1,100 @@
+//! 可重复使用的渲染器
+
+use alloc::string::String;
+
+use crate::{Document, Element, RenderOptions};
+
+/// 持有输出缓冲区和渲染选项的渲染器
+///
+/// 每次渲染清空并复用同一个缓冲区，适合在请求处理中反复渲染较大的页面，
+/// 缓冲区增长到所需大小后不再重新分配
+///
+/// ```
+/// # use htmlbuilder::{Element, Renderer};
+/// let mut renderer = Renderer::new("");
+/// for i in 0..3 {
+///     let page = Element::new("p", i.to_string());
+///     assert_eq!(renderer.render(&page), format!("<p>{}</p>", i));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Renderer {
+    buf: String,
+    split_s: String,
+    opts: RenderOptions,
+}
+
+impl Renderer {
+    /// 以`split_s`分隔元素，使用默认选项
+    pub fn new(split_s: impl Into<String>) -> Self {
+        Self { buf: String::new(), split_s: split_s.into(), opts: RenderOptions::new() }
+    }
+
+    /// 预先分配`capacity`字节的缓冲区
+    pub fn with_capacity(split_s: impl Into<String>, capacity: usize) -> Self {
+        Self { buf: String::with_capacity(capacity), ..Self::new(split_s) }
+    }
+
+    /// 设置渲染选项，默认选项可以使用渲染缓存
+    pub fn options(mut self, opts: RenderOptions) -> Self {
+        self.opts = opts;
+        self
+    }
+
+    /// 渲染元素，返回的字符串在下一次渲染前有效
+    pub fn render(&mut self, elem: &Element) -> &str {
+        self.buf.clear();
+        elem.render_pass(&self.split_s, &self.opts, &mut self.buf);
+        &self.buf
+    }
+
+    /// 渲染整个文档，包括`<!DOCTYPE html>`
+    pub fn render_document(&mut self, doc: &Document) -> &str {
+        self.buf.clear();
+        self.buf.push_str("<!DOCTYPE html>");
+        self.buf.push_str(&self.split_s);
+        doc.html().render_pass(&self.split_s, &self.opts, &mut self.buf);
+        &self.buf
+    }
+
+    /// 缓冲区当前的容量
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// 缓冲区容量超过`max`时缩小，避免偶尔渲染的超大页面一直占用内存
+    pub fn shrink_to(&mut self, max: usize) {
+        self.buf.clear();
+        self.buf.shrink_to(max);
+    }
+
+    /// 取出最后一次渲染的结果，渲染器之后重新分配缓冲区
+    pub fn into_string(self) -> String {
+        self.buf
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renderer_reuses_buffer() {
+        let big = Element::new("ul", "").with_children((0..100).map(|i| Element::new("li", "x".repeat(i))));
+        let mut renderer = Renderer::new("\n");
+        assert_eq!(renderer.render(&big), big.render("\n"));
+        let capacity = renderer.capacity();
+        assert_eq!(renderer.render(&Element::new("p", "a & b")), "<p>a &amp; b</p>");
+        assert_eq!(renderer.capacity(), capacity);
+
+        let doc = Document::html5();
+        assert_eq!(renderer.render_document(&doc), doc.render("\n"));
+
+        let mut sorted = Renderer::new("").options(RenderOptions::new().sort_attrs(true));
+        let a = Element::new("a", "").attrs(&[("title", "t"), ("href", "/")]);
+        assert_eq!(sorted.render(&a), r#"<a href="/" title="t"></a>"#);
+        sorted.shrink_to(0);
+        assert_eq!(sorted.into_string(), "");
+    }
+}
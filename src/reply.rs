@@ -0,0 +1,44 @@
+//! warp集成
+//!
+//! `warp::Reply`要求`Send`，而`Element`不是`Send`，因此先渲染为html再返回
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::reply::html_tree;
+//! use warp::Filter;
+//!
+//! let route = warp::path("hello").map(|| {
+//!     let page = Element::new("p", "hello");
+//!     html_tree(&page)
+//! });
+//! ```
+
+use alloc::string::String;
+
+use crate::{Document, Element};
+
+/// 渲染元素树，返回`text/html; charset=utf-8`响应
+pub fn html_tree(elem: &Element) -> warp::reply::Html<String> {
+    warp::reply::html(elem.render(""))
+}
+
+/// 渲染整个文档（包括`<!DOCTYPE html>`），返回`text/html; charset=utf-8`响应
+pub fn html_document(doc: &Document) -> warp::reply::Html<String> {
+    warp::reply::html(doc.render(""))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use warp::Reply;
+
+    #[test]
+    fn test_html_tree() {
+        let response = html_tree(&Element::new("p", "hi")).into_response();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "text/html; charset=utf-8");
+        let response = html_document(&Document::html5()).into_response();
+        assert_eq!(response.status(), 200);
+    }
+}
@@ -0,0 +1,97 @@
+//! web框架集成
+//!
+//! 处理函数可以直接返回`Element`，渲染为`text/html; charset=utf-8`响应
+
+use crate::{Document, Element};
+
+#[cfg(feature = "actix")]
+impl actix_web::Responder for Element {
+    type Body = actix_web::body::BoxBody;
+
+    fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
+        actix_web::HttpResponse::Ok()
+            .content_type(actix_web::http::header::ContentType::html())
+            .body(self.render(""))
+    }
+}
+
+#[cfg(feature = "actix")]
+impl actix_web::Responder for Document {
+    type Body = actix_web::body::BoxBody;
+
+    fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
+        actix_web::HttpResponse::Ok()
+            .content_type(actix_web::http::header::ContentType::html())
+            .body(self.render(""))
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for Element {
+    fn into_response(self) -> axum::response::Response {
+        axum::response::Html(self.render("")).into_response()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for Document {
+    fn into_response(self) -> axum::response::Response {
+        axum::response::Html(self.render("")).into_response()
+    }
+}
+
+#[cfg(feature = "rocket")]
+impl<'r> rocket::response::Responder<'r, 'static> for Element {
+    fn respond_to(self, req: &'r rocket::Request<'_>) -> rocket::response::Result<'static> {
+        rocket::response::content::RawHtml(self.render("")).respond_to(req)
+    }
+}
+
+#[cfg(feature = "rocket")]
+impl<'r> rocket::response::Responder<'r, 'static> for Document {
+    fn respond_to(self, req: &'r rocket::Request<'_>) -> rocket::response::Result<'static> {
+        rocket::response::content::RawHtml(self.render("")).respond_to(req)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "actix")]
+    #[test]
+    fn test_actix() {
+        use actix_web::Responder;
+
+        let req = actix_web::test::TestRequest::default().to_http_request();
+        let response = Element::new("p", "hi").respond_to(&req);
+        assert_eq!(response.status(), 200);
+        let content_type = response.headers().get("content-type").unwrap();
+        assert_eq!(content_type, "text/html; charset=utf-8");
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn test_axum() {
+        use axum::response::IntoResponse;
+
+        let response = Element::new("p", "hi").into_response();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "text/html; charset=utf-8");
+        let response = Document::html5().into_response();
+        assert_eq!(response.headers()["content-type"], "text/html; charset=utf-8");
+    }
+
+    #[cfg(feature = "rocket")]
+    #[test]
+    fn test_rocket() {
+        use rocket::response::Responder;
+
+        let client = rocket::local::blocking::Client::untracked(rocket::build()).unwrap();
+        let request = client.get("/");
+        let response = Element::new("p", "hi").respond_to(request.inner()).unwrap();
+        assert_eq!(response.status(), rocket::http::Status::Ok);
+        assert_eq!(response.content_type(), Some(rocket::http::ContentType::HTML));
+    }
+}
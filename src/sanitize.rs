@@ -0,0 +1,413 @@
+//! 不可信html的清理
+//!
+//! 按允许列表保留标签和属性，其余标签去掉（保留其中的文本），
+//! `script`、`style`等元素连同内容一起删除，事件属性和不安全的url总是会被删除
+//!
+//! ```
+//! use htmlbuilder::sanitize::{Policy, sanitize_html};
+//!
+//! let clean = sanitize_html(
+//!     r#"<p onclick="x()">Hi <a href="javascript:alert(1)">there</a><script>bad()</script></p>"#,
+//!     &Policy::default(),
+//! );
+//! assert_eq!(clean.render(""), "<div><p>Hi <a>there</a></p></div>");
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt;
+
+use crate::parser::parse_fragment_filtered;
+use crate::Element;
+
+/// 不被允许时连同内容一起删除的元素
+const STRIP_CONTENT: &[&str] = &[
+    "script", "style", "iframe", "object", "embed", "template", "noscript", "textarea", "select",
+    "title", "head", "svg", "math", "frame", "frameset", "applet",
+];
+
+/// 值为url的属性
+pub(crate) const URL_ATTRS: &[&str] = &[
+    "href", "src", "action", "formaction", "cite", "poster", "background", "longdesc", "data",
+    "xlink:href",
+];
+
+/// 获取url的协议（小写），相对url返回`None`
+///
+/// 与浏览器一致，忽略首尾的空白和控制字符以及中间的制表符和换行
+pub(crate) fn url_scheme(url: &str) -> Option<String> {
+    let url: String = url
+        .trim_matches(|c: char| c.is_ascii_whitespace() || c.is_ascii_control())
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    let end = url.find([':', '/', '?', '#'])?;
+    if !url[end..].starts_with(':') {
+        return None;
+    }
+    let scheme = &url[..end];
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then(|| scheme.to_ascii_lowercase())
+}
+
+/// url协议规则
+///
+/// 相对url总是允许；`UrlPolicy::default()`允许`http`、`https`、`mailto`和`tel`
+///
+/// ```
+/// # use htmlbuilder::Element;
+/// # use htmlbuilder::sanitize::UrlPolicy;
+/// let policy = UrlPolicy::default();
+/// let a = Element::new("a", "link");
+/// assert!(a.set_url("href", "javascript:alert(1)", &policy).is_err());
+/// assert!(a.set_url("href", "/home", &policy).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct UrlPolicy {
+    schemes: BTreeSet<String>,
+    data_images: bool,
+}
+
+impl Default for UrlPolicy {
+    fn default() -> Self {
+        Self::new().allow_schemes(&["http", "https", "mailto", "tel"])
+    }
+}
+
+impl UrlPolicy {
+    /// 只允许相对url
+    pub fn new() -> Self {
+        Self { schemes: BTreeSet::new(), data_images: false }
+    }
+
+    /// 允许的协议
+    pub fn allow_schemes(mut self, schemes: &[&str]) -> Self {
+        self.schemes.extend(schemes.iter().map(|s| s.to_ascii_lowercase()));
+        self
+    }
+
+    /// 是否允许`data:image/...`格式的图片（不含svg）
+    pub fn allow_data_images(mut self, allow: bool) -> Self {
+        self.data_images = allow;
+        self
+    }
+
+    /// url是否被允许
+    pub fn is_allowed(&self, url: &str) -> bool {
+        match url_scheme(url) {
+            None => true,
+            Some(scheme) if self.schemes.contains(&scheme) => true,
+            Some(scheme) if scheme == "data" && self.data_images => {
+                let data = url.split_once(':').map_or("", |(_, data)| data);
+                let mime = data.split([';', ',']).next().unwrap_or("").to_ascii_lowercase();
+                mime.starts_with("image/") && !mime.starts_with("image/svg")
+            }
+            Some(_) => false,
+        }
+    }
+}
+
+/// 不被允许的url
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsafeUrl {
+    /// 元素的路径
+    pub path: Vec<usize>,
+    /// 属性名
+    pub attr: String,
+    /// 属性值
+    pub url: String,
+}
+
+impl fmt::Display for UnsafeUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unsafe url in `{}` at {:?}: {}", self.attr, self.path, self.url)
+    }
+}
+
+impl core::error::Error for UnsafeUrl {}
+
+fn strip_urls(elem: &Element, policy: &UrlPolicy, path: &mut Vec<usize>, removed: &mut Vec<UnsafeUrl>) {
+    {
+        let mut inner = elem.inner.borrow_mut();
+        let pre = inner.pre;
+        let mut names: Vec<Cow<'static, str>> = inner.kws.keys()
+            .filter(|k| URL_ATTRS.contains(&k.to_ascii_lowercase().as_str()))
+            .cloned()
+            .collect();
+        names.sort();
+        for name in names {
+            let value = &inner.kws[&name];
+            let url = if pre { value.clone() } else { crate::un_escape_ascii(value) };
+            if !policy.is_allowed(&url) {
+                inner.kws.remove(&name);
+                inner.invalidate();
+                removed.push(UnsafeUrl { path: path.clone(), attr: name.to_string(), url });
+            }
+        }
+    }
+    for (i, child) in elem.children().iter().enumerate() {
+        path.push(i);
+        strip_urls(child, policy, path, removed);
+        path.pop();
+    }
+}
+
+impl Element {
+    /// 设置url属性，url不被允许时返回错误且不修改元素
+    pub fn set_url(&self, name: impl Into<Cow<'static, str>>, url: impl Into<String>, policy: &UrlPolicy) -> Result<(), UnsafeUrl> {
+        let (name, url) = (name.into(), url.into());
+        if !policy.is_allowed(&url) {
+            return Err(UnsafeUrl { path: Vec::new(), attr: name.to_string(), url });
+        }
+        self.set_attr(name, url);
+        Ok(())
+    }
+
+    /// 删除树中不被允许的url属性（`href`、`src`、`action`等），返回被删除的属性
+    pub fn strip_unsafe_urls(&self, policy: &UrlPolicy) -> Vec<UnsafeUrl> {
+        let mut removed = Vec::new();
+        strip_urls(self, policy, &mut Vec::new(), &mut removed);
+        removed
+    }
+}
+
+/// 清理规则
+///
+/// `Policy::default()`允许常见的文本格式、列表、表格、链接和图片
+#[derive(Debug, Clone)]
+pub struct Policy {
+    tags: BTreeSet<String>,
+    // 标签名 -> 属性名，`*`表示所有标签
+    attrs: BTreeMap<String, BTreeSet<String>>,
+    urls: UrlPolicy,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new()
+            .allow_tags(&[
+                "a", "abbr", "b", "blockquote", "br", "caption", "code", "dd", "del", "div", "dl",
+                "dt", "em", "figcaption", "figure", "h1", "h2", "h3", "h4", "h5", "h6", "hr", "i",
+                "img", "ins", "kbd", "li", "mark", "ol", "p", "pre", "q", "s", "small", "span",
+                "strong", "sub", "sup", "table", "tbody", "td", "tfoot", "th", "thead", "tr", "u", "ul",
+            ])
+            .allow_attrs("*", &["title", "lang", "dir"])
+            .allow_attrs("a", &["href"])
+            .allow_attrs("img", &["src", "alt", "width", "height"])
+            .allow_attrs("td", &["colspan", "rowspan"])
+            .allow_attrs("th", &["colspan", "rowspan", "scope"])
+            .allow_attrs("ol", &["start"])
+            .allow_attrs("blockquote", &["cite"])
+            .allow_attrs("q", &["cite"])
+            .allow_url_schemes(&["http", "https", "mailto"])
+    }
+}
+
+impl Policy {
+    /// 空规则，只保留文本
+    pub fn new() -> Self {
+        Self { tags: BTreeSet::new(), attrs: BTreeMap::new(), urls: UrlPolicy::new() }
+    }
+
+    /// 允许标签
+    pub fn allow_tags(mut self, tags: &[&str]) -> Self {
+        self.tags.extend(tags.iter().map(|t| t.to_ascii_lowercase()));
+        self
+    }
+
+    /// 允许标签上的属性，tag为`*`时对所有标签生效
+    ///
+    /// 以`on`开头的事件属性总是会被删除
+    pub fn allow_attrs(mut self, tag: &str, attrs: &[&str]) -> Self {
+        self.attrs
+            .entry(tag.to_ascii_lowercase())
+            .or_default()
+            .extend(attrs.iter().map(|a| a.to_ascii_lowercase()));
+        self
+    }
+
+    /// 允许url属性使用的协议，相对url总是允许
+    pub fn allow_url_schemes(mut self, schemes: &[&str]) -> Self {
+        self.urls = self.urls.allow_schemes(schemes);
+        self
+    }
+
+    /// 设置url规则，替换`allow_url_schemes`的设置
+    pub fn url_policy(mut self, urls: UrlPolicy) -> Self {
+        self.urls = urls;
+        self
+    }
+
+    /// 解析不可信的html，不被允许的属性在解析时直接丢弃
+    fn parse(&self, html: &str) -> Vec<Element> {
+        parse_fragment_filtered(html, &|tag, name| self.allows_attr(tag, name))
+    }
+
+    fn allows_attr(&self, tag: &str, name: &str) -> bool {
+        if name.starts_with("on") {
+            return false;
+        }
+        [tag, "*"].iter().any(|t| self.attrs.get(*t).is_some_and(|a| a.contains(name)))
+    }
+
+    /// 清理单个节点，结果追加到out
+    fn clean(&self, elem: &Element, out: &mut Vec<Element>) {
+        let inner = elem.inner.borrow();
+        let tag = inner.tag.to_ascii_lowercase();
+
+        if tag.is_empty() {
+            if inner.pre {
+                // 原样输出的html重新解析后清理，注释等无法解析的内容被丢弃
+                for node in self.parse(&inner.content) {
+                    let raw = node.inner.borrow().tag.is_empty() && node.inner.borrow().pre;
+                    if !raw {
+                        self.clean(&node, out);
+                    }
+                }
+            } else {
+                out.push(Element::new("", inner.text_content()));
+            }
+            return;
+        }
+
+        if !self.tags.contains(&tag) {
+            if STRIP_CONTENT.contains(&tag.as_str()) || inner.rawtext {
+                return;
+            }
+            // 去掉标签，保留内容
+            let text = inner.text_content();
+            if !text.is_empty() {
+                out.push(Element::new("", text));
+            }
+            for child in &inner.children {
+                self.clean(child, out);
+            }
+            return;
+        }
+
+        let clean = Element::new(tag.clone(), "").onetag(inner.onetag).rawtext(inner.rawtext);
+        clean.configcnt(inner.text_content());
+        for (name, value) in &inner.kws {
+            let name = name.to_ascii_lowercase();
+            if !self.allows_attr(&tag, &name) {
+                continue;
+            }
+            let value = if inner.pre { value.clone() } else { crate::un_escape_ascii(value) };
+            if URL_ATTRS.contains(&name.as_str()) && !self.urls.is_allowed(&value) {
+                continue;
+            }
+            clean.set_attr(name, value);
+        }
+        let mut children = Vec::new();
+        for child in &inner.children {
+            self.clean(child, &mut children);
+        }
+        for child in children {
+            clean.push_child(child);
+        }
+        out.push(clean);
+    }
+}
+
+/// 按规则清理元素树，返回新的元素树，原元素不变
+///
+/// 根元素不被允许时，替换为包含其清理后内容的`<div>`
+pub fn sanitize(elem: &Element, policy: &Policy) -> Element {
+    let mut nodes = Vec::new();
+    policy.clean(elem, &mut nodes);
+    let tag = elem.inner.borrow().tag.to_ascii_lowercase();
+    if policy.tags.contains(&tag) {
+        return nodes.remove(0);
+    }
+    let div = Element::new("div", "");
+    for node in nodes {
+        div.add(node);
+    }
+    div
+}
+
+/// 解析并清理html片段，结果放在一个`<div>`中
+pub fn sanitize_html(html: &str, policy: &Policy) -> Element {
+    let div = Element::new("div", "");
+    for node in policy.parse(html) {
+        let mut nodes = Vec::new();
+        policy.clean(&node, &mut nodes);
+        for node in nodes {
+            div.add(node);
+        }
+    }
+    div
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_url_scheme() {
+        assert_eq!(url_scheme("/a:b"), None);
+        assert_eq!(url_scheme("page?x=a:b"), None);
+        assert_eq!(url_scheme(" JavaScript:alert(1)").as_deref(), Some("javascript"));
+        assert_eq!(url_scheme("java\tscript:x").as_deref(), Some("javascript"));
+        assert_eq!(url_scheme("\u{1}data:text/html,x").as_deref(), Some("data"));
+    }
+
+    #[test]
+    fn test_url_policy() {
+        let policy = UrlPolicy::default().allow_data_images(true);
+        assert!(policy.is_allowed("data:image/png;base64,AA"));
+        assert!(!policy.is_allowed("data:image/svg+xml,<svg/>"));
+        assert!(!policy.is_allowed("data:text/html,x"));
+        assert!(policy.is_allowed("tel:123"));
+
+        let root = Element::new("div", "")
+            .add_with(Element::new("a", "").attrs(&[("href", " javascript:x()"), ("title", "javascript:")]))
+            .add_with(Element::new("form", "").attrs(&[("action", "/post")]));
+        let removed = root.strip_unsafe_urls(&policy);
+        assert_eq!(removed, vec![UnsafeUrl { path: vec![0], attr: "href".to_string(), url: " javascript:x()".to_string() }]);
+        assert_eq!(root.render(""), "<div><a title=\"javascript:\"></a><form action=\"/post\"></form></div>");
+
+        let err = root.set_url("href", "vbscript:x", &policy).unwrap_err();
+        assert_eq!(err.to_string(), "unsafe url in `href` at []: vbscript:x");
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let html = r#"<div class="x"><h1 onmouseover="x()" title="t">T</h1><font>kept</font>
+            <img src="data:image/png;base64,AA" alt="a"><a href="https://ok/">ok</a>
+            <style>p{}</style><!-- c --><iframe src="/x">text</iframe></div>"#;
+        let clean = sanitize_html(html, &Policy::default());
+        crate::assert_html_eq!(clean, r#"<div><div><h1 title="t">T</h1>kept
+            <img alt="a"><a href="https://ok/">ok</a></div></div>"#);
+
+        // 原样输出的html同样被清理
+        let raw = Element::new("", "").pre(true);
+        raw.configcnt("<b>b</b><script>x</script>");
+        let p = Element::new("p", "").add_with(raw);
+        assert_eq!(sanitize(&p, &Policy::default()).render(""), "<p><b>b</b></p>");
+
+        let script = Element::new("script", "x");
+        assert_eq!(sanitize(&script, &Policy::default()).render(""), "<div></div>");
+
+        let policy = Policy::new().allow_tags(&["a"]).allow_attrs("a", &["href", "onclick"]);
+        let a = Element::new("a", "x").attrs(&[("href", "mailto:a@b.c"), ("onclick", "y"), ("id", "z")]);
+        assert_eq!(sanitize(&a, &policy).render(""), "<a>x</a>");
+        let a = Element::new("a", "x").attrs(&[("href", "/rel")]);
+        assert_eq!(sanitize(&a, &policy).render(""), "<a href=\"/rel\">x</a>");
+    }
+
+    #[test]
+    fn test_parse_drops_attrs() {
+        // 不被允许的属性名在解析时就被丢弃，不会保存到元素中
+        let policy = Policy::new().allow_tags(&["a"]).allow_attrs("a", &["href"]);
+        let nodes = policy.parse("<a href='/' junk-1 junk-2=x onclick=y><b junk-3>x</b></a>");
+        assert_eq!(nodes[0].attr_list(), vec![("href".to_string(), "/".to_string())]);
+        assert!(nodes[0].children()[0].attr_list().is_empty());
+    }
+}
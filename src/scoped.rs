@@ -0,0 +1,140 @@
+//! 作用域样式
+//!
+//! 为子树生成基于样式内容哈希的唯一class，规则选择器限定在该class之下，避免组件间样式冲突
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::OnceCell;
+
+use crate::Element;
+use crate::css::Stylesheet;
+
+/// FNV-1a哈希，保证不同编译版本下生成的class相同
+fn fnv1a(s: &str) -> u32 {
+    let mut hash: u32 = 0x811c9dc5;
+    for b in s.bytes() {
+        hash ^= b as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    hash
+}
+
+/// 一次渲染中`<head>`输出的作用域样式
+///
+/// 第一次遇到`<head>`时从渲染的根元素收集一次，渲染结果只依赖被渲染的子树，不访问祖先元素
+pub(crate) struct ScopedStyle {
+    root: Element,
+    style: OnceCell<Option<Element>>,
+}
+
+impl ScopedStyle {
+    pub(crate) fn new(root: &Element) -> Self {
+        Self { root: root.clone(), style: OnceCell::new() }
+    }
+
+    /// 标签为`tag`的元素之后需要输出的样式元素，只有`<head>`有
+    pub(crate) fn for_tag(&self, tag: &str) -> Option<&Element> {
+        if tag != "head" {
+            return None;
+        }
+        self.style
+            .get_or_init(|| {
+                let css = self.root.scoped_styles();
+                if css.is_empty() {
+                    return None;
+                }
+                let style = Element::style_sheet(css);
+                if let Some(nonce) = crate::csp::document_nonce(&self.root) {
+                    style.set_attr("nonce", nonce);
+                }
+                Some(style)
+            })
+            .as_ref()
+    }
+}
+
+impl Element {
+    /// 为当前元素设置作用域样式，返回生成的class
+    ///
+    /// 选择器中的`&`代表当前元素，其他选择器限定为当前元素的后代。
+    /// 渲染包含`<head>`的元素树时，`<head>`中会自动输出整棵树的作用域样式，也可以用`scoped_styles`手动获取
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// # use htmlbuilder::css::Stylesheet;
+    /// let card = Element::new("div", "");
+    /// let class = card.scoped_style(
+    ///     Stylesheet::new()
+    ///         .rule("&", &[("padding", "4px")])
+    ///         .rule("h2, p", &[("margin", "0")]),
+    /// );
+    /// assert_eq!(
+    ///     card.scoped_styles(),
+    ///     format!(".{0}{{padding:4px}}.{0} h2,.{0} p{{margin:0}}", class),
+    /// );
+    /// ```
+    pub fn scoped_style(&self, sheet: Stylesheet) -> String {
+        let css = sheet.to_css();
+        let class = format!("s-{:08x}", fnv1a(&css));
+        let scoped = sheet.scoped(&format!(".{}", class)).to_css();
+
+        self.merge_class(&class);
+        let mut inner = self.inner.borrow_mut();
+        inner.scoped_css = Some(scoped);
+        inner.invalidate();
+        class
+    }
+
+    /// 收集子树中全部作用域样式，相同的样式只输出一次
+    pub fn scoped_styles(&self) -> String {
+        fn collect(elem: &Element, seen: &mut Vec<String>) {
+            let inner = elem.inner.borrow();
+            if let Some(css) = &inner.scoped_css
+                && !seen.contains(css)
+            {
+                seen.push(css.clone());
+            }
+            for child in &inner.children {
+                collect(child, seen);
+            }
+        }
+
+        let mut seen = Vec::new();
+        collect(self, &mut seen);
+        seen.concat()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoped_render() {
+        let html = Element::new("html", "");
+        let head = Element::new("head", "");
+        let body = Element::new("body", "");
+        html.add(head.clone());
+        html.add(body.clone());
+        assert_eq!(html.render(""), "<html><head></head><body></body></html>");
+
+        let sheet = || Stylesheet::new().rule("a", &[("color", "red")]);
+        let a = Element::new("div", "");
+        let b = Element::new("div", "");
+        body.add(a.clone());
+        body.add(b.clone());
+        let class = a.scoped_style(sheet());
+        assert_eq!(b.scoped_style(sheet()), class);
+
+        let div = format!("<div class=\"{}\"></div>", class);
+        assert_eq!(
+            html.render(""),
+            format!("<html><head><style>.{} a{{color:red}}</style></head><body>{1}{1}</body></html>", class, div)
+        );
+        // 只渲染子树时不访问祖先元素
+        assert_eq!(head.render(""), "<head></head>");
+        assert_eq!(a.render(""), format!("<div class=\"{}\"></div>", class));
+    }
+}
@@ -0,0 +1,112 @@
+//! scraper互操作
+//!
+//! 用html5ever（scraper）按标准解析真实网页，再转为可修改的元素树
+
+use alloc::format;
+
+use scraper::node::Node;
+use scraper::{ElementRef, Html};
+
+use crate::{Element, is_void_tag};
+
+/// 将scraper节点转为元素，文档类型等不支持的节点返回`None`
+fn from_node(node: ego_tree::NodeRef<'_, Node>) -> Option<Element> {
+    match node.value() {
+        Node::Text(text) => Some(Element::new("", &**text)),
+        Node::Comment(comment) => {
+            let elem = Element::new("", "").pre(true);
+            elem.configcnt(format!("<!--{}-->", &**comment));
+            Some(elem)
+        }
+        Node::Element(dom) => {
+            let tag = dom.name();
+            let raw = matches!(tag, "script" | "style");
+            let elem = Element::new(tag, "").onetag(is_void_tag(tag)).rawtext(raw);
+            for (name, value) in dom.attrs() {
+                elem.set_attr(alloc::string::String::from(name), value);
+            }
+            if raw {
+                let text: alloc::string::String = ElementRef::wrap(node)?.text().collect();
+                elem.configcnt(text);
+                return Some(elem);
+            }
+            // <template>的内容在单独的片段节点中
+            for child in node.children() {
+                if let Node::Fragment = child.value() {
+                    for child in child.children().filter_map(from_node) {
+                        elem.push_child(child);
+                    }
+                } else if let Some(child) = from_node(child) {
+                    elem.push_child(child);
+                }
+            }
+            Some(elem)
+        }
+        _ => None,
+    }
+}
+
+/// 转换整个文档，返回根元素`<html>`
+///
+/// ```
+/// # use htmlbuilder::Element;
+/// let doc = scraper::Html::parse_document("<title>T</title><p>a<b>b");
+/// let html = Element::from(doc);
+/// assert_eq!(html.render(""), "<html><head><title>T</title></head><body><p>a<b>b</b></p></body></html>");
+/// ```
+impl From<Html> for Element {
+    fn from(doc: Html) -> Self {
+        Element::from(doc.root_element())
+    }
+}
+
+/// 转换scraper元素及其后代，转换不会失败，`TryFrom`也可以使用
+impl From<ElementRef<'_>> for Element {
+    fn from(elem: ElementRef<'_>) -> Self {
+        from_node(*elem).expect("ElementRef always wraps an element node")
+    }
+}
+
+impl From<&ElementRef<'_>> for Element {
+    fn from(elem: &ElementRef<'_>) -> Self {
+        Element::from(*elem)
+    }
+}
+
+impl Element {
+    /// 渲染后用scraper重新解析，可以使用css选择器查询；根元素为`<html>`时按整个文档解析
+    pub fn to_scraper(&self) -> Html {
+        let html = self.render("");
+        if self.inner.borrow().tag == "html" {
+            Html::parse_document(&html)
+        } else {
+            Html::parse_fragment(&html)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scraper() {
+        let doc = Html::parse_fragment(
+            "<ul class=nav><li><a href='/?a=1&amp;b=2'>A &amp; B</a><li>C<!-- x --></ul><script>if (a<b) go()</script><br>",
+        );
+        let selector = scraper::Selector::parse("ul").unwrap();
+        let ul = Element::from(&doc.select(&selector).next().unwrap());
+        assert_eq!(
+            ul.render(""),
+            r#"<ul class="nav"><li><a href="/?a=1&amp;b=2">A &amp; B</a></li><li>C<!-- x --></li></ul>"#,
+        );
+
+        let root = Element::from(doc);
+        assert!(root.render("").ends_with("<script>if (a<b) go()</script><br></html>"));
+
+        let back = ul.to_scraper();
+        let a = scraper::Selector::parse("a").unwrap();
+        assert_eq!(back.select(&a).next().unwrap().value().attr("href"), Some("/?a=1&b=2"));
+    }
+}
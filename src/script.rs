@@ -0,0 +1,168 @@
+//! 脚本元素构建
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::script::Script;
+//!
+//! let app: Element = Script::external("/app.js")
+//!     .defer()
+//!     .integrity("sha384-abc")
+//!     .into();
+//! let init: Element = Script::inline("if (a < b) { start(); }").module().into();
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+
+use crate::{Element, attr_name};
+
+/// `<script>`构建器
+pub struct Script {
+    script: Element,
+}
+
+impl Script {
+    /// 外部脚本`<script src="...">`
+    pub fn external(src: impl Into<String>) -> Self {
+        let script = Element::new("script", "");
+        script.set_attr("src", src.into());
+        Self { script }
+    }
+
+    /// 内联脚本
+    ///
+    /// 内容不会被转义，但会阻止`</script>`提前闭合元素
+    pub fn inline(js: impl Into<String>) -> Self {
+        let script = Element::new("script", "").rawtext(true);
+        script.configcnt(js);
+        Self { script }
+    }
+
+    /// 嵌入序列化后的状态数据，用于前端水合
+    ///
+    /// 生成`<script type="application/json" id="...">`，前端通过
+    /// `JSON.parse(document.getElementById(id).textContent)`读取。需要`json`特性
+    #[cfg(feature = "json")]
+    pub fn inline_data(id: impl Into<String>, state: &impl serde::Serialize) -> serde_json::Result<Self> {
+        let script = Element::new("script", "").rawtext(true);
+        script.set_attr("type", "application/json");
+        script.set_attr("id", id.into());
+        script.configcnt(crate::json::to_script_json(state)?);
+        Ok(Self { script })
+    }
+
+    /// ES模块`type="module"`
+    pub fn module(self) -> Self {
+        self.script.set_attr("type", "module");
+        self
+    }
+
+    /// 延迟到文档解析完成后执行`defer`
+    pub fn defer(self) -> Self {
+        self.script.set_attr("defer", "");
+        self
+    }
+
+    /// 异步加载并尽快执行`async`
+    pub fn async_(self) -> Self {
+        self.script.set_attr("async", "");
+        self
+    }
+
+    /// 子资源完整性校验`integrity`，未设置`crossorigin`时同时设为`anonymous`
+    pub fn integrity(self, hash: impl Into<String>) -> Self {
+        self.script.set_attr("integrity", hash.into());
+        let has_crossorigin = self.script.inner.borrow().kws.contains_key("crossorigin");
+        if !has_crossorigin {
+            self.script.set_attr("crossorigin", "anonymous");
+        }
+        self
+    }
+
+    /// 跨域设置`crossorigin`
+    pub fn crossorigin(self, value: impl Into<String>) -> Self {
+        self.script.set_attr("crossorigin", value.into());
+        self
+    }
+
+    /// 获取脚本元素
+    pub fn element(&self) -> Element {
+        self.script.clone()
+    }
+}
+
+impl From<Script> for Element {
+    fn from(script: Script) -> Self {
+        script.script
+    }
+}
+
+impl Element {
+    /// 设置事件处理属性`on{event}`，如`on("click", js)`设置`onclick`
+    ///
+    /// js与其他属性值一样通过`set_attr`设置和转义。event只能包含ASCII字母和数字，否则panic
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let button = Element::new("button", "Go");
+    /// button.on("click", r#"if (n < 3 && ok) go("next")"#);
+    /// assert_eq!(
+    ///     button.render(""),
+    ///     r#"<button onclick="if (n &lt; 3 &amp;&amp; ok) go(&quot;next&quot;)">Go</button>"#,
+    /// );
+    /// ```
+    #[track_caller]
+    pub fn on(&self, event: &str, js: impl AsRef<str>) -> &Self {
+        assert!(
+            !event.is_empty() && event.bytes().all(|b| b.is_ascii_alphanumeric()),
+            "invalid event name `{}`",
+            event
+        );
+        self.set_attr(attr_name(format!("on{}", event.to_ascii_lowercase())), js.as_ref());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attr(elem: &Element, name: &str) -> Option<String> {
+        elem.inner.borrow().kws.get(name).cloned()
+    }
+
+    #[test]
+    fn test_script() {
+        let ext = Script::external("/a.js?x=1&y=2").defer().integrity("sha384-x").element();
+        assert_eq!(attr(&ext, "src").as_deref(), Some("/a.js?x=1&amp;y=2"));
+        assert_eq!(attr(&ext, "defer").as_deref(), Some(""));
+        assert_eq!(attr(&ext, "crossorigin").as_deref(), Some("anonymous"));
+        assert!(ext.render("").ends_with("></script>"));
+
+        let inline = Script::inline("if (a < b && c) {}</script>").element();
+        assert_eq!(inline.render(""), "<script>if (a < b && c) {}<\\/script></script>");
+    }
+
+    #[test]
+    fn test_on() {
+        let js = "a > b ? f('x') : g(\"&\")";
+        let input = Element::new("input", "");
+        input.on("Change", js);
+        assert_eq!(input.attr_list(), [("onchange".into(), js.into())]);
+        assert_eq!(attr(&input, "onchange").as_deref(), Some("a &gt; b ? f(&apos;x&apos;) : g(&quot;&amp;&quot;)"));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid event name")]
+    fn test_on_invalid_event() {
+        Element::new("body", "").on("x onload", "alert(1)");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_inline_data() {
+        let data = Script::inline_data("APP_STATE", &["</script>"]).unwrap().element();
+        assert_eq!(attr(&data, "type").as_deref(), Some("application/json"));
+        assert!(data.render("").ends_with(r#">["\u003c/script\u003e"]</script>"#));
+    }
+}
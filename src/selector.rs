@@ -0,0 +1,376 @@
+//! css选择器
+//!
+//! 支持标签、`*`、`#id`、`.class`、`[attr]`、`[attr=value]`、`[attr~=value]`、
+//! `[attr^=value]`、`[attr$=value]`、`[attr*=value]`，后代（空格）与子元素（`>`）组合符，以及逗号分隔的选择器列表
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Element, un_escape_ascii};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AttrOp {
+    Exists,
+    Equals,
+    Includes,
+    Prefix,
+    Suffix,
+    Contains,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attrs: Vec<(String, AttrOp, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+/// 复合选择器序列，如`ul.nav > li a`
+#[derive(Debug, Clone)]
+struct Complex {
+    // 从左到右，第一项的组合符无意义
+    parts: Vec<(Combinator, Compound)>,
+}
+
+/// 逗号分隔的选择器列表
+#[derive(Debug, Clone)]
+pub(crate) struct SelectorList {
+    selectors: Vec<Complex>,
+}
+
+/// 选择器优先级`(id数, class与属性数, 标签数)`
+pub(crate) type Specificity = (u32, u32, u32);
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+struct Parser<'a> {
+    chars: core::iter::Peekable<core::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if is_name_char(c) {
+                name.push(c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        name
+    }
+
+    fn skip_ws(&mut self) -> bool {
+        let mut skipped = false;
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.chars.next();
+            skipped = true;
+        }
+        skipped
+    }
+
+    fn attr(&mut self) -> Result<(String, AttrOp, String), String> {
+        self.skip_ws();
+        let name = self.name();
+        if name.is_empty() {
+            return Err("expected attribute name".to_string());
+        }
+        self.skip_ws();
+        let op = match self.chars.next() {
+            Some(']') => return Ok((name, AttrOp::Exists, String::new())),
+            Some('=') => AttrOp::Equals,
+            Some(c @ ('~' | '^' | '$' | '*')) => {
+                if self.chars.next() != Some('=') {
+                    return Err(format!("expected `=` after `{}`", c));
+                }
+                match c {
+                    '~' => AttrOp::Includes,
+                    '^' => AttrOp::Prefix,
+                    '$' => AttrOp::Suffix,
+                    _ => AttrOp::Contains,
+                }
+            }
+            c => return Err(format!("unexpected {:?} in attribute selector", c)),
+        };
+        self.skip_ws();
+        let value = match self.chars.peek() {
+            Some(&q @ ('"' | '\'')) => {
+                self.chars.next();
+                let mut value = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some(c) if c == q => break,
+                        Some(c) => value.push(c),
+                        None => return Err("unclosed string in attribute selector".to_string()),
+                    }
+                }
+                value
+            }
+            _ => self.name(),
+        };
+        self.skip_ws();
+        if self.chars.next() != Some(']') {
+            return Err("expected `]`".to_string());
+        }
+        Ok((name, op, value))
+    }
+
+    fn compound(&mut self) -> Result<Option<Compound>, String> {
+        let mut compound = Compound::default();
+        let mut empty = true;
+        if self.chars.peek() == Some(&'*') {
+            self.chars.next();
+            empty = false;
+        } else {
+            let tag = self.name();
+            if !tag.is_empty() {
+                compound.tag = Some(tag.to_ascii_lowercase());
+                empty = false;
+            }
+        }
+        loop {
+            match self.chars.peek() {
+                Some('#') => {
+                    self.chars.next();
+                    compound.id = Some(self.name());
+                }
+                Some('.') => {
+                    self.chars.next();
+                    compound.classes.push(self.name());
+                }
+                Some('[') => {
+                    self.chars.next();
+                    let attr = self.attr()?;
+                    compound.attrs.push(attr);
+                }
+                Some(':') => return Err("pseudo-classes are not supported".to_string()),
+                _ => break,
+            }
+            empty = false;
+        }
+        if compound.id.as_deref() == Some("") || compound.classes.iter().any(String::is_empty) {
+            return Err("expected a name after `#` or `.`".to_string());
+        }
+        Ok(if empty { None } else { Some(compound) })
+    }
+
+    fn complex(&mut self) -> Result<Complex, String> {
+        let mut parts = Vec::new();
+        let mut combinator = Combinator::Descendant;
+        self.skip_ws();
+        loop {
+            let Some(compound) = self.compound()? else {
+                return Err(match self.chars.peek() {
+                    Some(c) => format!("unexpected `{}` in selector", c),
+                    None => "expected a selector".to_string(),
+                });
+            };
+            parts.push((combinator, compound));
+
+            let spaced = self.skip_ws();
+            match self.chars.peek() {
+                None | Some(',') => return Ok(Complex { parts }),
+                Some('>') => {
+                    self.chars.next();
+                    self.skip_ws();
+                    combinator = Combinator::Child;
+                }
+                _ if spaced => combinator = Combinator::Descendant,
+                Some(c) => return Err(format!("unexpected `{}` in selector", c)),
+            }
+        }
+    }
+}
+
+pub(crate) fn attr_value(elem: &Element, name: &str) -> Option<String> {
+    let inner = elem.inner.borrow();
+    inner.kws.get(name).map(|v| if inner.pre { v.clone() } else { un_escape_ascii(v) })
+}
+
+impl Compound {
+    fn matches(&self, elem: &Element) -> bool {
+        {
+            let inner = elem.inner.borrow();
+            if inner.tag.is_empty() {
+                // 文本节点不参与匹配
+                return false;
+            }
+            if let Some(tag) = &self.tag
+                && !inner.tag.eq_ignore_ascii_case(tag)
+            {
+                return false;
+            }
+        }
+        if let Some(id) = &self.id
+            && attr_value(elem, "id").as_deref() != Some(id.as_str())
+        {
+            return false;
+        }
+        if !self.classes.is_empty() {
+            let class = attr_value(elem, "class").unwrap_or_default();
+            let tokens: Vec<&str> = class.split_whitespace().collect();
+            if !self.classes.iter().all(|c| tokens.contains(&c.as_str())) {
+                return false;
+            }
+        }
+        self.attrs.iter().all(|(name, op, value)| {
+            let Some(actual) = attr_value(elem, name) else { return false };
+            match op {
+                AttrOp::Exists => true,
+                AttrOp::Equals => &actual == value,
+                AttrOp::Includes => actual.split_whitespace().any(|t| t == value),
+                AttrOp::Prefix => !value.is_empty() && actual.starts_with(value.as_str()),
+                AttrOp::Suffix => !value.is_empty() && actual.ends_with(value.as_str()),
+                AttrOp::Contains => !value.is_empty() && actual.contains(value.as_str()),
+            }
+        })
+    }
+
+    fn specificity(&self) -> Specificity {
+        (
+            self.id.is_some() as u32,
+            (self.classes.len() + self.attrs.len()) as u32,
+            self.tag.is_some() as u32,
+        )
+    }
+}
+
+impl Complex {
+    fn matches(&self, elem: &Element) -> bool {
+        Self::matches_at(&self.parts, elem)
+    }
+
+    fn matches_at(parts: &[(Combinator, Compound)], elem: &Element) -> bool {
+        let Some(((combinator, last), rest)) = parts.split_last() else { return true };
+        if !last.matches(elem) {
+            return false;
+        }
+        if rest.is_empty() {
+            return true;
+        }
+        let mut ancestor = elem.parent();
+        while let Some(a) = ancestor {
+            if Self::matches_at(rest, &a) {
+                return true;
+            }
+            if *combinator == Combinator::Child {
+                return false;
+            }
+            ancestor = a.parent();
+        }
+        false
+    }
+
+    fn specificity(&self) -> Specificity {
+        self.parts.iter().fold((0, 0, 0), |acc, (_, c)| {
+            let s = c.specificity();
+            (acc.0 + s.0, acc.1 + s.1, acc.2 + s.2)
+        })
+    }
+}
+
+impl SelectorList {
+    /// 解析选择器列表
+    pub(crate) fn parse(selector: &str) -> Result<Self, String> {
+        let mut parser = Parser { chars: selector.chars().peekable() };
+        let mut selectors = vec![parser.complex()?];
+        while parser.chars.next() == Some(',') {
+            selectors.push(parser.complex()?);
+        }
+        Ok(Self { selectors })
+    }
+
+    /// 匹配时返回最高的优先级
+    pub(crate) fn match_specificity(&self, elem: &Element) -> Option<Specificity> {
+        self.selectors.iter()
+            .filter(|s| s.matches(elem))
+            .map(Complex::specificity)
+            .max()
+    }
+}
+
+impl Element {
+    /// 当前元素是否匹配css选择器，支持的语法见模块说明；选择器无效时返回false
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let link = Element::new("a", "x").attrs(&[("class", "external"), ("target", "_blank")]);
+    /// assert!(link.matches("a.external[target=_blank]"));
+    /// assert!(!link.matches("nav a"));
+    /// ```
+    pub fn matches(&self, selector: &str) -> bool {
+        SelectorList::parse(selector).is_ok_and(|list| list.match_specificity(self).is_some())
+    }
+
+    /// 按文档顺序查找匹配css选择器的后代元素，不包括自身；选择器无效时返回空列表
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b"]);
+    /// assert_eq!(ul.select("ul > li").len(), 2);
+    /// ```
+    pub fn select(&self, selector: &str) -> Vec<Element> {
+        fn walk(elem: &Element, list: &SelectorList, found: &mut Vec<Element>) {
+            for child in elem {
+                if list.match_specificity(&child).is_some() {
+                    found.push(child.clone());
+                }
+                walk(&child, list, found);
+            }
+        }
+        let mut found = Vec::new();
+        if let Ok(list) = SelectorList::parse(selector) {
+            walk(self, &list, &mut found);
+        }
+        found
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selector() {
+        let ul = Element::new("ul", "").attrs(&[("class", "nav main")]);
+        let li = Element::new("li", "");
+        let a = Element::new("a", "").attrs(&[("href", "https://x.com"), ("id", "home")]);
+        ul.add(li.clone().add_with(a.clone()));
+
+        let m = |sel: &str, e: &Element| SelectorList::parse(sel).unwrap().match_specificity(e).is_some();
+        assert!(m("a", &a));
+        assert!(m("ul.nav a", &a));
+        assert!(m("ul > li > a#home", &a));
+        assert!(!m("ul > a", &a));
+        assert!(m("p, .main", &ul));
+        assert!(m("[href^=https]", &a));
+        assert!(m("[class~='nav']", &ul));
+        assert!(!m("[class=nav]", &ul));
+        assert!(m("*", &li));
+
+        let list = SelectorList::parse("a, ul a#home").unwrap();
+        assert_eq!(list.match_specificity(&a), Some((1, 0, 2)));
+
+        assert!(SelectorList::parse("a:hover").is_err());
+        assert!(SelectorList::parse("a >").is_err());
+        assert!(SelectorList::parse("").is_err());
+
+        assert!(a.matches("li > a[href*='x.com']"));
+        assert!(!a.matches("a:hover"));
+        assert_eq!(ul.select("li, a"), [li.clone(), a.clone()]);
+        assert!(ul.select("ul").is_empty());
+    }
+}
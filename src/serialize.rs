@@ -0,0 +1,117 @@
+//! serde支持
+//! 
+//! 元素树序列化为`{tag, attrs, content, children}`结构，内容和属性均为未转义的原始文本
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::collections::HashMap;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::{Element, attr_name, escape_ascii, un_escape_ascii};
+
+impl Serialize for Element {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let inner = self.inner.borrow();
+
+        let attrs: HashMap<&str, String> = inner.kws
+            .iter()
+            .map(|(k, v)| (k.as_ref(), if inner.pre { v.clone() } else { un_escape_ascii(v) }))
+            .collect();
+        let content = inner.text_content();
+
+        let mut state = serializer.serialize_struct("Element", 8)?;
+        state.serialize_field("tag", inner.tag.as_str())?;
+        state.serialize_field("attrs", &attrs)?;
+        state.serialize_field("content", &content)?;
+        state.serialize_field("children", &inner.children)?;
+        state.serialize_field("onetag", &inner.onetag)?;
+        state.serialize_field("pre", &inner.pre)?;
+        state.serialize_field("rawtext", &inner.rawtext)?;
+        match &inner.scoped_css {
+            Some(css) => state.serialize_field("scoped_css", css)?,
+            None => state.skip_field("scoped_css")?,
+        }
+        state.end()
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ElementData {
+    tag: String,
+    #[serde(default)]
+    attrs: HashMap<String, String>,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    children: Vec<Element>,
+    #[serde(default)]
+    onetag: bool,
+    #[serde(default)]
+    pre: bool,
+    #[serde(default)]
+    rawtext: bool,
+    #[serde(default)]
+    scoped_css: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for Element {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ElementData::deserialize(deserializer)?;
+
+        let elem = Element::new(data.tag, "")
+            .onetag(data.onetag)
+            .pre(data.pre)
+            .rawtext(data.rawtext);
+        elem.configcnt(data.content);
+        {
+            let mut inner = elem.inner.borrow_mut();
+            inner.scoped_css = data.scoped_css;
+            for (k, v) in data.attrs {
+                let v = if data.pre { v } else { escape_ascii(&v) };
+                inner.kws.insert(attr_name(k), v);
+            }
+        }
+        for child in data.children {
+            elem.push_child(child);
+        }
+
+        Ok(elem)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let root = Element::new("div", "a < b").attrs(&[("class", "x\"y")]);
+        root.add(Element::new("br", "").onetag(true));
+        root.add(Element::new("p", "").pre(true).add_with(Element::new("", "<b>raw</b>")));
+        root.add(Element::style_sheet("a > b {}"));
+
+        let json = serde_json::to_string(&root).unwrap();
+        assert!(json.contains(r#""content":"a < b""#));
+        assert!(json.contains(r#""class":"x\"y""#));
+
+        let back: Element = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.render("\n"), root.render("\n"));
+        assert_eq!(back.children()[0].parent(), Some(back.clone()));
+    }
+
+    #[test]
+    fn test_minimal_json() {
+        let elem: Element = serde_json::from_str(r#"{"tag": "a", "attrs": {"data-id": "1"}}"#).unwrap();
+        assert_eq!(elem.render(""), r#"<a data-id="1"></a>"#);
+    }
+
+    #[test]
+    fn test_void_with_children() {
+        // 不可信的输入不能panic
+        let elem: Element = serde_json::from_str(r#"{"tag": "br", "children": [{"tag": "b"}]}"#).unwrap();
+        assert_eq!(elem.child_count(), 1);
+    }
+}
@@ -0,0 +1,163 @@
+//! 站点地图sitemap.xml
+//!
+//! ```
+//! use htmlbuilder::sitemap::{ChangeFreq, Sitemap};
+//!
+//! let xml = Sitemap::new()
+//!     .url("https://example.com/")
+//!     .lastmod("2024-05-01")
+//!     .changefreq(ChangeFreq::Weekly)
+//!     .priority(0.8)
+//!     .url("https://example.com/about?a=1&b=2")
+//!     .render();
+//! assert_eq!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>
+//! <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+//! <url>
+//! <loc>https://example.com/</loc>
+//! <lastmod>2024-05-01</lastmod>
+//! <changefreq>weekly</changefreq>
+//! <priority>0.8</priority>
+//! </url>
+//! <url>
+//! <loc>https://example.com/about?a=1&amp;b=2</loc>
+//! </url>
+//! </urlset>"#);
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Element, RenderOptions};
+
+/// XML声明
+pub(crate) const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+/// 页面的更新频率
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeFreq {
+    /// 每次访问都会变化
+    Always,
+    /// 每小时
+    Hourly,
+    /// 每天
+    Daily,
+    /// 每周
+    Weekly,
+    /// 每月
+    Monthly,
+    /// 每年
+    Yearly,
+    /// 已存档，不再变化
+    Never,
+}
+
+impl ChangeFreq {
+    /// `<changefreq>`的值
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeFreq::Always => "always",
+            ChangeFreq::Hourly => "hourly",
+            ChangeFreq::Daily => "daily",
+            ChangeFreq::Weekly => "weekly",
+            ChangeFreq::Monthly => "monthly",
+            ChangeFreq::Yearly => "yearly",
+            ChangeFreq::Never => "never",
+        }
+    }
+}
+
+struct Entry {
+    loc: String,
+    lastmod: Option<String>,
+    changefreq: Option<ChangeFreq>,
+    priority: Option<f32>,
+}
+
+/// 站点地图构建器
+///
+/// `lastmod`、`changefreq`、`priority`作用于最后添加的url，没有url时忽略
+#[derive(Default)]
+pub struct Sitemap {
+    entries: Vec<Entry>,
+}
+
+impl Sitemap {
+    /// 创建空的站点地图
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加页面的完整url
+    pub fn url(mut self, loc: impl Into<String>) -> Self {
+        self.entries.push(Entry { loc: loc.into(), lastmod: None, changefreq: None, priority: None });
+        self
+    }
+
+    /// 最后修改时间，W3C日期格式，如`2024-05-01`或`2024-05-01T12:00:00+08:00`
+    pub fn lastmod(mut self, date: impl Into<String>) -> Self {
+        if let Some(entry) = self.entries.last_mut() {
+            entry.lastmod = Some(date.into());
+        }
+        self
+    }
+
+    /// 更新频率
+    pub fn changefreq(mut self, freq: ChangeFreq) -> Self {
+        if let Some(entry) = self.entries.last_mut() {
+            entry.changefreq = Some(freq);
+        }
+        self
+    }
+
+    /// 相对优先级，限制在0.0到1.0之间
+    pub fn priority(mut self, priority: f32) -> Self {
+        if let Some(entry) = self.entries.last_mut() {
+            entry.priority = Some(priority.clamp(0.0, 1.0));
+        }
+        self
+    }
+
+    /// 生成`<urlset>`元素树
+    pub fn to_element(&self) -> Element {
+        let urlset = Element::new("urlset", "");
+        urlset.set_attr("xmlns", "http://www.sitemaps.org/schemas/sitemap/0.9");
+        for entry in &self.entries {
+            let url = Element::new("url", "").add_with(Element::new("loc", entry.loc.as_str()));
+            if let Some(date) = &entry.lastmod {
+                url.add(Element::new("lastmod", date.as_str()));
+            }
+            if let Some(freq) = entry.changefreq {
+                url.add(Element::new_static("changefreq", freq.as_str()));
+            }
+            if let Some(priority) = entry.priority {
+                url.add(Element::new("priority", format!("{:.1}", priority)));
+            }
+            urlset.add(url);
+        }
+        urlset
+    }
+
+    /// 渲染为sitemap.xml
+    pub fn render(&self) -> String {
+        let xml = self.to_element().render_with("\n", &RenderOptions::new().xml(true));
+        format!("{}\n{}", XML_DECLARATION, xml)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sitemap() {
+        assert_eq!(
+            Sitemap::new().lastmod("ignored").render(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"/>",
+        );
+        let xml = Sitemap::new().url("/a").priority(3.0).url("/b").priority(0.25).render();
+        assert!(xml.contains("<loc>/a</loc>\n<priority>1.0</priority>"));
+        assert!(xml.contains("<loc>/b</loc>\n<priority>0.2</priority>"));
+    }
+}
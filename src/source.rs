@@ -0,0 +1,70 @@
+//! 记录创建元素的源代码位置，需要`track-source`特性
+
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::panic::Location;
+
+use crate::Element;
+
+/// 元素的来源
+#[derive(Clone)]
+pub(crate) enum Source {
+    /// 调用`Element::new`等构造函数的位置
+    Location(&'static Location<'static>),
+    /// 用户设置的标签
+    Label(String),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Location(location) => write!(f, "{}", location),
+            Source::Label(label) => f.write_str(label),
+        }
+    }
+}
+
+/// 在说明后附加元素的来源
+pub(crate) fn describe(elem: &Element, message: String) -> String {
+    alloc::format!("{} (created at {})", message, elem.source())
+}
+
+impl Element {
+    /// 创建元素的源代码位置（`文件:行:列`）或`label`设置的标签，需要`track-source`特性
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let div = Element::new("div", "");
+    /// assert!(div.source().ends_with(&format!(":{}:11", line!() - 1)));
+    /// assert_eq!(div.label("sidebar").source(), "sidebar");
+    /// ```
+    pub fn source(&self) -> String {
+        self.inner.borrow().source.to_string()
+    }
+
+    /// 用标签代替源代码位置，适合由通用函数创建的元素，需要`track-source`特性
+    pub fn label(self, label: impl Into<String>) -> Self {
+        self.inner.borrow_mut().source = Source::Label(label.into());
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+    use crate::tags;
+
+    #[test]
+    fn test_source() {
+        let line = line!() + 1;
+        let p = tags::p().add_with(Element::new("div", ""));
+        assert_eq!(p.source(), format!("{}:{}:17", file!(), line));
+        let issues = p.validate();
+        assert!(issues[0].message.ends_with(&format!("(created at {})", p.children()[0].source())));
+        assert!(format!("{:?}", p).contains(&format!("source: {}:{}", file!(), line)));
+        assert_eq!(p.deep_clone().source(), p.source());
+    }
+}
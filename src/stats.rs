@@ -0,0 +1,73 @@
+//! 元素树统计
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+
+use crate::Element;
+
+/// 子树的统计信息，由`Element::stats`创建
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TreeStats {
+    /// 节点数，包括文本节点
+    pub node_count: usize,
+    /// 最大深度，只有根节点时为1
+    pub depth: usize,
+    /// 未转义文本内容的总字节数
+    pub text_bytes: usize,
+    /// 每种标签的元素数
+    pub tags: BTreeMap<String, usize>,
+}
+
+impl TreeStats {
+    fn visit(&mut self, elem: &Element, depth: usize) {
+        self.node_count += 1;
+        self.depth = self.depth.max(depth);
+        {
+            let inner = elem.inner.borrow();
+            self.text_bytes += inner.text_content().len();
+            if !inner.tag.is_empty() {
+                *self.tags.entry(inner.tag.to_string()).or_default() += 1;
+            }
+        }
+        elem.for_each_child(|child| self.visit(child, depth + 1));
+    }
+}
+
+impl Element {
+    /// 统计子树的节点数、深度、文本字节数和各标签的数量
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "bc"]);
+    /// let stats = ul.stats();
+    /// assert_eq!((stats.node_count, stats.depth, stats.text_bytes), (3, 2, 3));
+    /// assert_eq!(stats.tags["li"], 2);
+    /// ```
+    pub fn stats(&self) -> TreeStats {
+        let mut stats = TreeStats::default();
+        stats.visit(self, 1);
+        stats
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let body = Element::new("body", "")
+            .add_with(Element::new("p", "a &amp; b").add_with(Element::new("", " ")).add_with(Element::new("em", "x")))
+            .add_with(Element::new("p", ""));
+        let stats = body.stats();
+        assert_eq!(stats.node_count, 5);
+        assert_eq!(stats.depth, 3);
+        assert_eq!(stats.text_bytes, 11);
+        assert_eq!(stats.tags.into_iter().collect::<alloc::vec::Vec<_>>(), [
+            ("body".to_string(), 1),
+            ("em".to_string(), 1),
+            ("p".to_string(), 2),
+        ]);
+    }
+}
@@ -0,0 +1,171 @@
+//! 流式渲染
+//!
+//! 逐个节点输出html片段，拼接结果与`render`相同
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Element;
+use crate::scoped::ScopedStyle;
+
+enum Frame {
+    Text(String),
+    Open(Element),
+}
+
+/// 按文档顺序产生html片段
+pub(crate) struct Pieces {
+    split_s: String,
+    stack: Vec<Frame>,
+    scoped: ScopedStyle,
+}
+
+impl Pieces {
+    pub(crate) fn new(elem: &Element, split_s: &str) -> Self {
+        Self { split_s: split_s.to_string(), stack: vec![Frame::Open(elem.clone())], scoped: ScopedStyle::new(elem) }
+    }
+}
+
+impl Iterator for Pieces {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let elem = match self.stack.pop()? {
+            Frame::Text(text) => return Some(text),
+            Frame::Open(elem) => elem,
+        };
+        let inner = elem.inner.borrow();
+        if inner.tag.is_empty() {
+            return Some(inner.content.to_string());
+        }
+
+        let mut open = inner.open_tag();
+        open.push_str(&inner.content);
+
+        let style = self.scoped.for_tag(&inner.tag).map(|style| style.render(&self.split_s));
+        let close = if inner.onetag {
+            self.split_s.clone()
+        } else if !inner.children.is_empty() || style.is_some() {
+            format!("{}</{}>", self.split_s, inner.tag)
+        } else {
+            format!("</{}>", inner.tag)
+        };
+        self.stack.push(Frame::Text(close));
+        if let Some(style) = style {
+            self.stack.push(Frame::Text(format!("{}{}", self.split_s, style)));
+        }
+        for child in inner.children.iter().rev() {
+            self.stack.push(Frame::Open(child.clone()));
+            if !self.split_s.is_empty() {
+                self.stack.push(Frame::Text(self.split_s.clone()));
+            }
+        }
+        Some(open)
+    }
+}
+
+/// 分块渲染的迭代器，由`Element::render_chunks`创建
+pub struct Chunks {
+    pieces: Pieces,
+    hint: usize,
+}
+
+impl Iterator for Chunks {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let mut buf = String::with_capacity(self.hint);
+        for piece in self.pieces.by_ref() {
+            buf.push_str(&piece);
+            if buf.len() >= self.hint {
+                break;
+            }
+        }
+        if buf.is_empty() { None } else { Some(buf) }
+    }
+}
+
+impl Element {
+    /// 边遍历边渲染，按块产生html，每块大约`chunk_hint`字节（不拆分单个节点的输出）
+    ///
+    /// 拼接全部块的结果与`render(split_s)`相同，可用于http流式响应，在整个页面渲染完成前先发送`<head>`
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(0..100);
+    /// let chunks: Vec<String> = ul.render_chunks("", 64).collect();
+    /// assert!(chunks.len() > 1);
+    /// assert_eq!(chunks.concat(), ul.render(""));
+    /// ```
+    pub fn render_chunks(&self, split_s: &str, chunk_hint: usize) -> Chunks {
+        Chunks { pieces: Pieces::new(self, split_s), hint: chunk_hint.max(1) }
+    }
+
+    /// 渲染并逐步写入异步输出，每写入一块后让出执行权，避免长时间阻塞运行时。需要`tokio`特性
+    ///
+    /// 输出与`render(split_s)`相同。`Element`不是`Send`，返回的future也不是`Send`，
+    /// 需要在单线程运行时或`tokio::task::LocalSet`中运行
+    #[cfg(feature = "tokio")]
+    pub async fn render_async<W>(&self, w: &mut W, split_s: &str) -> std::io::Result<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        for chunk in self.render_chunks(split_s, 8 * 1024) {
+            w.write_all(chunk.as_bytes()).await?;
+            tokio::task::yield_now().await;
+        }
+        w.flush().await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::Stylesheet;
+
+    fn sample() -> Element {
+        let head = Element::new("head", "").add_with(Element::new("title", "t"));
+        let body = Element::new("body", "x")
+            .add_with(Element::new("br", "").onetag(true))
+            .add_with(Element::new("", "text"))
+            .add_with(Element::ul_from(["a", "b"]));
+        body.scoped_style(Stylesheet::new().rule("&", &[("color", "red")]));
+        Element::new("html", "").add_with(head).add_with(body)
+    }
+
+    #[test]
+    fn test_pieces() {
+        let html = sample();
+        for split_s in ["", "\n"] {
+            assert_eq!(Pieces::new(&html, split_s).collect::<String>(), html.render(split_s));
+        }
+    }
+
+    #[test]
+    fn test_render_chunks() {
+        let html = sample();
+        let chunks: Vec<String> = html.render_chunks("", 10).collect();
+        assert!(chunks.iter().all(|c| !c.is_empty()));
+        assert!(chunks[0].starts_with("<html><head>"));
+        assert_eq!(chunks.concat(), html.render(""));
+        assert_eq!(html.render_chunks("", 1 << 20).count(), 1);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_render_async() {
+        let html = sample();
+        for _ in 0..2000 {
+            html.children()[1].add(Element::new("p", "paragraph"));
+        }
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let mut out: Vec<u8> = Vec::new();
+        rt.block_on(html.render_async(&mut out, "\n")).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), html.render("\n"));
+    }
+}
@@ -0,0 +1,295 @@
+//! 表格构建
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::table::Table;
+//!
+//! let users = [("Alice", 30), ("Bob", 25)];
+//! let table: Element = Table::new(["Name", "Age"])
+//!     .rows(users.iter().map(|(name, age)| [name.to_string(), age.to_string()]))
+//!     .footer(["Total", "2"])
+//!     .into();
+//! ```
+
+use alloc::string::ToString;
+
+use crate::{DisplayText, Element};
+use crate::tags::{table, tbody, tfoot, thead, tr};
+
+/// 可作为单元格内容的类型
+///
+/// 文本被转义后放入单元格；`Element`若本身是`td`/`th`则直接作为单元格，否则放入单元格中
+pub trait IntoCell {
+    fn into_cell(self, cell_tag: &'static str) -> Element;
+}
+
+impl IntoCell for Element {
+    fn into_cell(self, cell_tag: &'static str) -> Element {
+        let is_cell = matches!(self.inner.borrow().tag.as_str(), "td" | "th");
+        if is_cell {
+            self
+        } else {
+            Element::new(cell_tag, "").add_with(self)
+        }
+    }
+}
+
+impl<T: DisplayText> IntoCell for T {
+    fn into_cell(self, cell_tag: &'static str) -> Element {
+        Element::new(cell_tag, self.into_text())
+    }
+}
+
+impl IntoCell for bool {
+    fn into_cell(self, cell_tag: &'static str) -> Element {
+        Element::new(cell_tag, self.to_string())
+    }
+}
+
+impl IntoCell for &bool {
+    fn into_cell(self, cell_tag: &'static str) -> Element {
+        (*self).into_cell(cell_tag)
+    }
+}
+
+impl IntoCell for &Element {
+    fn into_cell(self, cell_tag: &'static str) -> Element {
+        self.clone().into_cell(cell_tag)
+    }
+}
+
+/// `None`生成空单元格
+impl<T: IntoCell> IntoCell for Option<T> {
+    fn into_cell(self, cell_tag: &'static str) -> Element {
+        match self {
+            Some(value) => value.into_cell(cell_tag),
+            None => Element::new(cell_tag, ""),
+        }
+    }
+}
+
+impl<'a, T> IntoCell for &'a Option<T>
+where
+    &'a T: IntoCell,
+{
+    fn into_cell(self, cell_tag: &'static str) -> Element {
+        self.as_ref().into_cell(cell_tag)
+    }
+}
+
+/// 可以作为表格一行的结构体，可以用`#[derive(HtmlTable)]`实现（需要`macros`特性）
+///
+/// 每个字段是一列，字段的引用需要实现`IntoCell`（字符串、数字、`bool`、`Element`和它们的`Option`都可以）。字段属性`#[table(...)]`：
+///
+/// - `header = "..."`：表头文本，默认为首字母大写、下划线替换为空格的字段名
+/// - `skip`：不生成该列
+///
+/// ```
+/// # #[cfg(feature = "macros")] {
+/// use htmlbuilder::table::{HtmlTable, Table};
+///
+/// #[derive(HtmlTable)]
+/// struct User {
+///     name: String,
+///     #[table(header = "Age (years)")]
+///     age: u32,
+///     #[table(skip)]
+///     password_hash: String,
+/// }
+///
+/// let users = vec![User { name: "Alice".into(), age: 30, password_hash: String::new() }];
+/// assert_eq!(
+///     Table::from_rows(&users).element().render(""),
+///     "<table><thead><tr><th>Name</th><th>Age (years)</th></tr></thead>\
+///      <tbody><tr><td>Alice</td><td>30</td></tr></tbody></table>",
+/// );
+/// # }
+/// ```
+pub trait HtmlTable {
+    /// 表头
+    fn headers() -> &'static [&'static str];
+
+    /// 生成一行`<tr>`
+    fn to_table_row(&self) -> Element;
+}
+
+#[cfg(feature = "macros")]
+pub use htmlbuilder_macros::HtmlTable;
+
+/// 表格构建器
+pub struct Table {
+    table: Element,
+    tbody: Element,
+    tfoot: Option<Element>,
+}
+
+fn make_row<I>(cells: I, cell_tag: &'static str) -> Element
+where
+    I: IntoIterator,
+    I::Item: IntoCell,
+{
+    let row = tr();
+    for cell in cells {
+        row.add(cell.into_cell(cell_tag));
+    }
+    row
+}
+
+impl Table {
+    /// 创建表格，headers为表头单元格
+    pub fn new<I>(headers: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoCell,
+    {
+        let table = table();
+        let head = make_row(headers, "th");
+        if !head.children().is_empty() {
+            table.add(thead().add_with(head));
+        }
+        let tbody = tbody();
+        table.add(tbody.clone());
+        Self { table, tbody, tfoot: None }
+    }
+
+    /// 由结构体列表创建表格，表头和每行的单元格由`HtmlTable`生成
+    pub fn from_rows<'a, T: HtmlTable + 'a>(rows: impl IntoIterator<Item = &'a T>) -> Self {
+        let table = Self::new(T::headers().iter().copied());
+        for row in rows {
+            table.tbody.add(row.to_table_row());
+        }
+        table
+    }
+
+    /// 添加一行
+    pub fn row<I>(self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoCell,
+    {
+        self.tbody.add(make_row(cells, "td"));
+        self
+    }
+
+    /// 添加一行并设置该行`<tr>`的属性
+    pub fn row_attrs<I>(self, cells: I, attrs: &[(&'static str, &str)]) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoCell,
+    {
+        let row = make_row(cells, "td");
+        row.set_attrs(attrs);
+        self.tbody.add(row);
+        self
+    }
+
+    /// 批量添加行
+    pub fn rows<R, I>(mut self, rows: R) -> Self
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator,
+        I::Item: IntoCell,
+    {
+        for cells in rows {
+            self = self.row(cells);
+        }
+        self
+    }
+
+    /// 添加一行表尾`<tfoot>`
+    pub fn footer<I>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoCell,
+    {
+        let tfoot = self.tfoot.get_or_insert_with(|| {
+            let tfoot = tfoot();
+            self.table.add(tfoot.clone());
+            tfoot
+        });
+        tfoot.add(make_row(cells, "td"));
+        self
+    }
+
+    /// 获取表格元素
+    pub fn element(&self) -> Element {
+        self.table.clone()
+    }
+}
+
+impl From<Table> for Element {
+    fn from(table: Table) -> Self {
+        table.table
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::tags::td;
+
+    #[test]
+    fn test_table() {
+        let link = Element::new("a", "x");
+        let wide = td().add_with(Element::new("", "wide"));
+        wide.set_attr("colspan", "2");
+
+        let table = Table::new(["A", "B"])
+            .rows([[1, 2], [3, 4]])
+            .row_attrs(["<5>", "6"], &[("class", "odd")])
+            .row([link])
+            .row([wide])
+            .footer(["sum", "10"])
+            .element();
+
+        assert_eq!(
+            table.render(""),
+            concat!(
+                "<table><thead><tr><th>A</th><th>B</th></tr></thead>",
+                "<tbody><tr><td>1</td><td>2</td></tr><tr><td>3</td><td>4</td></tr>",
+                "<tr class=\"odd\"><td>&lt;5&gt;</td><td>6</td></tr>",
+                "<tr><td><a>x</a></td></tr>",
+                "<tr><td colspan=\"2\">wide</td></tr></tbody>",
+                "<tfoot><tr><td>sum</td><td>10</td></tr></tfoot></table>",
+            )
+        );
+    }
+
+    #[test]
+    fn test_no_headers() {
+        let table = Table::new(Vec::<&str>::new()).row(["x"]).element();
+        assert_eq!(table.render(""), "<table><tbody><tr><td>x</td></tr></tbody></table>");
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_derive_html_table() {
+        use alloc::string::String;
+
+        #[derive(HtmlTable)]
+        struct Row {
+            id: u32,
+            #[table(header = "E-mail")]
+            email: String,
+            last_login: Option<String>,
+            active: bool,
+        }
+
+        let rows = [
+            Row { id: 1, email: "a@b.c".into(), last_login: Some("today".into()), active: true },
+            Row { id: 2, email: "<x>".into(), last_login: None, active: false },
+        ];
+        assert_eq!(Row::headers(), ["Id", "E-mail", "Last login", "Active"]);
+        assert_eq!(
+            Table::from_rows(&rows).element().render(""),
+            concat!(
+                "<table><thead><tr><th>Id</th><th>E-mail</th><th>Last login</th><th>Active</th></tr></thead>",
+                "<tbody><tr><td>1</td><td>a@b.c</td><td>today</td><td>true</td></tr>",
+                "<tr><td>2</td><td>&lt;x&gt;</td><td></td><td>false</td></tr></tbody></table>",
+            )
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! 标签构造函数
+//!
+//! 用函数代替标签字符串，避免拼写错误；空元素已设为单标签
+//!
+//! ```
+//! use htmlbuilder::tags::{a, div, img};
+//!
+//! let card = div()
+//!     .add_with(img("logo.png", "logo"))
+//!     .add_with(a("https://www.rust-lang.org/").add_with(htmlbuilder::Element::new("", "rust")));
+//! ```
+
+use alloc::string::String;
+
+use crate::Element;
+
+macro_rules! tags {
+    ($($tag:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("创建`<", stringify!($tag), ">`元素")]
+            #[track_caller]
+            pub fn $tag() -> Element {
+                Element::new(stringify!($tag), "")
+            }
+        )*
+    };
+}
+
+macro_rules! void_tags {
+    ($($tag:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("创建`<", stringify!($tag), ">`空元素（单标签）")]
+            #[track_caller]
+            pub fn $tag() -> Element {
+                Element::new(stringify!($tag), "").onetag(true)
+            }
+        )*
+    };
+}
+
+tags!(
+    html, head, title, body, style, script, noscript, template,
+    header, footer, main, nav, section, article, aside, address, search,
+    h1, h2, h3, h4, h5, h6, hgroup,
+    div, p, span, pre, blockquote, figure, figcaption,
+    ul, ol, li, dl, dt, dd, menu,
+    em, strong, small, s, cite, q, dfn, abbr, code, var, samp, kbd,
+    sub, sup, i, b, u, mark, bdi, bdo, ruby, rt, rp, time, data,
+    ins, del, picture, video, audio, canvas, iframe, object, map, svg,
+    table, caption, colgroup, thead, tbody, tfoot, tr, th, td,
+    form, fieldset, legend, label, button, select, datalist, optgroup, option,
+    textarea, output, progress, meter, details, summary, dialog,
+);
+
+void_tags!(br, hr, wbr, area, base, col, embed, source, track);
+
+/// 创建链接`<a href="...">`
+#[track_caller]
+pub fn a(href: impl Into<String>) -> Element {
+    let elem = Element::new("a", "");
+    elem.set_attr("href", href.into());
+    elem
+}
+
+/// 创建图片`<img src="..." alt="...">`（单标签）
+#[track_caller]
+pub fn img(src: impl Into<String>, alt: impl Into<String>) -> Element {
+    let elem = Element::new("img", "").onetag(true);
+    elem.set_attr("src", src.into());
+    elem.set_attr("alt", alt.into());
+    elem
+}
+
+/// 创建输入框`<input type="...">`（单标签）
+#[track_caller]
+pub fn input(ty: impl Into<String>) -> Element {
+    let elem = Element::new("input", "").onetag(true);
+    elem.set_attr("type", ty.into());
+    elem
+}
+
+/// 创建`<link rel="..." href="...">`（单标签）
+#[track_caller]
+pub fn link(rel: impl Into<String>, href: impl Into<String>) -> Element {
+    let elem = Element::new("link", "").onetag(true);
+    elem.set_attr("rel", rel.into());
+    elem.set_attr("href", href.into());
+    elem
+}
+
+/// 创建`<meta>`（单标签）
+#[track_caller]
+pub fn meta() -> Element {
+    Element::new("meta", "").onetag(true)
+}
+
+/// 创建文本节点（标签名为空的元素）
+#[track_caller]
+pub fn text(content: impl Into<String>) -> Element {
+    Element::new("", content)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags() {
+        let list = ul().add_with(li().add_with(text("a<b")));
+        assert_eq!(list.render(""), "<ul><li>a&lt;b</li></ul>");
+
+        assert_eq!(br().render(""), "<br>");
+        assert_eq!(a("/x?a=1&b=2").render(""), "<a href=\"/x?a=1&amp;b=2\"></a>");
+        assert_eq!(input("checkbox").render(""), "<input type=\"checkbox\">");
+
+        let image = img("a.png", "pic").render("");
+        assert!(image.starts_with("<img ") && image.contains("alt=\"pic\"") && !image.contains("</img>"));
+    }
+}
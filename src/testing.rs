@@ -0,0 +1,195 @@
+//! 测试工具
+//!
+//! 比较html时忽略属性顺序、引号风格和无意义的空白
+//!
+//! ```
+//! use htmlbuilder::{Element, assert_html_eq};
+//!
+//! let div = Element::new("div", "text").attrs(&[("id", "a"), ("class", "b")]);
+//! assert_html_eq!(div, "<div class='b' id=\"a\">\n  text\n</div>");
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::parser::parse_fragment;
+use crate::{Element, escape_ascii};
+
+/// 可以作为html比较的值
+pub trait AsHtml {
+    /// 转为html文本
+    fn as_html(&self) -> String;
+}
+
+impl AsHtml for Element {
+    fn as_html(&self) -> String {
+        self.render("")
+    }
+}
+
+impl AsHtml for str {
+    fn as_html(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl AsHtml for String {
+    fn as_html(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T: AsHtml + ?Sized> AsHtml for &T {
+    fn as_html(&self) -> String {
+        (**self).as_html()
+    }
+}
+
+/// 保留空白的元素
+const PRESERVE_WS: &[&str] = &["pre", "textarea", "script", "style"];
+
+fn collapse_ws(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn canonical(elem: &Element, depth: usize, preserve: bool, lines: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    let inner = elem.inner.borrow();
+    if inner.tag.is_empty() {
+        let text = if inner.pre {
+            inner.content.trim().to_string()
+        } else if preserve {
+            escape_ascii(&inner.text_content())
+        } else {
+            escape_ascii(&collapse_ws(&inner.text_content()))
+        };
+        if !text.is_empty() {
+            lines.push(format!("{}{}", indent, text));
+        }
+        return;
+    }
+
+    let mut attrs: Vec<(&str, String)> = inner.kws.iter()
+        .map(|(k, v)| (k.as_ref(), if inner.pre { escape_ascii(v) } else { v.clone() }))
+        .collect();
+    attrs.sort();
+    let mut open = format!("{}<{}", indent, inner.tag);
+    for (k, v) in attrs {
+        open.push_str(&format!(" {}=\"{}\"", k, v));
+    }
+    open.push('>');
+    lines.push(open);
+
+    let preserve = preserve || PRESERVE_WS.contains(&inner.tag.as_str());
+    let content = Element::new("", "");
+    content.inner.borrow_mut().content = inner.content.clone();
+    canonical(&content, depth + 1, preserve, lines);
+    for child in &inner.children {
+        canonical(child, depth + 1, preserve, lines);
+    }
+
+    if !inner.onetag {
+        lines.push(format!("{}</{}>", indent, inner.tag));
+    }
+}
+
+fn canonical_lines(html: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for node in parse_fragment(html) {
+        canonical(&node, 0, false, &mut lines);
+    }
+    lines
+}
+
+/// 将html转为规范形式：每个标签和文本各占一行并缩进，属性按名称排序、统一使用双引号，
+/// 文本中的连续空白合并为一个空格（`pre`、`textarea`、`script`、`style`中除外）
+///
+/// ```
+/// # use htmlbuilder::testing::normalize_html;
+/// assert_eq!(normalize_html("<p b=1 a='2'> x  y </p>"), "<p a=\"2\" b=\"1\">\n  x y\n</p>");
+/// ```
+pub fn normalize_html(html: &str) -> String {
+    canonical_lines(html).join("\n")
+}
+
+/// 按行比较，输出`-`/`+`标记的差异
+fn line_diff(left: &[String], right: &[String]) -> String {
+    let (n, m) = (left.len(), right.len());
+    // 最长公共子序列
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if left[i] == right[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && left[i] == right[j] {
+            out.push_str(&format!("  {}\n", left[i]));
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push_str(&format!("- {}\n", left[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("+ {}\n", right[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_html_eq_impl(left: &dyn AsHtml, right: &dyn AsHtml, message: Option<core::fmt::Arguments>) {
+    let (left, right) = (canonical_lines(&left.as_html()), canonical_lines(&right.as_html()));
+    if left != right {
+        let message = message.map(|m| format!(": {}", m)).unwrap_or_default();
+        panic!("html not equal{}\n(- left, + right)\n{}", message, line_diff(&left, &right));
+    }
+}
+
+/// 断言两段html结构相同，参数可以是`Element`或html字符串
+///
+/// 忽略属性顺序、引号风格和无意义的空白，失败时输出逐行的结构差异
+#[macro_export]
+macro_rules! assert_html_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::testing::assert_html_eq_impl(&$left, &$right, None)
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::testing::assert_html_eq_impl(&$left, &$right, Some(format_args!($($arg)+)))
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assert_html_eq() {
+        let ul = Element::ul_from(["a", "b"]).attrs(&[("id", "x"), ("class", "list")]);
+        assert_html_eq!(ul, r#"
+            <ul class=list id='x'>
+                <li>a</li>
+                <li> b </li>
+            </ul>
+        "#);
+        assert_html_eq!("<pre> a  b</pre>", String::from("<pre> a  b</pre>"));
+
+        let result = std::panic::catch_unwind(|| {
+            assert_html_eq!("<p>a<br></p>", "<p>b<br></p>", "case {}", 1);
+        });
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(*message, "html not equal: case 1\n(- left, + right)\n  <p>\n-   a\n+   b\n    <br>\n  </p>\n");
+    }
+}
@@ -0,0 +1,460 @@
+//! 文本转换
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::Element;
+
+/// 块级元素，其余元素按行内元素处理
+const BLOCK_TAGS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "body", "details", "dialog", "div", "dl", "dd",
+    "dt", "fieldset", "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6",
+    "header", "hgroup", "hr", "html", "li", "main", "menu", "nav", "ol", "p", "pre", "section",
+    "summary", "table", "tbody", "td", "tfoot", "th", "thead", "tr", "ul",
+];
+
+/// 不输出文本的元素
+const SKIP_TAGS: &[&str] = &["head", "script", "style", "template", "title"];
+
+fn collapse_ws(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut last_ws = false;
+    for c in s.chars() {
+        if c.is_whitespace() {
+            if !last_ws {
+                result.push(' ');
+            }
+            last_ws = true;
+        } else {
+            result.push(c);
+            last_ws = false;
+        }
+    }
+    result
+}
+
+fn tag_of(elem: &Element) -> String {
+    elem.inner.borrow().tag.to_string()
+}
+
+fn attr_of(elem: &Element, name: &str) -> String {
+    let inner = elem.inner.borrow();
+    match inner.kws.get(name) {
+        Some(v) if inner.pre => v.clone(),
+        Some(v) => crate::un_escape_ascii(v),
+        None => String::new(),
+    }
+}
+
+/// 默认保留空白的元素
+const PRESERVE_WS_TAGS: &[&str] = &["pre", "code", "textarea"];
+
+fn is_block(elem: &Element) -> bool {
+    BLOCK_TAGS.contains(&tag_of(elem).as_str())
+}
+
+/// 是否为普通文本节点（不含占位符等带属性的标记节点）
+fn is_plain_text(elem: &Element) -> bool {
+    let inner = elem.inner.borrow();
+    inner.tag.is_empty() && !inner.pre && inner.kws.is_empty()
+}
+
+/// 合并空白后按需去掉首尾空白，直接修改已转义的内容
+fn normalize_content(elem: &Element, trim_start: bool, trim_end: bool) {
+    let mut inner = elem.inner.borrow_mut();
+    let mut text = collapse_ws(&inner.content);
+    if trim_end {
+        text.truncate(text.trim_end().len());
+    }
+    if trim_start {
+        text = text.trim_start().to_string();
+    }
+    if text != inner.content {
+        inner.content = text.into();
+        inner.invalidate();
+    }
+}
+
+fn normalize_ws(elem: &Element, preserve: &[&str]) {
+    let skip = {
+        let inner = elem.inner.borrow();
+        inner.pre || inner.rawtext || preserve.contains(&inner.tag.as_str())
+    };
+    if skip {
+        return;
+    }
+
+    let block = is_block(elem);
+    let children = elem.children();
+    let has_tag = !elem.inner.borrow().tag.is_empty();
+    if has_tag {
+        let first_block = children.first().is_none_or(is_block);
+        normalize_content(elem, block, first_block && (block || !children.is_empty()));
+    }
+
+    let mut empty = Vec::new();
+    for (i, child) in children.iter().enumerate() {
+        if !is_plain_text(child) {
+            normalize_ws(child, preserve);
+            continue;
+        }
+        let prev_block = match i.checked_sub(1) {
+            Some(prev) => is_block(&children[prev]),
+            None => block && elem.inner.borrow().content.is_empty(),
+        };
+        let next_block = children.get(i + 1).map_or(block, is_block);
+        normalize_content(child, prev_block, next_block);
+        if child.inner.borrow().content.is_empty() {
+            empty.push(child);
+        }
+    }
+    for child in empty {
+        elem.remove_child_by_ref(child);
+    }
+}
+
+/// 改写子树中的文本内容，不修改标签、属性、`pre`和原始文本元素
+fn map_text(elem: &Element, f: &impl Fn(&str) -> Option<String>) {
+    let text = {
+        let inner = elem.inner.borrow();
+        if inner.pre || inner.rawtext { None } else { Some(inner.text_content()) }
+    };
+    if let Some(text) = text.as_deref().and_then(f) {
+        elem.configcnt(text);
+    }
+    for child in elem {
+        map_text(&child, f);
+    }
+}
+
+/// 元素自身及全部后代的原始文本
+fn raw_text(elem: &Element, out: &mut String) {
+    out.push_str(&elem.inner.borrow().text_content());
+    for child in elem.children() {
+        raw_text(&child, out);
+    }
+}
+
+struct Markdown {
+    out: String,
+}
+
+impl Markdown {
+    fn inline(&self, elem: &Element) -> String {
+        let tag = tag_of(elem);
+        if SKIP_TAGS.contains(&tag.as_str()) {
+            return String::new();
+        }
+        match tag.as_str() {
+            "br" => return "  \n".to_string(),
+            "img" => return format!("![{}]({})", attr_of(elem, "alt"), attr_of(elem, "src")),
+            "code" => {
+                let mut code = String::new();
+                raw_text(elem, &mut code);
+                return format!("`{}`", code);
+            }
+            _ => {}
+        }
+
+        let mut text = collapse_ws(&elem.inner.borrow().text_content());
+        for child in elem.children() {
+            text.push_str(&self.inline(&child));
+        }
+        match tag.as_str() {
+            "a" => format!("[{}]({})", text.trim(), attr_of(elem, "href")),
+            "em" | "i" => format!("*{}*", text),
+            "strong" | "b" => format!("**{}**", text),
+            "del" | "s" => format!("~~{}~~", text),
+            _ => text,
+        }
+    }
+
+    fn paragraph(&mut self, text: &str, prefix: &str) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        self.out.push_str(prefix);
+        self.out.push_str(text);
+        self.out.push_str("\n\n");
+    }
+
+    /// 输出块级内容，内联元素合并为段落
+    fn blocks(&mut self, elem: &Element) {
+        let mut pending = collapse_ws(&elem.inner.borrow().text_content());
+        for child in elem.children() {
+            let tag = tag_of(&child);
+            if BLOCK_TAGS.contains(&tag.as_str()) {
+                self.paragraph(&core::mem::take(&mut pending), "");
+                self.block(&child);
+            } else {
+                pending.push_str(&self.inline(&child));
+            }
+        }
+        self.paragraph(&pending, "");
+    }
+
+    fn block(&mut self, elem: &Element) {
+        let tag = tag_of(elem);
+        match tag.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = tag[1..].parse::<usize>().unwrap_or(1);
+                let prefix = format!("{} ", "#".repeat(level));
+                self.paragraph(&self.inline(elem), &prefix);
+            }
+            "p" => self.paragraph(&self.inline(elem), ""),
+            "hr" => self.out.push_str("---\n\n"),
+            "pre" => {
+                let code = elem.children().into_iter().find(|c| tag_of(c) == "code");
+                let lang = code.as_ref()
+                    .map(|c| attr_of(c, "class"))
+                    .and_then(|class| {
+                        class.split_whitespace()
+                            .find_map(|c| c.strip_prefix("language-").map(str::to_string))
+                    })
+                    .unwrap_or_default();
+                let mut text = String::new();
+                raw_text(elem, &mut text);
+                self.out.push_str(&format!("```{}\n{}", lang, text));
+                if !text.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out.push_str("```\n\n");
+            }
+            "ul" | "ol" => {
+                self.list(elem, 0);
+                self.out.push('\n');
+            }
+            "blockquote" => {
+                let mut inner = Markdown { out: String::new() };
+                inner.blocks(elem);
+                for line in inner.out.trim_end().lines() {
+                    self.out.push('>');
+                    if !line.is_empty() {
+                        self.out.push(' ');
+                        self.out.push_str(line);
+                    }
+                    self.out.push('\n');
+                }
+                self.out.push('\n');
+            }
+            "table" => self.table(elem),
+            _ if SKIP_TAGS.contains(&tag.as_str()) => {}
+            _ => self.blocks(elem),
+        }
+    }
+
+    fn list(&mut self, list: &Element, depth: usize) {
+        let ordered = tag_of(list) == "ol";
+        let indent = "  ".repeat(depth);
+        let mut index = 1;
+        for item in list.children() {
+            if tag_of(&item) != "li" {
+                continue;
+            }
+            let mut text = collapse_ws(&item.inner.borrow().text_content());
+            let mut nested = Vec::new();
+            for child in item.children() {
+                match tag_of(&child).as_str() {
+                    "ul" | "ol" => nested.push(child),
+                    _ => text.push_str(&self.inline(&child)),
+                }
+            }
+            let marker = if ordered { format!("{}.", index) } else { "-".to_string() };
+            self.out.push_str(&format!("{}{} {}\n", indent, marker, text.trim()));
+            for sub in nested {
+                self.list(&sub, depth + 1);
+            }
+            index += 1;
+        }
+    }
+
+    fn table(&mut self, table: &Element) {
+        fn rows(elem: &Element, out: &mut Vec<Element>) {
+            for child in elem.children() {
+                match tag_of(&child).as_str() {
+                    "tr" => out.push(child),
+                    "thead" | "tbody" | "tfoot" => rows(&child, out),
+                    _ => {}
+                }
+            }
+        }
+        let mut trs = Vec::new();
+        rows(table, &mut trs);
+        for (i, tr) in trs.iter().enumerate() {
+            let cells: Vec<String> = tr.children()
+                .iter()
+                .map(|cell| self.inline(cell).trim().replace('|', "\\|"))
+                .collect();
+            self.out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            if i == 0 {
+                self.out.push_str(&format!("|{}\n", " --- |".repeat(cells.len())));
+            }
+        }
+        self.out.push('\n');
+    }
+}
+
+impl Element {
+    /// 获取元素及全部后代的文本内容（未转义），与DOM的`textContent`相同
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let p = Element::new("p", "a < b ").add_with(Element::new("em", "and c"));
+    /// assert_eq!(p.text(), "a < b and c");
+    /// ```
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        raw_text(self, &mut text);
+        text
+    }
+
+    /// 获取文本内容，并将连续空白合并为一个空格、去掉首尾空白
+    pub fn text_normalized(&self) -> String {
+        collapse_ws(&self.text()).trim().to_string()
+    }
+
+    /// 规范化空白：合并文本中的连续空白，去掉块级元素边界处的空白
+    ///
+    /// `pre`、`code`、`textarea`和原始文本元素保持不变，
+    /// 需要保留其他元素时使用`normalize_whitespace_except`
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let div = Element::new("div", "\n  Hello,\n  ")
+    ///     .add_with(Element::new("b", "world"))
+    ///     .add_with(Element::new("", " !\n"))
+    ///     .add_with(Element::new("p", "  a  b  "));
+    /// div.normalize_whitespace();
+    /// assert_eq!(div.render(""), "<div>Hello, <b>world</b> !<p>a b</p></div>");
+    /// ```
+    pub fn normalize_whitespace(&self) -> &Self {
+        self.normalize_whitespace_except(PRESERVE_WS_TAGS)
+    }
+
+    /// 规范化空白，`preserve`中的元素及其后代保持不变
+    pub fn normalize_whitespace_except(&self, preserve: &[&str]) -> &Self {
+        normalize_ws(self, preserve);
+        self
+    }
+
+    /// 在子树的文本内容中将`from`全部替换为`to`，不修改标签和属性
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let footer = Element::new("footer", "Build {build}")
+    ///     .add_with(Element::new("a", "{build} notes").attrs(&[("title", "{build}")]));
+    /// footer.replace_text("{build}", "1.2 & up");
+    /// assert_eq!(footer.render(""), r#"<footer>Build 1.2 &amp; up<a title="{build}">1.2 &amp; up notes</a></footer>"#);
+    /// ```
+    pub fn replace_text(&self, from: &str, to: &str) -> &Self {
+        if !from.is_empty() {
+            map_text(self, &|text: &str| text.contains(from).then(|| text.replace(from, to)));
+        }
+        self
+    }
+
+    /// 用正则表达式替换子树的文本内容，`rep`的语法与`Regex::replace_all`相同，需要`regex`特性
+    #[cfg(feature = "regex")]
+    pub fn replace_text_regex(&self, re: &regex::Regex, rep: &str) -> &Self {
+        map_text(self, &|text: &str| match re.replace_all(text, rep) {
+            alloc::borrow::Cow::Owned(text) => Some(text),
+            alloc::borrow::Cow::Borrowed(_) => None,
+        });
+        self
+    }
+
+    /// 转换为Markdown文本
+    ///
+    /// 支持标题、段落、列表、链接、图片、强调、代码、引用和表格，其他元素只输出文本
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let div = Element::new("div", "")
+    ///     .add_with(Element::new("h2", "Title"))
+    ///     .add_with(
+    ///         Element::new("p", "See ")
+    ///             .add_with(Element::new("a", "docs").attrs(&[("href", "/docs")]))
+    ///     );
+    /// assert_eq!(div.to_markdown(), "## Title\n\nSee [docs](/docs)\n");
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut md = Markdown { out: String::new() };
+        if BLOCK_TAGS.contains(&tag_of(self).as_str()) {
+            md.block(self);
+        } else {
+            let text = md.inline(self);
+            md.paragraph(&text, "");
+        }
+        let mut out = md.out.trim_end().to_string();
+        out.push('\n');
+        out
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        let div = Element::new("div", "\n  Hello,")
+            .add_with(Element::new("b", " <world>\n"))
+            .add_with(Element::new("", "&amp;"))
+            .add_with(Element::new("span", "").pre(true).add_with(Element::new("", "!")));
+        assert_eq!(div.text(), "\n  Hello, <world>\n&amp;!");
+        assert_eq!(div.text_normalized(), "Hello, <world> &amp;!");
+    }
+
+    #[test]
+    fn test_normalize_whitespace() {
+        let ul = Element::new("ul", "\n")
+            .add_with(Element::new("li", " a "))
+            .add_with(Element::new("", "\n  "))
+            .add_with(Element::new("li", "").add_with(Element::new("code", "x  y")))
+            .add_with(Element::placeholder("p"));
+        let pre = Element::new("pre", " 1\n 2 ");
+        let root = Element::new("div", "").add_with(ul).add_with(pre);
+        root.cache_render(true);
+        root.render("");
+        root.normalize_whitespace();
+        assert_eq!(root.render(""), "<div><ul><li>a</li><li><code>x  y</code></li></ul><pre> 1\n 2 </pre></div>");
+        assert_eq!(root.children()[0].child_count(), 3);
+
+        root.normalize_whitespace_except(&[]);
+        assert_eq!(root.render(""), "<div><ul><li>a</li><li><code>x y</code></li></ul><pre>1 2</pre></div>");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_replace_text_regex() {
+        let p = Element::new("p", "call 555-1234 or ")
+            .add_with(Element::new("b", "555-9876"))
+            .add_with(Element::new("script", "f('555-0000')").rawtext(true));
+        let re = regex::Regex::new(r"\d{3}-\d{4}").unwrap();
+        p.replace_text_regex(&re, "[redacted]");
+        assert_eq!(p.render(""), "<p>call [redacted] or <b>[redacted]</b><script>f('555-0000')</script></p>");
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let list = Element::ul_from(["a", "b"]);
+        list.children()[1].add(Element::ol_from(["x"]));
+        let code = Element::new("code", "fn main() {}\n").attrs(&[("class", "language-rust")]);
+
+        let root = Element::new("div", "")
+            .add_with(Element::new("h1", "T"))
+            .add_with(Element::new("p", "").add_with(Element::new("em", "e")).add_with(Element::new("code", "a<b")))
+            .add_with(list)
+            .add_with(Element::new("pre", "").add_with(code))
+            .add_with(Element::new("blockquote", "").add_with(Element::new("p", "q")))
+            .add_with(Element::new("script", "ignored"));
+
+        assert_eq!(
+            root.to_markdown(),
+            "# T\n\n*e*`a<b`\n\n- a\n- b\n  1. x\n\n```rust\nfn main() {}\n```\n\n> q\n"
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! 渲染前的转换
+//!
+//! 转换在元素树的拷贝上执行，原树不变，多个转换按顺序组合
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::transform::{CspNonce, RewriteUrls, StripComments, Transform};
+//!
+//! let body = Element::new("body", "")
+//!     .add_with(Element::new("", "<!-- todo -->").pre(true))
+//!     .add_with(Element::new("img", "").attrs(&[("src", "/logo.png")]).onetag(true));
+//! let transforms: Vec<Box<dyn Transform>> = vec![
+//!     Box::new(StripComments),
+//!     Box::new(RewriteUrls(|url: &str| format!("https://cdn.example.com{}", url))),
+//!     Box::new(CspNonce("r4nd0m".into())),
+//! ];
+//! assert_eq!(
+//!     body.render_with_transforms("", &transforms),
+//!     r#"<body><img src="https://cdn.example.com/logo.png"></body>"#,
+//! );
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::Element;
+use crate::sanitize::URL_ATTRS;
+use crate::selector::attr_value;
+
+/// 修改元素树的转换
+///
+/// 闭包`Fn(&Element)`也实现了`Transform`
+pub trait Transform {
+    /// 修改以root为根的元素树
+    fn apply(&self, root: &Element);
+}
+
+impl<F: Fn(&Element)> Transform for F {
+    fn apply(&self, root: &Element) {
+        self(root)
+    }
+}
+
+/// 为内联`<script>`和`<style>`设置nonce，见`Element::apply_csp_nonce`
+pub struct CspNonce(pub String);
+
+impl Transform for CspNonce {
+    fn apply(&self, root: &Element) {
+        root.apply_csp_nonce(&self.0);
+    }
+}
+
+/// 在`method`不是`get`/`dialog`的`<form>`开头添加CSRF隐藏字段
+///
+/// 令牌通常每个请求不同，作为转换在拷贝上执行，不影响原树和渲染缓存
+///
+/// ```
+/// use htmlbuilder::{Element, assert_html_eq};
+/// use htmlbuilder::transform::{CsrfToken, Transform};
+///
+/// let form = Element::new("form", "").attrs(&[("method", "post")]).add_with(Element::new("button", "Log out"));
+/// let transforms: Vec<Box<dyn Transform>> = vec![Box::new(CsrfToken::new("_csrf", "t0k3n"))];
+/// assert_html_eq!(
+///     form.render_with_transforms("", &transforms),
+///     r#"<form method="post"><input type="hidden" name="_csrf" value="t0k3n"><button>Log out</button></form>"#,
+/// );
+/// assert_eq!(form.render(""), r#"<form method="post"><button>Log out</button></form>"#);
+/// ```
+pub struct CsrfToken {
+    /// 隐藏字段的name
+    pub name: String,
+    /// 令牌
+    pub token: String,
+}
+
+impl CsrfToken {
+    /// 字段名为`name`、值为`token`的CSRF字段
+    pub fn new(name: impl Into<String>, token: impl Into<String>) -> Self {
+        Self { name: name.into(), token: token.into() }
+    }
+}
+
+impl Transform for CsrfToken {
+    fn apply(&self, root: &Element) {
+        if root.inner.borrow().tag == "form"
+            && attr_value(root, "method").is_some_and(|m| {
+                let m = m.trim();
+                !m.eq_ignore_ascii_case("get") && !m.eq_ignore_ascii_case("dialog")
+            })
+        {
+            let hidden = crate::tags::input("hidden");
+            hidden.set_attr("name", self.name.as_str());
+            hidden.set_attr("value", self.token.as_str());
+            hidden.move_to_index(root, 0);
+        }
+        for child in root {
+            self.apply(&child);
+        }
+    }
+}
+
+/// 删除html注释
+pub struct StripComments;
+
+impl StripComments {
+    fn is_comment(elem: &Element) -> bool {
+        let inner = elem.inner.borrow();
+        inner.tag.is_empty() && inner.pre && inner.content.trim_start().starts_with("<!--")
+    }
+}
+
+impl Transform for StripComments {
+    fn apply(&self, root: &Element) {
+        let mut index = 0;
+        while let Some(child) = root.child(index) {
+            if Self::is_comment(&child) {
+                root.remove_child(index);
+            } else {
+                self.apply(&child);
+                index += 1;
+            }
+        }
+    }
+}
+
+/// 改写url属性（`href`、`src`等）
+pub struct RewriteUrls<F: Fn(&str) -> String>(pub F);
+
+impl<F: Fn(&str) -> String> Transform for RewriteUrls<F> {
+    fn apply(&self, root: &Element) {
+        let pre = root.inner.borrow().pre;
+        let urls: Vec<(Cow<'static, str>, String)> = root.inner.borrow().kws.iter()
+            .filter(|(k, _)| URL_ATTRS.contains(&k.to_ascii_lowercase().as_str()))
+            .map(|(k, v)| (k.clone(), if pre { v.clone() } else { crate::un_escape_ascii(v) }))
+            .collect();
+        for (name, url) in urls {
+            let url = (self.0)(&url);
+            if pre {
+                let mut inner = root.inner.borrow_mut();
+                inner.kws.insert(name, url);
+                inner.invalidate();
+            } else {
+                root.set_attr(name, url);
+            }
+        }
+        for child in root {
+            self.apply(&child);
+        }
+    }
+}
+
+/// 翻译可读文本：文本内容以及`title`、`alt`、`placeholder`、`aria-label`属性
+///
+/// 查找函数以去掉首尾空白的文本为键，返回None时保留原文；`pre`和原始文本元素（script/style）不翻译
+pub struct Translate<F: Fn(&str) -> Option<String>>(pub F);
+
+/// 可翻译的属性
+const TEXT_ATTRS: &[&str] = &["title", "alt", "placeholder", "aria-label"];
+
+impl<F: Fn(&str) -> Option<String>> Translate<F> {
+    /// 翻译文本，保留首尾空白
+    fn translate(&self, text: &str) -> Option<String> {
+        let key = text.trim();
+        if key.is_empty() {
+            return None;
+        }
+        let start = text.len() - text.trim_start().len();
+        let end = start + key.len();
+        (self.0)(key).map(|t| [&text[..start], t.as_str(), &text[end..]].concat())
+    }
+}
+
+impl<F: Fn(&str) -> Option<String>> Transform for Translate<F> {
+    fn apply(&self, root: &Element) {
+        let (pre, rawtext) = {
+            let inner = root.inner.borrow();
+            (inner.pre, inner.rawtext)
+        };
+        if pre || rawtext {
+            return;
+        }
+        let text = root.inner.borrow().text_content();
+        if let Some(text) = self.translate(&text) {
+            root.configcnt(text);
+        }
+        let attrs: Vec<(Cow<'static, str>, String)> = root.inner.borrow().kws.iter()
+            .filter(|(k, _)| TEXT_ATTRS.contains(&k.as_ref()))
+            .map(|(k, v)| (k.clone(), crate::un_escape_ascii(v)))
+            .collect();
+        for (name, value) in attrs {
+            if let Some(value) = self.translate(&value) {
+                root.set_attr(name, value);
+            }
+        }
+        for child in root {
+            self.apply(&child);
+        }
+    }
+}
+
+impl Element {
+    /// 渲染时用`lookup`翻译全部可读文本，原树不变，见`transform::Translate`
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let button = Element::new("button", " Save ").attrs(&[("title", "Save changes")]);
+    /// let zh = |key: &str| match key {
+    ///     "Save" => Some("保存".to_string()),
+    ///     "Save changes" => Some("保存修改".to_string()),
+    ///     _ => None,
+    /// };
+    /// assert_eq!(button.render_localized("", zh), r#"<button title="保存修改"> 保存 </button>"#);
+    /// ```
+    pub fn render_localized(&self, split_s: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+        let copy = self.deep_clone();
+        Translate(lookup).apply(&copy);
+        copy.render(split_s)
+    }
+
+    /// 在元素树的拷贝上依次执行转换后渲染，原树不变
+    pub fn render_with_transforms(&self, split_s: &str, transforms: &[Box<dyn Transform>]) -> String {
+        let copy = self.deep_clone();
+        for transform in transforms {
+            transform.apply(&copy);
+        }
+        copy.render(split_s)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_transforms() {
+        let list = Element::ul_from(["a", "b"]);
+        list.add(Element::new("", "<!--x-->").pre(true));
+        let body = Element::new("body", "").add_with(list);
+        let transforms: Vec<Box<dyn Transform>> = vec![
+            Box::new(StripComments),
+            Box::new(|root: &Element| root.set_attr("lang", "en")),
+        ];
+        assert_eq!(body.render_with_transforms("", &transforms), r#"<body lang="en"><ul><li>a</li><li>b</li></ul></body>"#);
+        assert_eq!(body.render(""), "<body><ul><li>a</li><li>b</li><!--x--></ul></body>");
+
+        let link = Element::new("a", "").attrs(&[("href", "/a?x=1&y=2"), ("title", "/t")]);
+        let rewrite: Vec<Box<dyn Transform>> = vec![Box::new(RewriteUrls(|url: &str| url.to_string() + "&v=3"))];
+        crate::assert_html_eq!(link.render_with_transforms("", &rewrite), r#"<a href="/a?x=1&amp;y=2&amp;v=3" title="/t"></a>"#);
+    }
+    #[test]
+    fn test_csrf_token() {
+        let page = Element::new("div", "")
+            .add_with(Element::new("form", "").attrs(&[("method", "POST")]))
+            .add_with(Element::new("form", "").attrs(&[("method", "get")]))
+            .add_with(Element::new("form", ""));
+        page.cache_render(true);
+        let plain = page.render("");
+        let transforms: Vec<Box<dyn Transform>> = vec![Box::new(CsrfToken::new("_csrf", "a\"b"))];
+        let html = page.render_with_transforms("", &transforms);
+
+        let forms = crate::parser::parse_fragment(&html).remove(0).children();
+        assert_eq!(forms[0].attr_list()[0].1, "POST");
+        let hidden = &forms[0].children()[0];
+        assert_eq!(hidden.attr_list(), [("name".into(), "_csrf".into()), ("type".into(), "hidden".into()), ("value".into(), "a\"b".into())]);
+        assert_eq!(forms[1].child_count(), 0);
+        assert_eq!(forms[2].child_count(), 0);
+        assert_eq!(page.render(""), plain);
+    }
+
+    #[test]
+    fn test_translate() {
+        let page = Element::new("div", "Hello")
+            .add_with(Element::new("", " World\n"))
+            .add_with(Element::new("img", "").attrs(&[("alt", "Logo"), ("src", "Logo")]).onetag(true))
+            .add_with(Element::new("pre", "Hello").pre(true))
+            .add_with(Element::new("script", "Hello").rawtext(true));
+        let fr = |key: &str| match key {
+            "Hello" => Some("Bonjour & salut".to_string()),
+            "World" => Some("Monde".to_string()),
+            "Logo" => Some("Le logo".to_string()),
+            _ => None,
+        };
+        crate::assert_html_eq!(
+            page.render_localized("", fr),
+            r#"<div>Bonjour &amp; salut Monde
+<img alt="Le logo" src="Logo"><pre>Hello</pre><script>Hello</script></div>"#,
+        );
+    }
+}
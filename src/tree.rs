@@ -0,0 +1,334 @@
+//! 兄弟节点、位置与子元素重排
+
+use alloc::collections::BTreeSet;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::Element;
+
+impl Element {
+    /// 父元素和当前元素在父元素中的位置
+    fn parent_and_index(&self) -> Option<(Element, usize)> {
+        let parent = self.parent()?;
+        let index = parent.children_ref().iter().position(|child| child == self)?;
+        Some((parent, index))
+    }
+
+    /// 在父元素的子元素列表中的位置，没有父元素时返回None
+    pub fn index_in_parent(&self) -> Option<usize> {
+        self.parent_and_index().map(|(_, index)| index)
+    }
+
+    /// 从根元素到当前元素的路径，每项是在父元素中的位置；根元素的路径为空
+    ///
+    /// 路径与`diff::Patch`中的path格式相同，可以保存下来，之后用`Element::at_path`重新找到元素
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b"]);
+    /// let body = Element::new("body", "").add_with(Element::new("h1", "")).add_with(ul.clone());
+    /// let b = ul.child(1).unwrap();
+    /// assert_eq!(b.path(), [1, 1]);
+    /// assert_eq!(Element::at_path(&body, &b.path()), Some(b));
+    /// ```
+    pub fn path(&self) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut current = self.clone();
+        while let Some((parent, index)) = current.parent_and_index() {
+            path.push(index);
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// 按`path`返回的路径从`root`查找元素，路径无效时返回None
+    pub fn at_path(root: &Element, path: &[usize]) -> Option<Element> {
+        path.iter().try_fold(root.clone(), |elem, &index| elem.child(index))
+    }
+
+    /// 下一个兄弟节点（包括文本节点）
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b"]);
+    /// let first = ul.child(0).unwrap();
+    /// assert_eq!(first.next_sibling().unwrap().content(), "b");
+    /// assert_eq!(first.prev_sibling(), None);
+    /// ```
+    pub fn next_sibling(&self) -> Option<Element> {
+        let (parent, index) = self.parent_and_index()?;
+        parent.child(index + 1)
+    }
+
+    /// 上一个兄弟节点（包括文本节点）
+    pub fn prev_sibling(&self) -> Option<Element> {
+        let (parent, index) = self.parent_and_index()?;
+        parent.child(index.checked_sub(1)?)
+    }
+
+    /// 按顺序遍历兄弟节点，不包括自身；没有父元素时为空
+    ///
+    /// 兄弟节点在调用时取出，迭代期间可以修改元素树
+    pub fn siblings(&self) -> impl Iterator<Item = Element> + use<> {
+        let me = self.clone();
+        let siblings: Vec<Element> = self.parent().map(|parent| parent.children()).unwrap_or_default();
+        siblings.into_iter().filter(move |sibling| *sibling != me)
+    }
+
+    /// 交换两个子元素的位置，下标越界时不修改并返回false
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b", "c"]);
+    /// assert!(ul.swap_children(0, 2));
+    /// assert_eq!(ul.render(""), "<ul><li>c</li><li>b</li><li>a</li></ul>");
+    /// ```
+    pub fn swap_children(&self, i: usize, j: usize) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        if i >= inner.children.len() || j >= inner.children.len() {
+            return false;
+        }
+        if i != j {
+            inner.children.swap(i, j);
+            inner.invalidate();
+        }
+        true
+    }
+
+    /// 将`from`处的子元素移动到`to`处，其余子元素顺序不变；下标越界时不修改并返回false
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b", "c"]);
+    /// assert!(ul.move_child(2, 0));
+    /// assert_eq!(ul.render(""), "<ul><li>c</li><li>a</li><li>b</li></ul>");
+    /// ```
+    pub fn move_child(&self, from: usize, to: usize) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        let len = inner.children.len();
+        if from >= len || to >= len {
+            return false;
+        }
+        if from < to {
+            inner.children[from..=to].rotate_left(1);
+        } else {
+            inner.children[to..=from].rotate_right(1);
+        }
+        if from != to {
+            inner.invalidate();
+        }
+        true
+    }
+
+    /// 从原父元素中移出，添加为`new_parent`的最后一个子元素，见`move_to_index`
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let draft = Element::ul_from(["a"]);
+    /// let published = Element::ul_from(["b"]);
+    /// draft.child(0).unwrap().move_to(&published);
+    /// assert_eq!(draft.render(""), "<ul></ul>");
+    /// assert_eq!(published.render(""), "<ul><li>b</li><li>a</li></ul>");
+    /// ```
+    pub fn move_to(&self, new_parent: &Element) -> bool {
+        let index = new_parent.child_count() - usize::from(self.parent().as_ref() == Some(new_parent));
+        self.move_to_index(new_parent, index)
+    }
+
+    /// 从原父元素中移出，插入为`new_parent`的第`index`个子元素，同时更新父指针
+    ///
+    /// `new_parent`是自身或自身的后代、是空元素（见`add`）、或`index`超出范围（移出后）时不修改并返回false
+    pub fn move_to_index(&self, new_parent: &Element, index: usize) -> bool {
+        if new_parent.inner.borrow().is_void() {
+            return false;
+        }
+        let mut ancestor = Some(new_parent.clone());
+        while let Some(elem) = ancestor {
+            if elem == *self {
+                return false;
+            }
+            ancestor = elem.parent();
+        }
+        let old = self.parent_and_index();
+        let len = new_parent.child_count() - usize::from(old.as_ref().is_some_and(|(parent, _)| parent == new_parent));
+        if index > len {
+            return false;
+        }
+
+        if let Some((parent, i)) = old {
+            let mut inner = parent.inner.borrow_mut();
+            inner.children.remove(i);
+            inner.invalidate();
+        }
+        if new_parent.inner.borrow().cache.is_some() {
+            self.cache_render(true);
+        }
+        let mut inner = new_parent.inner.borrow_mut();
+        self.inner.borrow_mut().parent = Some(Rc::downgrade(&new_parent.inner));
+        inner.children.insert(index, self.clone());
+        inner.invalidate();
+        true
+    }
+
+    /// 只保留使`f`返回true的子元素（包括文本节点），删除的子元素不再有父元素
+    ///
+    /// 调用`f`时不借用当前元素，`f`中可以访问父元素
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let article = Element::new("article", "")
+    ///     .add_with(Element::new("p", "text"))
+    ///     .add_with(Element::new("p", ""))
+    ///     .add_with(Element::new("img", "").attrs(&[("src", "pixel.gif")]).onetag(true));
+    /// article.retain_children(|child| child.tag() != "img" && !child.text().is_empty());
+    /// assert_eq!(article.render(""), "<article><p>text</p></article>");
+    /// ```
+    pub fn retain_children(&self, mut f: impl FnMut(&Element) -> bool) {
+        let children = self.children();
+        let removed: BTreeSet<_> = children.iter()
+            .filter(|child| !f(child))
+            .map(|child| Rc::as_ptr(&child.inner))
+            .collect();
+        if removed.is_empty() {
+            return;
+        }
+        {
+            let mut inner = self.inner.borrow_mut();
+            inner.children.retain(|child| !removed.contains(&Rc::as_ptr(&child.inner)));
+            inner.invalidate();
+        }
+        for child in children {
+            if removed.contains(&Rc::as_ptr(&child.inner)) && child.parent().as_ref() == Some(self) {
+                child.inner.borrow_mut().parent = None;
+            }
+        }
+    }
+
+    /// 按比较函数对子元素（包括文本节点）稳定排序
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let toc = Element::ul_from(["Rust", "C", "Go"]);
+    /// toc.sort_children_by(|a, b| a.text().cmp(&b.text()));
+    /// assert_eq!(toc.render(""), "<ul><li>C</li><li>Go</li><li>Rust</li></ul>");
+    /// ```
+    pub fn sort_children_by(&self, mut compare: impl FnMut(&Element, &Element) -> Ordering) {
+        // 排序时不借用当前元素，比较函数中可以访问父元素
+        let mut children = self.children();
+        children.sort_by(|a, b| compare(a, b));
+        let mut inner = self.inner.borrow_mut();
+        inner.children = children;
+        inner.invalidate();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::*;
+
+    #[test]
+    fn test_siblings() {
+        let body = Element::new("body", "");
+        body.add_all(["a", "b", "c"].map(|text| Element::new("section", text)));
+        let sections = body.children();
+
+        for section in &sections[..2] {
+            assert_eq!(section.next_sibling().unwrap().tag(), "section");
+        }
+        assert_eq!(sections[2].next_sibling(), None);
+        assert_eq!(sections[2].prev_sibling(), Some(sections[1].clone()));
+        assert_eq!(sections[1].siblings().collect::<Vec<_>>(), [sections[0].clone(), sections[2].clone()]);
+        assert_eq!(body.siblings().count(), 0);
+        assert_eq!(body.next_sibling(), None);
+    }
+
+    #[test]
+    fn test_path() {
+        let cell = Element::new("td", "x");
+        let table = Element::new("table", "")
+            .add_with(Element::new("thead", ""))
+            .add_with(Element::new("tbody", "").add_with(Element::new("tr", "")).add_with(Element::new("tr", "").add_with(cell.clone())));
+        assert_eq!(cell.index_in_parent(), Some(0));
+        assert_eq!(table.index_in_parent(), None);
+        assert_eq!(cell.path(), [1, 1, 0]);
+        assert!(table.path().is_empty());
+        assert_eq!(Element::at_path(&table, &[]), Some(table.clone()));
+        assert_eq!(Element::at_path(&table, &[1, 1, 0]), Some(cell));
+        assert_eq!(Element::at_path(&table, &[1, 2]), None);
+    }
+
+    #[test]
+    fn test_reorder() {
+        let tr = Element::new("tr", "");
+        tr.add_all(["1", "2", "3", "4"].map(|text| Element::new("td", text)));
+        tr.cache_render(true);
+        tr.render("");
+        let texts = |tr: &Element| tr.children().iter().map(Element::content).collect::<Vec<_>>().concat();
+
+        assert!(tr.move_child(0, 3));
+        assert_eq!(texts(&tr), "2341");
+        assert!(tr.move_child(3, 1));
+        assert_eq!(texts(&tr), "2134");
+        assert!(tr.swap_children(1, 3));
+        assert_eq!(tr.render(""), "<tr><td>2</td><td>4</td><td>3</td><td>1</td></tr>");
+        assert!(!tr.swap_children(0, 4));
+        assert!(!tr.move_child(4, 0));
+        assert_eq!(tr.child(3).unwrap().parent(), Some(tr.clone()));
+    }
+
+    #[test]
+    fn test_move_to() {
+        let item = Element::new("li", "x");
+        let old = Element::new("ul", "").add_with(item.clone());
+        let new = Element::ul_from(["a", "b"]);
+        let root = Element::new("div", "").add_with(old.clone()).add_with(new.clone());
+        root.cache_render(true);
+        root.render("");
+
+        assert!(item.move_to_index(&new, 1));
+        assert_eq!(root.render(""), "<div><ul></ul><ul><li>a</li><li>x</li><li>b</li></ul></div>");
+        assert_eq!(item.parent(), Some(new.clone()));
+        assert_eq!(old.child_count(), 0);
+
+        // 同一父元素中移动
+        assert!(item.move_to(&new));
+        assert_eq!(new.render(""), "<ul><li>a</li><li>b</li><li>x</li></ul>");
+        assert!(!item.move_to_index(&new, 3));
+        assert!(!new.move_to(&item));
+        assert!(!root.move_to(&new));
+        assert!(new.move_to_index(&root, 0));
+        assert_eq!(root.children(), [new, old]);
+    }
+
+    #[test]
+    fn test_retain_children() {
+        let ul = Element::ul_from(["a", "", "b", ""]);
+        ul.add(Element::new("", "text"));
+        let empty = ul.child(1).unwrap();
+        ul.retain_children(|child| child.parent().as_ref() == Some(&ul) && !child.content().is_empty() && child.tag() == "li");
+        assert_eq!(ul.render(""), "<ul><li>a</li><li>b</li></ul>");
+        assert_eq!(empty.parent(), None);
+        ul.retain_children(|_| true);
+        assert_eq!(ul.child_count(), 2);
+    }
+
+    #[test]
+    fn test_sort_children_by() {
+        let tbody = Element::new("tbody", "");
+        for (name, age) in [("bob", "30"), ("amy", "25"), ("cat", "30")] {
+            tbody.add(Element::new("tr", "").add_with(Element::new("td", name)).add_with(Element::new("td", age)));
+        }
+        let cell = |row: &Element, i: usize| row.child(i).unwrap().content();
+        tbody.sort_children_by(|a, b| cell(a, 1).cmp(&cell(b, 1)));
+        let names: Vec<String> = tbody.children().iter().map(|row| cell(row, 0)).collect();
+        assert_eq!(names, ["amy", "bob", "cat"]);
+        tbody.sort_children_by(|a, b| cell(b, 1).cmp(&cell(a, 1)));
+        let names: Vec<String> = tbody.children().iter().map(|row| cell(row, 0)).collect();
+        assert_eq!(names, ["bob", "cat", "amy"]);
+    }
+}
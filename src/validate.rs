@@ -0,0 +1,337 @@
+//! html结构检查
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::validate::IssueKind;
+//!
+//! let p = Element::new("p", "").add_with(Element::new("div", ""));
+//! let issues = p.validate();
+//! assert_eq!(issues[0].kind, IssueKind::BlockInParagraph);
+//! assert_eq!(issues[0].path, vec![0]);
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+use crate::{Element, is_void_tag, un_escape_ascii};
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IssueKind {
+    /// 空元素含有内容或子元素
+    VoidWithChildren,
+    /// `li`不在`ul`、`ol`、`menu`中
+    ListItemOutsideList,
+    /// `p`中含有块级元素
+    BlockInParagraph,
+    /// `head`中重复的`title`、`base`或`meta charset`，或`html`中重复的`head`、`body`
+    DuplicateChild,
+    /// 无效的标签名
+    InvalidTagName,
+    /// 已废弃的元素
+    ObsoleteElement,
+    /// 图片缺少`alt`属性
+    MissingAlt,
+    /// 表单控件没有关联的标签
+    MissingLabel,
+    /// 按钮或链接没有可访问名称
+    MissingName,
+    /// `html`缺少`lang`属性
+    MissingLang,
+    /// 标题跳级，如`h1`之后直接是`h3`
+    HeadingLevelSkip,
+    /// 重复的`id`
+    DuplicateId,
+}
+
+/// 检查发现的问题
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// 从检查的根元素开始逐层的子元素下标
+    pub path: Vec<usize>,
+    /// 问题类型
+    pub kind: IssueKind,
+    /// 说明
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.path, self.message)
+    }
+}
+
+/// 重复的`id`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateId {
+    /// `id`的值
+    pub id: String,
+    /// 使用该`id`的全部元素的路径，按文档顺序
+    pub paths: Vec<Vec<usize>>,
+}
+
+impl fmt::Display for DuplicateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "id `{}` is used by {} elements: ", self.id, self.paths.len())?;
+        let paths: Vec<String> = self.paths.iter().map(|p| format!("{:?}", p)).collect();
+        write!(f, "{}", paths.join(", "))
+    }
+}
+
+fn collect_ids(elem: &Element, path: &mut Vec<usize>, ids: &mut Vec<(String, Vec<usize>)>) {
+    {
+        let inner = elem.inner.borrow();
+        if let Some(id) = inner.kws.get("id") {
+            let id = if inner.pre { id.clone() } else { un_escape_ascii(id) };
+            ids.push((id, path.clone()));
+        }
+    }
+    for (i, child) in elem.children().iter().enumerate() {
+        path.push(i);
+        collect_ids(child, path, ids);
+        path.pop();
+    }
+}
+
+/// `p`中不允许的块级元素
+const BLOCK_TAGS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "details", "dialog", "div", "dl", "fieldset",
+    "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6", "header",
+    "hgroup", "hr", "main", "menu", "nav", "ol", "p", "pre", "section", "table", "ul",
+];
+
+const OBSOLETE_TAGS: &[&str] = &[
+    "acronym", "applet", "basefont", "bgsound", "big", "blink", "center", "dir", "font", "frame",
+    "frameset", "isindex", "keygen", "listing", "marquee", "menuitem", "multicol", "nextid",
+    "nobr", "noembed", "noframes", "plaintext", "rb", "rtc", "spacer", "strike", "tt", "xmp",
+];
+
+/// 标签名以字母开头，只含字母、数字和`-`
+fn valid_tag_name(tag: &str) -> bool {
+    tag.starts_with(|c: char| c.is_ascii_alphabetic())
+        && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn tag_of(elem: &Element) -> String {
+    elem.inner.borrow().tag.to_ascii_lowercase()
+}
+
+struct Validator {
+    path: Vec<usize>,
+    issues: Vec<ValidationIssue>,
+    /// 已出现的`id`及首次出现的路径
+    ids: BTreeMap<String, Vec<usize>>,
+}
+
+impl Validator {
+    fn report(&mut self, elem: &Element, kind: IssueKind, message: String) {
+        #[cfg(feature = "track-source")]
+        let message = crate::source::describe(elem, message);
+        #[cfg(not(feature = "track-source"))]
+        let _ = elem;
+        self.issues.push(ValidationIssue { path: self.path.clone(), kind, message });
+    }
+
+    fn check(&mut self, elem: &Element) {
+        let tag = tag_of(elem);
+        if tag.is_empty() {
+            // 文本节点
+            return;
+        }
+        let children = elem.children();
+
+        let id = {
+            let inner = elem.inner.borrow();
+            inner.kws.get("id").map(|id| if inner.pre { id.clone() } else { un_escape_ascii(id) })
+        };
+        if let Some(id) = id {
+            match self.ids.get(&id) {
+                Some(first) => {
+                    let message = format!("duplicate id `{}`, first used at {:?}", id, first);
+                    self.report(elem, IssueKind::DuplicateId, message);
+                }
+                None => {
+                    self.ids.insert(id, self.path.clone());
+                }
+            }
+        }
+        if !valid_tag_name(&tag) {
+            self.report(elem, IssueKind::InvalidTagName, format!("invalid tag name `{}`", tag));
+        }
+        if OBSOLETE_TAGS.contains(&tag.as_str()) {
+            self.report(elem, IssueKind::ObsoleteElement, format!("`<{}>` is obsolete", tag));
+        }
+        if is_void_tag(&tag) && (!children.is_empty() || !elem.inner.borrow().content.is_empty()) {
+            self.report(elem, IssueKind::VoidWithChildren, format!("void element `<{}>` has content", tag));
+        }
+        if tag == "li"
+            && let Some(parent) = elem.parent()
+            && !matches!(tag_of(&parent).as_str(), "ul" | "ol" | "menu")
+        {
+            self.report(
+                elem,
+                IssueKind::ListItemOutsideList,
+                format!("`<li>` inside `<{}>`", tag_of(&parent)),
+            );
+        }
+
+        let unique: &[&str] = match tag.as_str() {
+            "head" => &["title", "base"],
+            "html" => &["head", "body"],
+            _ => &[],
+        };
+        let mut seen: Vec<String> = Vec::new();
+        let mut charset = false;
+        for (i, child) in children.iter().enumerate() {
+            let child_tag = tag_of(child);
+            self.path.push(i);
+            if tag == "p" && BLOCK_TAGS.contains(&child_tag.as_str()) {
+                self.report(child, IssueKind::BlockInParagraph, format!("`<{}>` inside `<p>`", child_tag));
+            }
+            if unique.contains(&child_tag.as_str()) {
+                if seen.contains(&child_tag) {
+                    self.report(
+                        child,
+                        IssueKind::DuplicateChild,
+                        format!("duplicate `<{}>` in `<{}>`", child_tag, tag),
+                    );
+                }
+                seen.push(child_tag.clone());
+            }
+            if tag == "head" && child_tag == "meta" && child.inner.borrow().kws.contains_key("charset") {
+                if charset {
+                    self.report(child, IssueKind::DuplicateChild, "duplicate `<meta charset>` in `<head>`".to_string());
+                }
+                charset = true;
+            }
+            self.check(child);
+            self.path.pop();
+        }
+    }
+}
+
+impl Element {
+    /// 检查元素树是否符合html5的结构规则，返回发现的问题
+    ///
+    /// 检查空元素的内容、`li`的父元素、`p`中的块级元素、`head`与`html`中重复的子元素、
+    /// 标签名、已废弃的元素以及重复的`id`
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut validator = Validator { path: Vec::new(), issues: Vec::new(), ids: BTreeMap::new() };
+        validator.check(self);
+        validator.issues
+    }
+
+    /// 检查树中重复的`id`，按首次出现的顺序返回
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::new("ul", "");
+    /// for _ in 0..2 {
+    ///     ul.add(Element::new("li", "").attrs(&[("id", "item")]));
+    /// }
+    /// let dups = ul.check_unique_ids();
+    /// assert_eq!(dups[0].id, "item");
+    /// assert_eq!(dups[0].paths, vec![vec![0], vec![1]]);
+    /// ```
+    pub fn check_unique_ids(&self) -> Vec<DuplicateId> {
+        let mut ids = Vec::new();
+        collect_ids(self, &mut Vec::new(), &mut ids);
+
+        let mut index: BTreeMap<String, usize> = BTreeMap::new();
+        let mut groups: Vec<DuplicateId> = Vec::new();
+        for (id, path) in ids {
+            match index.get(&id) {
+                Some(&i) => groups[i].paths.push(path),
+                None => {
+                    index.insert(id.clone(), groups.len());
+                    groups.push(DuplicateId { id, paths: vec![path] });
+                }
+            }
+        }
+        groups.retain(|g| g.paths.len() > 1);
+        groups
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(elem: &Element) -> Vec<(Vec<usize>, IssueKind)> {
+        elem.validate().into_iter().map(|i| (i.path, i.kind)).collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let ok = Element::new("div", "")
+            .add_with(Element::ul_from(["a"]))
+            .add_with(Element::new("p", "").add_with(Element::new("em", "x")))
+            .add_with(Element::new("my-widget", ""));
+        assert_eq!(kinds(&ok), vec![]);
+
+        let head = Element::new("head", "")
+            .add_with(Element::new("title", "a"))
+            .add_with(Element::new("meta", "").attrs(&[("charset", "utf-8")]).onetag(true))
+            .add_with(Element::new("title", "b"))
+            .add_with(Element::new("meta", "").attrs(&[("charset", "utf-8")]).onetag(true));
+        let body = Element::new("body", "")
+            .add_with(Element::new("li", ""))
+            .add_with(Element::new("br", "x").onetag(true))
+            .add_with(Element::new("p", "").add_with(Element::new("ul", "")))
+            .add_with(Element::new("center", ""))
+            .add_with(Element::new("1a", ""));
+        let html = Element::new("html", "").add_with(head).add_with(body);
+
+        assert_eq!(kinds(&html), vec![
+            (vec![0, 2], IssueKind::DuplicateChild),
+            (vec![0, 3], IssueKind::DuplicateChild),
+            (vec![1, 0], IssueKind::ListItemOutsideList),
+            (vec![1, 1], IssueKind::VoidWithChildren),
+            (vec![1, 2, 0], IssueKind::BlockInParagraph),
+            (vec![1, 3], IssueKind::ObsoleteElement),
+            (vec![1, 4], IssueKind::InvalidTagName),
+        ]);
+        // 启用`track-source`时说明后附有创建位置
+        assert!(html.validate()[2].to_string().starts_with("[1, 0]: `<li>` inside `<body>`"));
+    }
+
+    #[test]
+    fn test_check_unique_ids() {
+        let root = Element::new("div", "").attrs(&[("id", "a")])
+            .add_with(Element::new("p", "").attrs(&[("id", "b")]))
+            .add_with(Element::new("p", "").add_with(Element::new("span", "").attrs(&[("id", "a")])))
+            .add_with(Element::new("p", "").attrs(&[("id", "b")]))
+            .add_with(Element::new("p", "").attrs(&[("id", "a")]));
+        let dups = root.check_unique_ids();
+        assert_eq!(dups, vec![
+            DuplicateId { id: "a".to_string(), paths: vec![vec![], vec![1, 0], vec![3]] },
+            DuplicateId { id: "b".to_string(), paths: vec![vec![0], vec![2]] },
+        ]);
+        assert_eq!(dups[1].to_string(), "id `b` is used by 2 elements: [0], [2]");
+
+        let issues = kinds(&root);
+        assert_eq!(issues, vec![
+            (vec![1, 0], IssueKind::DuplicateId),
+            (vec![2], IssueKind::DuplicateId),
+            (vec![3], IssueKind::DuplicateId),
+        ]);
+
+        // 与其他问题一起按文档顺序报告
+        let root = Element::new("div", "")
+            .add_with(Element::new("p", "").attrs(&[("id", "a")]))
+            .add_with(Element::new("center", "").attrs(&[("id", "a")]))
+            .add_with(Element::new("li", ""));
+        assert_eq!(kinds(&root), vec![
+            (vec![1], IssueKind::DuplicateId),
+            (vec![1], IssueKind::ObsoleteElement),
+            (vec![2], IssueKind::ListItemOutsideList),
+        ]);
+        assert!(root.validate()[0].message.starts_with("duplicate id `a`, first used at [0]"));
+    }
+}
@@ -0,0 +1,195 @@
+//! quick-xml互操作，需要`quick-xml`特性
+//!
+//! 读取XML（配置文件、SVG等）为元素树，修改后写回
+//!
+//! ```
+//! use htmlbuilder::Element;
+//!
+//! let svg = Element::from_xml(r#"<svg><circle/><!-- dot --></svg>"#).unwrap();
+//! svg.children()[0].set_attr("fill", "red");
+//!
+//! let mut writer = quick_xml::Writer::new(Vec::new());
+//! svg.write_xml(&mut writer).unwrap();
+//! let xml = String::from_utf8(writer.into_inner()).unwrap();
+//! assert_eq!(xml, r#"<svg><circle fill="red"/><!-- dot --></svg>"#);
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use std::io::{self, BufRead, Write};
+
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::{Reader, Writer, XmlVersion};
+
+use crate::Element;
+
+/// XML读取错误
+#[derive(Debug)]
+pub enum XmlError {
+    /// XML格式错误
+    Parse(quick_xml::Error),
+    /// 没有根元素
+    NoRoot,
+    /// 元素没有闭合
+    Unclosed(String),
+}
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XmlError::Parse(e) => write!(f, "invalid xml: {}", e),
+            XmlError::NoRoot => write!(f, "xml has no root element"),
+            XmlError::Unclosed(tag) => write!(f, "element `<{}>` is not closed", tag),
+        }
+    }
+}
+
+impl core::error::Error for XmlError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            XmlError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<quick_xml::Error> for XmlError {
+    fn from(e: quick_xml::Error) -> Self {
+        XmlError::Parse(e)
+    }
+}
+
+fn start_element(start: &BytesStart<'_>) -> Result<Element, XmlError> {
+    let elem = Element::new(start.name().as_ref(), "");
+    for attr in start.attributes() {
+        let attr = attr.map_err(quick_xml::Error::from)?;
+        let value = attr.normalized_value(XmlVersion::Implicit1_0)?;
+        elem.set_attr(String::from(attr.key.as_ref()), value);
+    }
+    Ok(elem)
+}
+
+/// 实体引用对应的文本，未知的实体保持原样
+fn resolve_ref(name: &str) -> String {
+    if let Some(c) = name.strip_prefix('#').and_then(|n| match n.strip_prefix('x') {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => n.parse().ok(),
+    }).and_then(char::from_u32)
+    {
+        return c.to_string();
+    }
+    match resolve_predefined_entity(name) {
+        Some(text) => text.to_string(),
+        None => alloc::format!("&{};", name),
+    }
+}
+
+impl Element {
+    /// 读取XML，返回根元素；声明、处理指令和根元素之外的内容被忽略
+    pub fn from_xml_reader<R: BufRead>(reader: &mut Reader<R>) -> Result<Element, XmlError> {
+        let mut stack: Vec<Element> = Vec::new();
+        let mut root = None;
+        let mut buf = Vec::new();
+        // 添加节点，没有父元素时作为根元素
+        let mut push = |stack: &mut Vec<Element>, node: Element| match stack.last() {
+            Some(parent) => {
+                parent.push_child(node);
+            }
+            None if root.is_none() && !node.inner.borrow().tag.is_empty() => root = Some(node),
+            None => {}
+        };
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(start) => stack.push(start_element(&start)?),
+                Event::End(_) => {
+                    if let Some(elem) = stack.pop() {
+                        push(&mut stack, elem);
+                    }
+                }
+                Event::Empty(start) => push(&mut stack, start_element(&start)?),
+                Event::Text(text) => push(&mut stack, Element::new("", text.xml10_content())),
+                Event::CData(cdata) => push(&mut stack, Element::new("", cdata.xml10_content())),
+                Event::GeneralRef(r) => push(&mut stack, Element::new("", resolve_ref(&r.xml10_content()))),
+                Event::Comment(comment) => {
+                    let node = Element::new("", "").pre(true);
+                    node.configcnt(alloc::format!("<!--{}-->", comment.xml10_content()));
+                    push(&mut stack, node);
+                }
+                Event::Decl(_) | Event::PI(_) | Event::DocType(_) => {}
+                Event::Eof => break,
+            }
+            buf.clear();
+        }
+        if let Some(elem) = stack.first() {
+            return Err(XmlError::Unclosed(elem.tag()));
+        }
+        root.ok_or(XmlError::NoRoot)
+    }
+
+    /// 解析XML字符串，见`from_xml_reader`
+    pub fn from_xml(xml: &str) -> Result<Element, XmlError> {
+        Self::from_xml_reader(&mut Reader::from_str(xml))
+    }
+
+    /// 将元素树写为XML事件，没有内容和子元素的元素写为空元素`<tag/>`
+    pub fn write_xml<W: Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+        let inner = self.inner.borrow();
+        if inner.tag.is_empty() {
+            // 内容已转义或为原样输出的标记
+            return writer.write_event(Event::Text(BytesText::from_escaped(&*inner.content)));
+        }
+
+        let attrs: Vec<(&str, String)> = inner.kws.iter()
+            .map(|(k, v)| (k.as_ref(), if inner.pre { v.clone() } else { crate::un_escape_ascii(v) }))
+            .collect();
+        let start = BytesStart::new(inner.tag.as_str())
+            .with_attributes(attrs.iter().map(|(k, v)| (*k, v.as_str())));
+        if inner.content.is_empty() && inner.children.is_empty() {
+            return writer.write_event(Event::Empty(start));
+        }
+        writer.write_event(Event::Start(start))?;
+        if inner.rawtext {
+            writer.write_event(Event::Text(BytesText::new(&inner.content)))?;
+        } else if !inner.content.is_empty() {
+            writer.write_event(Event::Text(BytesText::from_escaped(&*inner.content)))?;
+        }
+        for child in &inner.children {
+            child.write_xml(writer)?;
+        }
+        writer.write_event(Event::End(BytesEnd::new(inner.tag.as_str())))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xml() {
+        let xml = "<?xml version=\"1.0\"?>\n<config a=\"1 &amp; 2\">\n  <name>x &lt; y &#65;</name>\n  <raw><![CDATA[<b>]]></raw>\n</config>\n";
+        let config = Element::from_xml(xml).unwrap();
+        assert_eq!(config.attr_list(), [("a".to_string(), "1 & 2".to_string())]);
+        assert_eq!(config.children()[1].text(), "x < y A");
+        assert_eq!(config.children()[3].text(), "<b>");
+
+        let mut writer = Writer::new(Vec::new());
+        config.write_xml(&mut writer).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "<config a=\"1 &amp; 2\">\n  <name>x &lt; y A</name>\n  <raw>&lt;b&gt;</raw>\n</config>",
+        );
+
+        assert!(matches!(Element::from_xml("<a><b></a>"), Err(XmlError::Parse(_))));
+        assert!(matches!(Element::from_xml("<a>"), Err(XmlError::Unclosed(tag)) if tag == "a"));
+        assert!(matches!(Element::from_xml("<!-- x -->"), Err(XmlError::NoRoot)));
+
+        // XML中没有html空元素，`<link>`可以有内容
+        let rss = Element::from_xml("<rss><channel><link>https://x</link></channel></rss>").unwrap();
+        assert_eq!(rss.children()[0].children()[0].text(), "https://x");
+        let feed = crate::feed::Feed::new("Blog", "https://example.com/");
+        assert!(Element::from_xml(&feed.render_rss()).is_ok());
+    }
+}
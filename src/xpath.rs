@@ -0,0 +1,458 @@
+//! XPath子集
+//!
+//! 支持：
+//!
+//! - 绝对路径`/html/body`、任意深度`//p`、相对路径`div/p`
+//! - 名称测试`div`和`*`，`.`（自身）与`..`（父元素）
+//! - 位置谓词`[1]`、`[last()]`
+//! - 属性谓词`[@id]`、`[@id='main']`、`[@id!='main']`、`[contains(@class,'x')]`、`[starts-with(@href,'/')]`
+//!
+//! 结果只包含元素，不包含文本和属性节点
+//!
+//! ```
+//! use htmlbuilder::Element;
+//! use htmlbuilder::xpath::XPath;
+//!
+//! let main = Element::new("div", "").attrs(&[("id", "main")])
+//!     .add_with(Element::new("p", "first"))
+//!     .add_with(Element::new("p", "second"));
+//! let body = Element::new("body", "").add_with(Element::new("p", "outside")).add_with(main);
+//!
+//! let path = XPath::parse("//div[@id='main']/p[1]").unwrap();
+//! let found = path.evaluate(&body);
+//! assert_eq!(found.len(), 1);
+//! assert_eq!(found[0].content(), "first");
+//! ```
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Element;
+use crate::selector::attr_value;
+
+/// XPath解析错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XPathError {
+    /// 表达式不完整
+    UnexpectedEnd,
+    /// 位置（字符序号）处出现意外的字符
+    Unexpected(usize, char),
+    /// 不支持的语法，如轴和函数
+    Unsupported(String),
+}
+
+impl fmt::Display for XPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XPathError::UnexpectedEnd => write!(f, "unexpected end of xpath"),
+            XPathError::Unexpected(pos, c) => write!(f, "unexpected `{}` at position {} in xpath", c, pos),
+            XPathError::Unsupported(what) => write!(f, "unsupported xpath syntax `{}`", what),
+        }
+    }
+}
+
+impl core::error::Error for XPathError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    /// `/`
+    Child,
+    /// `//`
+    Descendant,
+    /// `.`
+    SelfNode,
+    /// `..`
+    Parent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Test {
+    Name(String),
+    /// `*`
+    AnyElement,
+    /// `.`和`..`，包括文档节点
+    AnyNode,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    Position(usize),
+    Last,
+    Has(String),
+    Equals(String, String),
+    NotEquals(String, String),
+    Contains(String, String),
+    StartsWith(String, String),
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    axis: Axis,
+    test: Test,
+    predicates: Vec<Predicate>,
+}
+
+/// 解析后的XPath表达式，可以重复使用
+#[derive(Debug, Clone)]
+pub struct XPath {
+    absolute: bool,
+    steps: Vec<Step>,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        let len = s.chars().count();
+        if self.chars.len() >= self.pos + len && s.chars().eq(self.chars[self.pos..self.pos + len].iter().copied()) {
+            self.pos += len;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self) -> XPathError {
+        match self.peek() {
+            Some(c) => XPathError::Unexpected(self.pos, c),
+            None => XPathError::UnexpectedEnd,
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), XPathError> {
+        self.skip_ws();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn name(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn literal(&mut self) -> Result<String, XPathError> {
+        self.skip_ws();
+        let quote = match self.peek() {
+            Some(q @ ('\'' | '"')) => q,
+            _ => return Err(self.error()),
+        };
+        self.pos += 1;
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != quote) {
+            self.pos += 1;
+        }
+        if self.peek().is_none() {
+            return Err(XPathError::UnexpectedEnd);
+        }
+        self.pos += 1;
+        Ok(self.chars[start..self.pos - 1].iter().collect())
+    }
+
+    fn attr_name(&mut self) -> Result<String, XPathError> {
+        self.expect('@')?;
+        let name = self.name();
+        if name.is_empty() { Err(self.error()) } else { Ok(name) }
+    }
+
+    fn predicate(&mut self) -> Result<Predicate, XPathError> {
+        self.skip_ws();
+        let predicate = match self.peek() {
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+                let digits: String = self.chars[start..self.pos].iter().collect();
+                match digits.parse() {
+                    Ok(position) if position > 0 => Predicate::Position(position),
+                    _ => return Err(XPathError::Unsupported(digits)),
+                }
+            }
+            Some('@') => {
+                let name = self.attr_name()?;
+                self.skip_ws();
+                if self.eat("!=") {
+                    Predicate::NotEquals(name, self.literal()?)
+                } else if self.eat("=") {
+                    Predicate::Equals(name, self.literal()?)
+                } else {
+                    Predicate::Has(name)
+                }
+            }
+            _ => {
+                let function = self.name();
+                self.expect('(')?;
+                match function.as_str() {
+                    "last" => {
+                        self.expect(')')?;
+                        Predicate::Last
+                    }
+                    "contains" | "starts-with" => {
+                        let name = self.attr_name()?;
+                        self.expect(',')?;
+                        let value = self.literal()?;
+                        self.expect(')')?;
+                        if function == "contains" {
+                            Predicate::Contains(name, value)
+                        } else {
+                            Predicate::StartsWith(name, value)
+                        }
+                    }
+                    _ => return Err(XPathError::Unsupported(function + "()")),
+                }
+            }
+        };
+        self.expect(']')?;
+        Ok(predicate)
+    }
+
+    fn step(&mut self, axis: Axis) -> Result<Step, XPathError> {
+        let (axis, test) = if self.eat("..") {
+            (Axis::Parent, Test::AnyNode)
+        } else if self.eat(".") {
+            (Axis::SelfNode, Test::AnyNode)
+        } else if self.eat("*") {
+            (axis, Test::AnyElement)
+        } else {
+            let name = self.name();
+            if name.is_empty() {
+                return Err(self.error());
+            }
+            if self.eat("::") || self.peek() == Some('(') {
+                return Err(XPathError::Unsupported(name));
+            }
+            (axis, Test::Name(name.to_ascii_lowercase()))
+        };
+        let mut predicates = Vec::new();
+        while self.peek() == Some('[') {
+            self.pos += 1;
+            predicates.push(self.predicate()?);
+        }
+        Ok(Step { axis, test, predicates })
+    }
+}
+
+/// 求值时的节点，文档节点是根元素的父节点
+#[derive(Clone)]
+enum Node {
+    Document(Element),
+    Element(Element),
+}
+
+impl Node {
+    fn same(&self, other: &Node) -> bool {
+        match (self, other) {
+            (Node::Document(a), Node::Document(b)) | (Node::Element(a), Node::Element(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    fn children(&self) -> Vec<Node> {
+        match self {
+            Node::Document(root) => vec![Node::Element(root.clone())],
+            Node::Element(elem) => elem.children().into_iter()
+                .filter(|child| !child.inner.borrow().tag.is_empty())
+                .map(Node::Element)
+                .collect(),
+        }
+    }
+
+    fn descendants_or_self(&self, out: &mut Vec<Node>) {
+        out.push(self.clone());
+        for child in self.children() {
+            child.descendants_or_self(out);
+        }
+    }
+
+    fn parent(&self) -> Option<Node> {
+        match self {
+            Node::Document(_) => None,
+            Node::Element(elem) => Some(elem.parent().map_or_else(|| Node::Document(elem.clone()), Node::Element)),
+        }
+    }
+}
+
+impl Test {
+    fn matches(&self, node: &Node) -> bool {
+        match (self, node) {
+            (Test::AnyNode, _) => true,
+            (_, Node::Document(_)) => false,
+            (Test::AnyElement, Node::Element(_)) => true,
+            (Test::Name(name), Node::Element(elem)) => elem.inner.borrow().tag.eq_ignore_ascii_case(name),
+        }
+    }
+}
+
+impl Predicate {
+    fn matches(&self, node: &Node, position: usize, last: usize) -> bool {
+        let attr = |name: &str| match node {
+            Node::Element(elem) => attr_value(elem, name),
+            Node::Document(_) => None,
+        };
+        match self {
+            Predicate::Position(n) => position == *n,
+            Predicate::Last => position == last,
+            Predicate::Has(name) => attr(name).is_some(),
+            Predicate::Equals(name, value) => attr(name).as_deref() == Some(value.as_str()),
+            Predicate::NotEquals(name, value) => attr(name).is_some_and(|v| v != *value),
+            Predicate::Contains(name, value) => attr(name).is_some_and(|v| v.contains(value.as_str())),
+            Predicate::StartsWith(name, value) => attr(name).is_some_and(|v| v.starts_with(value.as_str())),
+        }
+    }
+}
+
+impl Step {
+    fn apply(&self, context: &[Node]) -> Vec<Node> {
+        let mut out: Vec<Node> = Vec::new();
+        for node in context {
+            // 位置谓词相对于每个节点的候选列表
+            let groups = match self.axis {
+                Axis::Child => vec![node.children()],
+                Axis::Descendant => {
+                    let mut nodes = Vec::new();
+                    node.descendants_or_self(&mut nodes);
+                    nodes.iter().map(Node::children).collect()
+                }
+                Axis::SelfNode => vec![vec![node.clone()]],
+                Axis::Parent => vec![node.parent().into_iter().collect()],
+            };
+            for group in groups {
+                let mut nodes: Vec<Node> = group.into_iter().filter(|n| self.test.matches(n)).collect();
+                for predicate in &self.predicates {
+                    let last = nodes.len();
+                    nodes = nodes.into_iter()
+                        .enumerate()
+                        .filter(|(i, n)| predicate.matches(n, i + 1, last))
+                        .map(|(_, n)| n)
+                        .collect();
+                }
+                for n in nodes {
+                    if !out.iter().any(|o| o.same(&n)) {
+                        out.push(n);
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+impl XPath {
+    /// 解析表达式
+    pub fn parse(expr: &str) -> Result<Self, XPathError> {
+        let mut parser = Parser { chars: expr.trim().chars().collect(), pos: 0 };
+        let mut steps = Vec::new();
+        let absolute = parser.peek() == Some('/');
+        let mut axis = Axis::Child;
+        if parser.eat("//") {
+            axis = Axis::Descendant;
+        } else if parser.eat("/") && parser.peek().is_none() {
+            return Ok(Self { absolute, steps });
+        }
+        loop {
+            steps.push(parser.step(axis)?);
+            if parser.eat("//") {
+                axis = Axis::Descendant;
+            } else if parser.eat("/") {
+                axis = Axis::Child;
+            } else if parser.peek().is_none() {
+                return Ok(Self { absolute, steps });
+            } else {
+                return Err(parser.error());
+            }
+        }
+    }
+
+    /// 以`context`为上下文求值，按文档顺序返回匹配的元素；绝对路径从`context`所在树的根开始
+    pub fn evaluate(&self, context: &Element) -> Vec<Element> {
+        let mut nodes = if self.absolute {
+            let mut root = context.clone();
+            while let Some(parent) = root.parent() {
+                root = parent;
+            }
+            vec![Node::Document(root)]
+        } else {
+            vec![Node::Element(context.clone())]
+        };
+        for step in &self.steps {
+            nodes = step.apply(&nodes);
+        }
+        nodes.into_iter()
+            .filter_map(|node| match node {
+                Node::Element(elem) => Some(elem),
+                Node::Document(_) => None,
+            })
+            .collect()
+    }
+}
+
+impl Element {
+    /// 用XPath子集查找元素，支持的语法见`xpath`模块
+    ///
+    /// ```
+    /// # use htmlbuilder::Element;
+    /// let ul = Element::ul_from(["a", "b", "c"]);
+    /// let last = ul.xpath("li[last()]").unwrap();
+    /// assert_eq!(last[0].content(), "c");
+    /// ```
+    pub fn xpath(&self, expr: &str) -> Result<Vec<Element>, XPathError> {
+        Ok(XPath::parse(expr)?.evaluate(self))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_xpath() {
+        let list = Element::ul_from(["1", "2"]).attrs(&[("class", "nav main")]);
+        let link = Element::new("a", "x").attrs(&[("href", "/home")]);
+        let section = Element::new("section", "").add_with(Element::new("p", "a")).add_with(link.clone());
+        let body = Element::new("body", "").add_with(list.clone()).add_with(section.clone());
+        let html = Element::new("html", "").add_with(body.clone());
+        let names = |expr: &str, ctx: &Element| -> Vec<String> {
+            ctx.xpath(expr).unwrap().iter().map(|e| e.tag() + &e.content()).collect()
+        };
+
+        assert_eq!(names("/html/body/*", &link), ["ul", "section"]);
+        assert_eq!(names("//li", &html), ["li1", "li2"]);
+        assert_eq!(names("//*[1]", &html), ["html", "body", "ul", "li1", "pa"]);
+        assert_eq!(names("//ul[contains(@class,'main')]/li[2]", &html), ["li2"]);
+        assert_eq!(names("//*[starts-with(@href, '/')]/..", &html), ["section"]);
+        assert_eq!(names("../section//a[@href!='/']", &list), ["ax"]);
+        assert_eq!(names(".", &link), ["ax"]);
+        assert_eq!(names("/", &link), Vec::<String>::new());
+        assert_eq!(names("p[@id]", &section), Vec::<String>::new());
+
+        assert_eq!(XPath::parse("//p[").unwrap_err(), XPathError::UnexpectedEnd);
+        assert_eq!(XPath::parse("p]").unwrap_err(), XPathError::Unexpected(1, ']'));
+        assert_eq!(XPath::parse("ancestor::p").unwrap_err(), XPathError::Unsupported("ancestor".to_string()));
+        assert_eq!(XPath::parse("p[text()]").unwrap_err(), XPathError::Unsupported("text()".to_string()));
+    }
+}